@@ -0,0 +1,322 @@
+// Copyright (c) 2025 Xiaomi Corporation
+// SPDX-License-Identifier: Apache-2.0
+
+//! aes-gcm example for NuttX
+//!
+//! Checks AES-128-GCM and AES-256-GCM against the test cases of the GCM
+//! specification (McGrew and Viega, as used by NIST), measures throughput and
+//! reports which AES and GHASH backends are compiled in.
+
+use aes_gcm::aead::consts::U12;
+use aes_gcm::aead::{Aead, AeadInPlace, KeyInit, Payload};
+use aes_gcm::{Aes128Gcm, Aes256Gcm, Key, Nonce, Tag};
+use hex_literal::hex;
+use std::time::Instant;
+
+const THROUGHPUT_BUFFER: usize = 16 * 1024;
+const THROUGHPUT_ITERATIONS: u32 = 16;
+
+struct TestVector {
+    name: &'static str,
+    key: &'static [u8],
+    iv: [u8; 12],
+    aad: &'static [u8],
+    plaintext: &'static [u8],
+    ciphertext: &'static [u8],
+    tag: [u8; 16],
+}
+
+const PLAINTEXT: &[u8] = &hex!(
+    "d9313225f88406e5a55909c5aff5269a86a7a9531534f7da2e4c303d8a318a72"
+    "1c3c0c95956809532fcf0e2449a6b525b16aedf5aa0de657ba637b39"
+);
+const AAD: &[u8] = &hex!("feedfacedeadbeeffeedfacedeadbeefabaddad2");
+const IV: [u8; 12] = hex!("cafebabefacedbaddecaf888");
+
+const TEST_VECTORS: &[TestVector] = &[
+    TestVector {
+        name: "Test Case 1 (AES-128, empty)",
+        key: &[0; 16],
+        iv: [0; 12],
+        aad: &[],
+        plaintext: &[],
+        ciphertext: &[],
+        tag: hex!("58e2fccefa7e3061367f1d57a4e7455a"),
+    },
+    TestVector {
+        name: "Test Case 2 (AES-128, zero block)",
+        key: &[0; 16],
+        iv: [0; 12],
+        aad: &[],
+        plaintext: &[0; 16],
+        ciphertext: &hex!("0388dace60b6a392f328c2b971b2fe78"),
+        tag: hex!("ab6e47d42cec13bdf53a67b21257bddf"),
+    },
+    TestVector {
+        name: "Test Case 4 (AES-128, with AAD)",
+        key: &hex!("feffe9928665731c6d6a8f9467308308"),
+        iv: IV,
+        aad: AAD,
+        plaintext: PLAINTEXT,
+        ciphertext: &hex!(
+            "42831ec2217774244b7221b784d0d49ce3aa212f2c02a4e035c17e2329aca12e"
+            "21d514b25466931c7d8f6a5aac84aa051ba30b396a0aac973d58e091"
+        ),
+        tag: hex!("5bc94fbc3221a5db94fae95ae7121a47"),
+    },
+    TestVector {
+        name: "Test Case 16 (AES-256, with AAD)",
+        key: &hex!("feffe9928665731c6d6a8f9467308308feffe9928665731c6d6a8f9467308308"),
+        iv: IV,
+        aad: AAD,
+        plaintext: PLAINTEXT,
+        ciphertext: &hex!(
+            "522dc1f099567d07f47f37a32a84427d643a8cdcbfe5c0c97598a2bd2555d1aa"
+            "8cb08e48590dbb3da7b08b1056828838c5f61e6393ba7a0abcc9f662"
+        ),
+        tag: hex!("76fc6ece0f4e1768cddf8853bb2d551b"),
+    },
+];
+
+fn seal(key: &[u8], nonce: &Nonce<U12>, payload: Payload) -> Vec<u8> {
+    match key.len() {
+        16 => Aes128Gcm::new(Key::<Aes128Gcm>::from_slice(key)).encrypt(nonce, payload),
+        32 => Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(key)).encrypt(nonce, payload),
+        _ => unreachable!(),
+    }
+    .unwrap()
+}
+
+fn open(key: &[u8], nonce: &Nonce<U12>, payload: Payload) -> Result<Vec<u8>, aes_gcm::Error> {
+    match key.len() {
+        16 => Aes128Gcm::new(Key::<Aes128Gcm>::from_slice(key)).decrypt(nonce, payload),
+        32 => Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(key)).decrypt(nonce, payload),
+        _ => unreachable!(),
+    }
+}
+
+fn test_vectors() {
+    println!("Testing GCM specification test vectors");
+
+    for vector in TEST_VECTORS {
+        println!("  {}", vector.name);
+        let nonce = Nonce::from_slice(&vector.iv);
+
+        // The output of encrypt() is the ciphertext followed by the tag
+        let sealed = seal(
+            vector.key,
+            nonce,
+            Payload {
+                msg: vector.plaintext,
+                aad: vector.aad,
+            },
+        );
+        let (ciphertext, tag) = sealed.split_at(vector.plaintext.len());
+        assert_eq!(ciphertext, vector.ciphertext);
+        assert_eq!(tag, vector.tag);
+
+        let opened = open(
+            vector.key,
+            nonce,
+            Payload {
+                msg: &sealed,
+                aad: vector.aad,
+            },
+        )
+        .unwrap();
+        assert_eq!(opened, vector.plaintext);
+    }
+
+    println!("GCM specification test vectors passed");
+}
+
+fn test_tampering() {
+    println!("Testing authentication failures");
+
+    let vector = &TEST_VECTORS[2];
+    let nonce = Nonce::from_slice(&vector.iv);
+    let sealed = seal(
+        vector.key,
+        nonce,
+        Payload {
+            msg: vector.plaintext,
+            aad: vector.aad,
+        },
+    );
+
+    // Flipping any single bit of the ciphertext or tag is detected
+    for bit in [0, 7, 8 * sealed.len() / 2, 8 * sealed.len() - 1] {
+        let mut tampered = sealed.clone();
+        tampered[bit / 8] ^= 1 << (bit % 8);
+        let payload = Payload {
+            msg: &tampered,
+            aad: vector.aad,
+        };
+        assert!(open(vector.key, nonce, payload).is_err());
+    }
+
+    // So is a different AAD, nonce or key
+    let payload = |aad| Payload { msg: &sealed, aad };
+    assert!(open(vector.key, nonce, payload(b"other")).is_err());
+    assert!(open(vector.key, Nonce::from_slice(&[0; 12]), payload(vector.aad)).is_err());
+    assert!(open(&[0; 16], nonce, payload(vector.aad)).is_err());
+
+    println!("Authentication failure tests passed");
+}
+
+fn test_in_place() {
+    println!("Testing in place encryption with detached tag");
+
+    let vector = &TEST_VECTORS[3];
+    let cipher = Aes256Gcm::new_from_slice(vector.key).unwrap();
+    let nonce = Nonce::from_slice(&vector.iv);
+
+    // A telemetry frame encrypted without allocating
+    let mut frame = [0u8; 60];
+    frame.copy_from_slice(vector.plaintext);
+    let tag = cipher
+        .encrypt_in_place_detached(nonce, vector.aad, &mut frame)
+        .unwrap();
+    assert_eq!(frame[..], *vector.ciphertext);
+    assert_eq!(tag[..], vector.tag);
+
+    cipher
+        .decrypt_in_place_detached(nonce, vector.aad, &mut frame, &tag)
+        .unwrap();
+    assert_eq!(frame[..], *vector.plaintext);
+
+    // A wrong tag leaves the buffer untouched
+    let mut frame = [0u8; 60];
+    frame.copy_from_slice(vector.ciphertext);
+    let bad_tag = Tag::from([0; 16]);
+    assert!(
+        cipher
+            .decrypt_in_place_detached(nonce, vector.aad, &mut frame, &bad_tag)
+            .is_err()
+    );
+    assert_eq!(frame[..], *vector.ciphertext);
+
+    println!("In place encryption tests passed");
+}
+
+fn report_backend() {
+    println!("Backend:");
+    println!(
+        "  target            {} ({} bit)",
+        std::env::consts::ARCH,
+        usize::BITS
+    );
+
+    // Without AES instructions the aes crate uses its bitsliced
+    // ("fixslice") software implementation, and without carry-less multiply
+    // polyval a software GHASH built from constant-time multiplications.
+    // Neither uses lookup tables indexed by secret data.
+    println!(
+        "  AES               {}",
+        match hardware_aes() {
+            Some(true) => "hardware instructions",
+            Some(false) => "software fixslice (hardware not detected or not enabled)",
+            None => "software fixslice",
+        }
+    );
+    if hardware_aes() != Some(true) {
+        println!(
+            "  fixslice variant  {}",
+            if cfg!(aes_compact) {
+                "compact (--cfg aes_compact)"
+            } else {
+                "full"
+            }
+        );
+    }
+    println!(
+        "  GHASH             {}",
+        match hardware_ghash() {
+            Some(true) => "carry-less multiply instructions",
+            Some(false) => "software (instructions not detected or not enabled)",
+            None => "software",
+        }
+    );
+}
+
+/// Whether the aes crate uses AES instructions, on architectures where it
+/// can. On aarch64 they also need `--cfg aes_armv8`
+fn hardware_aes() -> Option<bool> {
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    {
+        Some(std::arch::is_x86_feature_detected!("aes") && !cfg!(aes_force_soft))
+    }
+    #[cfg(target_arch = "aarch64")]
+    {
+        Some(
+            cfg!(aes_armv8)
+                && std::arch::is_aarch64_feature_detected!("aes")
+                && !cfg!(aes_force_soft),
+        )
+    }
+    #[cfg(not(any(target_arch = "x86", target_arch = "x86_64", target_arch = "aarch64")))]
+    {
+        None
+    }
+}
+
+/// Whether polyval computes GHASH with PCLMULQDQ or PMULL. The latter
+/// needs `--cfg polyval_armv8`, and polyval detects it through the AES
+/// extension, which implies it
+fn hardware_ghash() -> Option<bool> {
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    {
+        Some(std::arch::is_x86_feature_detected!("pclmulqdq") && !cfg!(polyval_force_soft))
+    }
+    #[cfg(target_arch = "aarch64")]
+    {
+        Some(
+            cfg!(polyval_armv8)
+                && std::arch::is_aarch64_feature_detected!("aes")
+                && !cfg!(polyval_force_soft),
+        )
+    }
+    #[cfg(not(any(target_arch = "x86", target_arch = "x86_64", target_arch = "aarch64")))]
+    {
+        None
+    }
+}
+
+fn report_throughput() {
+    println!("Throughput, {} byte messages:", THROUGHPUT_BUFFER);
+
+    let nonce = Nonce::from_slice(&IV);
+    let mut buffer = vec![0x5a; THROUGHPUT_BUFFER];
+
+    let aes128 = Aes128Gcm::new(&Key::<Aes128Gcm>::from([7; 16]));
+    let aes256 = Aes256Gcm::new(&Key::<Aes256Gcm>::from([7; 32]));
+    let mut measure = |name: &str, encrypt: &dyn Fn(&mut [u8])| {
+        let start = Instant::now();
+        for _ in 0..THROUGHPUT_ITERATIONS {
+            encrypt(&mut buffer);
+        }
+        let elapsed = start.elapsed().as_secs_f64();
+        let bytes = (THROUGHPUT_BUFFER as u32 * THROUGHPUT_ITERATIONS) as f64;
+        println!("  {:<16} {:>8.2} MB/s", name, bytes / 1_000_000.0 / elapsed);
+    };
+
+    measure("AES-128-GCM", &|buf| {
+        aes128.encrypt_in_place_detached(nonce, b"", buf).unwrap();
+    });
+    measure("AES-256-GCM", &|buf| {
+        aes256.encrypt_in_place_detached(nonce, b"", buf).unwrap();
+    });
+}
+
+#[unsafe(no_mangle)]
+pub fn rust_crate_test_aes_gcm_main() {
+    println!("Running aes-gcm tests");
+
+    test_vectors();
+    test_tampering();
+    test_in_place();
+    report_backend();
+    report_throughput();
+
+    println!("All aes-gcm tests completed");
+}
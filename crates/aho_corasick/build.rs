@@ -0,0 +1,36 @@
+// Copyright (c) 2025 Xiaomi Corporation
+// SPDX-License-Identifier: Apache-2.0
+
+use std::env;
+use std::fs;
+use std::path::Path;
+
+/// Used when the NuttX configuration is not available, matches the Kconfig default
+const DEFAULT_STREAM_FILE_KB: &str = "256";
+
+/// Value of `option` in the generated `nuttx/config.h`, if the NuttX
+/// configuration is available. Every build.rs reading Kconfig has this same
+/// copy, keep them identical.
+fn kconfig_value(option: &str) -> Option<String> {
+    println!("cargo:rerun-if-env-changed=NUTTX_INCLUDE_DIR");
+
+    let dirs = env::var("NUTTX_INCLUDE_DIR").ok()?;
+    let config = dirs
+        .split(':')
+        .map(|dir| Path::new(dir).join("nuttx/config.h"))
+        .find(|path| path.exists())?;
+
+    println!("cargo:rerun-if-changed={}", config.display());
+
+    let prefix = format!("#define {option} ");
+    fs::read_to_string(config).ok()?.lines().find_map(|line| {
+        line.strip_prefix(&prefix)
+            .map(|value| value.trim().to_string())
+    })
+}
+
+fn main() {
+    let size = kconfig_value("CONFIG_RUST_CRATE_AHO_CORASICK_STREAM_FILE_KB")
+        .unwrap_or_else(|| DEFAULT_STREAM_FILE_KB.to_string());
+    println!("cargo:rustc-env=RUST_CRATE_AHO_CORASICK_STREAM_FILE_KB={size}");
+}
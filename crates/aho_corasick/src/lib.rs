@@ -14,11 +14,350 @@ pub fn rust_crate_test_aho_corasick_main() {
     // Example 3: Stream replacement
     example_stream_replace();
 
-    // Example 4: Default leftmost-longest matching
+    // Example 4: Leftmost-longest matching
     example_leftmost_longest();
 
     // Example 5: Leftmost-first matching
     example_leftmost_first();
+
+    // Example 6: Overlapping matching
+    example_overlapping();
+
+    // Example 7: Anchored matching
+    example_anchored();
+
+    // Example 8: Streaming search over a file
+    example_stream_file();
+
+    // Example 9: Large dictionary with each automaton kind
+    example_large_dictionary();
+
+    // Example 10: Packed Teddy searcher and its fallback
+    example_packed_teddy();
+}
+
+/// Size of the log written to tmpfs for the streaming search, selected in
+/// Kconfig, see build.rs
+fn stream_file_size() -> usize {
+    env!("RUST_CRATE_AHO_CORASICK_STREAM_FILE_KB")
+        .parse::<usize>()
+        .unwrap()
+        * 1024
+}
+
+const STREAM_FILE_PATH: &str = "/tmp/aho_corasick_stream.log";
+
+const DICTIONARY_SIZE: usize = 2000;
+const TIMING_ITERATIONS: u32 = 4;
+
+/// A small xorshift generator, so the dictionary and haystacks are the same
+/// on every run
+struct XorShift(u32);
+
+impl XorShift {
+    fn next(&mut self) -> u32 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 17;
+        self.0 ^= self.0 << 5;
+        self.0
+    }
+}
+
+/// Three syllable words, like `kalori`, all different
+fn dictionary(len: usize) -> Vec<String> {
+    const SYLLABLES: &[&str] = &[
+        "ka", "ri", "to", "ne", "mu", "sa", "lo", "pe", "di", "fu", "ga", "hi", "jo", "ve", "zu",
+        "bo", "ce", "wa", "xi", "yo",
+    ];
+    let n = SYLLABLES.len();
+    (0..len)
+        .map(|i| {
+            let i = i * 7919 % (n * n * n);
+            format!(
+                "{}{}{}",
+                SYLLABLES[i / (n * n)],
+                SYLLABLES[i / n % n],
+                SYLLABLES[i % n]
+            )
+        })
+        .collect()
+}
+
+/// Words separated by spaces, every fourth one from `words`
+fn haystack(words: &[String], len: usize) -> String {
+    let mut rng = XorShift(0x9e3779b9);
+    let mut text = String::with_capacity(len + 16);
+    while text.len() < len {
+        let r = rng.next() as usize;
+        match r % 4 {
+            0 => text.push_str(&words[r / 4 % words.len()]),
+            k => text.push_str(["sensor", "value", "status"][k - 1]),
+        }
+        text.push(' ');
+    }
+    text
+}
+
+fn example_overlapping() {
+    println!("\n=== Overlapping Matching ===");
+
+    let patterns = &["Sam", "Samwise", "wise", "is"];
+    let haystack = "Samwise";
+
+    // Only the standard semantics report every match, including matches
+    // inside other matches, in the order they end
+    let ac = aho_corasick::AhoCorasick::new(patterns).unwrap();
+    let mut matches = vec![];
+    for mat in ac.find_overlapping_iter(haystack) {
+        println!(
+            "Found overlapping pattern '{}' at positions {}-{}",
+            patterns[mat.pattern().as_usize()],
+            mat.start(),
+            mat.end()
+        );
+        matches.push(&haystack[mat.start()..mat.end()]);
+    }
+    assert_eq!(matches, vec!["Sam", "is", "Samwise", "wise"]);
+
+    // Leftmost semantics have no overlapping search
+    let ac = aho_corasick::AhoCorasick::builder()
+        .match_kind(aho_corasick::MatchKind::LeftmostFirst)
+        .build(patterns)
+        .unwrap();
+    let Err(err) = ac.try_find_overlapping_iter(haystack) else {
+        panic!("leftmost overlapping search should fail");
+    };
+    println!("Leftmost-first overlapping search: {}", err);
+}
+
+fn example_anchored() {
+    println!("\n=== Anchored Matching ===");
+
+    let commands = &["get", "set", "reboot"];
+    let ac = aho_corasick::AhoCorasick::builder()
+        .start_kind(aho_corasick::StartKind::Anchored)
+        .build(commands)
+        .unwrap();
+
+    // An anchored search only matches at the start of the input, so
+    // "reset" is not taken for "set"
+    let lines = [
+        ("set rate=250", Some("set")),
+        ("reset", None),
+        ("reboot now", Some("reboot")),
+        (" get rate", None),
+    ];
+    for (line, expected) in lines {
+        let input = aho_corasick::Input::new(line).anchored(aho_corasick::Anchored::Yes);
+        let found = ac.find(input).map(|mat| commands[mat.pattern().as_usize()]);
+        println!("Command in '{}': {:?}", line, found);
+        assert_eq!(found, expected);
+    }
+
+    // An automaton built for anchored searches cannot do unanchored ones
+    let err = ac
+        .try_find("reset")
+        .expect_err("unanchored search should fail");
+    println!("Unanchored search: {}", err);
+}
+
+fn example_stream_file() {
+    println!("\n=== Streaming Search Over A File ===");
+
+    use std::io::Write;
+
+    let patterns = &["overtemp", "undervolt", "i2c timeout", "watchdog"];
+
+    // Write a log to tmpfs, with an event every few lines, counting what
+    // goes in
+    let mut expected = [0usize; 4];
+    let mut file = std::io::BufWriter::new(std::fs::File::create(STREAM_FILE_PATH).unwrap());
+    let mut written = 0;
+    let mut line = 0usize;
+    let size = stream_file_size();
+    while written < size {
+        let text = if line % 5 == 4 {
+            let event = line / 5 % patterns.len();
+            expected[event] += 1;
+            format!(
+                "t={} ch{} event={}\n",
+                line * 250,
+                line % 4,
+                patterns[event]
+            )
+        } else {
+            format!(
+                "t={} ch{} adc={}\n",
+                line * 250,
+                line % 4,
+                (line * 37) % 1024
+            )
+        };
+        file.write_all(text.as_bytes()).unwrap();
+        written += text.len();
+        line += 1;
+    }
+    file.flush().unwrap();
+    drop(file);
+
+    // The file is read through a fixed buffer rather than loaded, and
+    // matches crossing the buffer boundary are still found
+    let ac = aho_corasick::AhoCorasick::new(patterns).unwrap();
+    let start = std::time::Instant::now();
+    let mut counts = [0usize; 4];
+    let file = std::fs::File::open(STREAM_FILE_PATH).unwrap();
+    for mat in ac.stream_find_iter(file) {
+        counts[mat.unwrap().pattern().as_usize()] += 1;
+    }
+    let elapsed = start.elapsed();
+
+    println!(
+        "Scanned {} bytes in {} ms, {:.2} MB/s",
+        written,
+        elapsed.as_millis(),
+        written as f64 / 1_000_000.0 / elapsed.as_secs_f64()
+    );
+    for (pattern, count) in patterns.iter().zip(counts) {
+        println!("  {:<12} {:>6}", pattern, count);
+    }
+    assert_eq!(counts, expected);
+
+    std::fs::remove_file(STREAM_FILE_PATH).unwrap();
+}
+
+fn example_large_dictionary() {
+    println!("\n=== Large Dictionary ===");
+
+    let words = dictionary(DICTIONARY_SIZE);
+    let text = haystack(&words, 64 * 1024);
+
+    let kinds = [
+        (
+            "noncontiguous NFA",
+            aho_corasick::AhoCorasickKind::NoncontiguousNFA,
+        ),
+        (
+            "contiguous NFA",
+            aho_corasick::AhoCorasickKind::ContiguousNFA,
+        ),
+        ("DFA", aho_corasick::AhoCorasickKind::DFA),
+    ];
+
+    println!(
+        "{} patterns, {} byte haystack, {} iterations:",
+        words.len(),
+        text.len(),
+        TIMING_ITERATIONS
+    );
+    println!(
+        "  {:<18} {:>10} {:>10} {:>10}",
+        "kind", "memory", "build us", "search us"
+    );
+
+    let mut expected = None;
+    for (name, kind) in kinds {
+        let start = std::time::Instant::now();
+        let ac = aho_corasick::AhoCorasick::builder()
+            .kind(Some(kind))
+            .match_kind(aho_corasick::MatchKind::LeftmostLongest)
+            .build(&words)
+            .unwrap();
+        let build = start.elapsed();
+        assert_eq!(ac.kind(), kind);
+
+        let start = std::time::Instant::now();
+        let mut matches = vec![];
+        for _ in 0..TIMING_ITERATIONS {
+            matches = ac
+                .find_iter(&text)
+                .map(|mat| (mat.pattern(), mat.start()))
+                .collect();
+        }
+        let search = start.elapsed() / TIMING_ITERATIONS;
+
+        println!(
+            "  {:<18} {:>10} {:>10} {:>10}",
+            name,
+            ac.memory_usage(),
+            build.as_micros(),
+            search.as_micros()
+        );
+
+        // Every kind finds the same matches, they only trade memory for speed
+        match &expected {
+            None => expected = Some(matches),
+            Some(expected) => assert_eq!(&matches, expected),
+        }
+    }
+    assert!(!expected.unwrap().is_empty());
+}
+
+fn example_packed_teddy() {
+    println!("\n=== Packed Teddy Searcher ===");
+
+    let words = dictionary(16);
+    let text = haystack(&words, 64 * 1024);
+
+    // Teddy needs SSE2 or NEON, so Cortex-M and RISC-V boards get None here
+    // and AhoCorasick runs on its automaton alone
+    let teddy = aho_corasick::packed::Searcher::new(&words);
+    println!(
+        "Teddy on {}: {}",
+        std::env::consts::ARCH,
+        if teddy.is_some() {
+            "available"
+        } else {
+            "not available"
+        }
+    );
+    assert_eq!(
+        teddy.is_some(),
+        cfg!(any(target_arch = "x86_64", target_arch = "aarch64"))
+    );
+
+    // Past a few dozen patterns Teddy would be slower than the automaton,
+    // so it is not built even where SIMD is available
+    assert!(aho_corasick::packed::Searcher::new(dictionary(DICTIONARY_SIZE)).is_none());
+
+    let with_prefilter = aho_corasick::AhoCorasick::builder()
+        .match_kind(aho_corasick::MatchKind::LeftmostFirst)
+        .build(&words)
+        .unwrap();
+    let without_prefilter = aho_corasick::AhoCorasick::builder()
+        .match_kind(aho_corasick::MatchKind::LeftmostFirst)
+        .prefilter(false)
+        .build(&words)
+        .unwrap();
+
+    let find_all = |ac: &aho_corasick::AhoCorasick| -> Vec<(usize, usize)> {
+        ac.find_iter(&text)
+            .map(|mat| (mat.pattern().as_usize(), mat.start()))
+            .collect()
+    };
+    let expected = find_all(&without_prefilter);
+    assert_eq!(find_all(&with_prefilter), expected);
+    if let Some(teddy) = &teddy {
+        let matches: Vec<_> = teddy
+            .find_iter(&text)
+            .map(|mat| (mat.pattern().as_usize(), mat.start()))
+            .collect();
+        assert_eq!(matches, expected);
+    }
+
+    for (name, ac) in [
+        ("with prefilter", &with_prefilter),
+        ("without prefilter", &without_prefilter),
+    ] {
+        let start = std::time::Instant::now();
+        for _ in 0..TIMING_ITERATIONS {
+            std::hint::black_box(find_all(ac));
+        }
+        println!(
+            "  {:<18} {:>10} us",
+            name,
+            (start.elapsed() / TIMING_ITERATIONS).as_micros()
+        );
+    }
 }
 
 fn example_basic_matching() {
@@ -102,12 +441,21 @@ fn example_stream_replace() {
 }
 
 fn example_leftmost_longest() {
-    println!("\n=== Leftmost-Longest Matching (Default) ===");
+    println!("\n=== Leftmost-Longest Matching ===");
 
-    let patterns = &["Samwise", "Sam"];
+    let patterns = &["Sam", "Samwise"];
     let haystack = "Samwise";
 
-    let ac = aho_corasick::AhoCorasick::new(patterns).unwrap();
+    // The default standard semantics report the match found first, "Sam",
+    // which is why the leftmost-longest kind has to be asked for
+    let standard = aho_corasick::AhoCorasick::new(patterns).unwrap();
+    let mat = standard.find(haystack).expect("should have a match");
+    assert_eq!("Sam", &haystack[mat.range()]);
+
+    let ac = aho_corasick::AhoCorasick::builder()
+        .match_kind(aho_corasick::MatchKind::LeftmostLongest)
+        .build(patterns)
+        .unwrap();
     let mat = ac.find(haystack).expect("should have a match");
 
     println!("Pattern found: \"{}\"", &haystack[mat.start()..mat.end()]);
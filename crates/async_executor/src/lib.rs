@@ -0,0 +1,144 @@
+// Copyright (c) 2025 Xiaomi Corporation
+// SPDX-License-Identifier: Apache-2.0
+
+//! async-executor and async-io example for NuttX
+//!
+//! This is the runtime stack used by `smol`: `async-executor` schedules the
+//! tasks and `async-io` drives timers and file descriptors through the
+//! `polling` crate, which uses the `poll()` backend on NuttX.
+
+use std::cell::RefCell;
+use std::io;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+use async_executor::{Executor, LocalExecutor};
+use async_io::{Async, Timer};
+use futures_lite::{AsyncReadExt, AsyncWriteExt, StreamExt};
+
+/// Wait on one-shot and periodic timers
+async fn test_timers() {
+    println!("Testing async_io::Timer");
+
+    let start = Instant::now();
+    Timer::after(Duration::from_millis(100)).await;
+    let elapsed = start.elapsed();
+    println!("Timer::after(100ms) elapsed: {:?}", elapsed);
+    assert!(
+        elapsed >= Duration::from_millis(100),
+        "timer fired too early"
+    );
+
+    let start = Instant::now();
+    let ticks = Timer::interval(Duration::from_millis(20))
+        .take(5)
+        .count()
+        .await;
+    let elapsed = start.elapsed();
+    println!("Timer::interval produced {} ticks in {:?}", ticks, elapsed);
+    assert_eq!(ticks, 5);
+    assert!(
+        elapsed >= Duration::from_millis(100),
+        "interval fired too early"
+    );
+}
+
+/// Spawn tasks on a thread-safe executor and wait for all of them
+async fn test_spawn(ex: &Executor<'_>) {
+    println!("Testing Executor::spawn");
+
+    let tasks = (0..5u64)
+        .map(|i| {
+            ex.spawn(async move {
+                Timer::after(Duration::from_millis(10 * (5 - i))).await;
+                i * 2
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let mut results = Vec::new();
+    for task in tasks {
+        results.push(task.await);
+    }
+
+    println!("Spawned task results: {:?}", results);
+    assert_eq!(results, vec![0, 2, 4, 6, 8]);
+}
+
+/// Spawn `!Send` tasks on a local executor
+fn test_local_executor() {
+    println!("Testing LocalExecutor");
+
+    let local_ex = LocalExecutor::new();
+    let shared = Rc::new(RefCell::new(Vec::new()));
+
+    let tasks = (0..3)
+        .map(|i| {
+            let shared = shared.clone();
+            local_ex.spawn(async move {
+                Timer::after(Duration::from_millis(10)).await;
+                shared.borrow_mut().push(i);
+            })
+        })
+        .collect::<Vec<_>>();
+
+    async_io::block_on(local_ex.run(async {
+        for task in tasks {
+            task.await;
+        }
+    }));
+
+    let mut values = shared.borrow().clone();
+    values.sort();
+    println!("LocalExecutor values: {:?}", values);
+    assert_eq!(values, vec![0, 1, 2]);
+}
+
+/// Move data through a pipe registered with the async-io reactor
+async fn test_pipe(ex: &Executor<'_>) -> io::Result<()> {
+    println!("Testing Async pipe");
+
+    let (reader, writer) = io::pipe()?;
+    let mut reader = Async::new(reader)?;
+    let mut writer = Async::new(writer)?;
+
+    // The writer starts late so the reader has to wait for readiness
+    let producer = ex.spawn(async move {
+        for chunk in ["hello ", "from ", "async-io"] {
+            Timer::after(Duration::from_millis(20)).await;
+            writer.write_all(chunk.as_bytes()).await?;
+        }
+        // Dropping the writer closes the pipe and ends the stream
+        io::Result::Ok(())
+    });
+
+    let mut received = String::new();
+    reader.read_to_string(&mut received).await?;
+    producer.await?;
+
+    println!("Received from pipe: {}", received);
+    assert_eq!(received, "hello from async-io");
+
+    Ok(())
+}
+
+#[unsafe(no_mangle)]
+pub fn rust_crate_test_async_executor_main() {
+    println!("Running async-executor tests");
+
+    let ex = Executor::new();
+    let result = async_io::block_on(ex.run(async {
+        test_timers().await;
+        test_spawn(&ex).await;
+        test_pipe(&ex).await
+    }));
+
+    if let Err(e) = result {
+        eprintln!("Pipe test failed: {}", e);
+        panic!("async-executor tests failed");
+    }
+
+    test_local_executor();
+
+    println!("All async-executor tests completed");
+}
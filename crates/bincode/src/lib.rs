@@ -0,0 +1,257 @@
+// Copyright (c) 2025 Xiaomi Corporation
+// SPDX-License-Identifier: Apache-2.0
+
+//! bincode 2 example for NuttX
+//!
+//! Round-trips the `Person`/`Address` structs used by the serde_json crate,
+//! plus enums, maps and byte arrays, through the serde integration and
+//! checks the exact wire bytes of the standard and legacy configurations.
+//! The native `Encode`/`Decode` derives are covered as well.
+
+use bincode::config;
+use bincode::{Decode, Encode};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct Person {
+    name: String,
+    age: u32,
+    phones: Vec<String>,
+    address: Address,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct Address {
+    street: String,
+    city: String,
+    zip: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+enum Command {
+    Reset,
+    SetLevel(u8),
+    Move { x: i16, y: i16 },
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct Frame {
+    id: u16,
+    #[serde(with = "serde_bytes")]
+    payload: Vec<u8>,
+    checksum: [u8; 4],
+}
+
+/// Uses the native bincode derives instead of serde
+#[derive(Encode, Decode, Debug, PartialEq)]
+struct Telemetry {
+    seq: u32,
+    temps: [i16; 3],
+    online: bool,
+}
+
+fn sample_person() -> Person {
+    Person {
+        name: "Ann".to_string(),
+        age: 30,
+        phones: vec!["555-1234".to_string()],
+        address: Address {
+            street: "Main St".to_string(),
+            city: "Oslo".to_string(),
+            zip: "0150".to_string(),
+        },
+    }
+}
+
+fn sample_map() -> BTreeMap<String, u32> {
+    BTreeMap::from([("temp".to_string(), 21), ("volt".to_string(), 3300)])
+}
+
+fn sample_frame() -> Frame {
+    Frame {
+        id: 0x0102,
+        payload: vec![0xde, 0xad, 0xbe, 0xef, 0x00],
+        checksum: [1, 2, 3, 4],
+    }
+}
+
+fn encode<T: Serialize>(value: &T) -> Vec<u8> {
+    bincode::serde::encode_to_vec(value, config::standard()).unwrap()
+}
+
+fn decode<T: serde::de::DeserializeOwned>(bytes: &[u8]) -> T {
+    let (value, len) = bincode::serde::decode_from_slice(bytes, config::standard()).unwrap();
+    assert_eq!(len, bytes.len(), "trailing bytes left after decoding");
+    value
+}
+
+fn test_person_roundtrip() {
+    println!("Testing Person round-trip");
+
+    let person = sample_person();
+    let encoded = encode(&person);
+    println!("Encoded Person: {} bytes", encoded.len());
+
+    // Strings are a varint length followed by UTF-8 bytes
+    let expected: &[u8] = &[
+        0x03, b'A', b'n', b'n', // name
+        0x1e, // age
+        0x01, 0x08, b'5', b'5', b'5', b'-', b'1', b'2', b'3', b'4', // phones
+        0x07, b'M', b'a', b'i', b'n', b' ', b'S', b't', // address.street
+        0x04, b'O', b's', b'l', b'o', // address.city
+        0x04, b'0', b'1', b'5', b'0', // address.zip
+    ];
+    assert_eq!(encoded, expected);
+
+    let decoded: Person = decode(&encoded);
+    assert_eq!(decoded, person);
+
+    println!("Person round-trip tests passed");
+}
+
+fn test_enum_roundtrip() {
+    println!("Testing enum round-trip");
+
+    // Varints above 250 get a 0xfb marker followed by a little endian u16
+    let cases: [(Command, &[u8]); 3] = [
+        (Command::Reset, &[0x00]),
+        (Command::SetLevel(200), &[0x01, 0xc8]),
+        (
+            Command::Move { x: -2, y: 300 },
+            &[0x02, 0x03, 0xfb, 0x58, 0x02],
+        ),
+    ];
+
+    for (command, expected) in cases {
+        let encoded = encode(&command);
+        println!("{:?} -> {:02x?}", command, encoded);
+        assert_eq!(encoded, expected);
+
+        let decoded: Command = decode(&encoded);
+        assert_eq!(decoded, command);
+    }
+
+    println!("Enum round-trip tests passed");
+}
+
+fn test_map_roundtrip() {
+    println!("Testing map round-trip");
+
+    let map = sample_map();
+    let encoded = encode(&map);
+    assert_eq!(
+        encoded,
+        [
+            0x02, 0x04, b't', b'e', b'm', b'p', 0x15, 0x04, b'v', b'o', b'l', b't', 0xfb, 0xe4,
+            0x0c
+        ]
+    );
+
+    let decoded: BTreeMap<String, u32> = decode(&encoded);
+    assert_eq!(decoded, map);
+
+    println!("Map round-trip tests passed");
+}
+
+fn test_bytes_roundtrip() {
+    println!("Testing byte array round-trip");
+
+    let frame = sample_frame();
+    let encoded = encode(&frame);
+    assert_eq!(
+        encoded,
+        [
+            0xfb, 0x02, 0x01, 0x05, 0xde, 0xad, 0xbe, 0xef, 0x00, 0x01, 0x02, 0x03, 0x04
+        ]
+    );
+
+    let decoded: Frame = decode(&encoded);
+    assert_eq!(decoded, frame);
+
+    println!("Byte array round-trip tests passed");
+}
+
+fn test_configurations() {
+    println!("Testing encoder configurations");
+
+    let command = Command::Move { x: -2, y: 300 };
+
+    // bincode 1.x compatible layout: fixed width little endian integers
+    let legacy = bincode::serde::encode_to_vec(&command, config::legacy()).unwrap();
+    assert_eq!(legacy, [0x02, 0x00, 0x00, 0x00, 0xfe, 0xff, 0x2c, 0x01]);
+
+    let big_endian = config::standard()
+        .with_big_endian()
+        .with_fixed_int_encoding();
+    let encoded = bincode::serde::encode_to_vec(&command, big_endian).unwrap();
+    assert_eq!(encoded, [0x00, 0x00, 0x00, 0x02, 0xff, 0xfe, 0x01, 0x2c]);
+
+    let (decoded, _): (Command, usize) =
+        bincode::serde::decode_from_slice(&encoded, big_endian).unwrap();
+    assert_eq!(decoded, command);
+
+    // A decode limit protects small heaps against hostile length prefixes
+    let limited = config::standard().with_limit::<16>();
+    let result: Result<(Person, usize), _> =
+        bincode::serde::decode_from_slice(&encode(&sample_person()), limited);
+    match result {
+        Err(bincode::error::DecodeError::LimitExceeded) => {
+            println!("Decode limit enforced");
+        }
+        other => panic!("expected LimitExceeded, got {:?}", other),
+    }
+
+    println!("Encoder configuration tests passed");
+}
+
+fn test_native_derive() {
+    println!("Testing native Encode/Decode derive");
+
+    let telemetry = Telemetry {
+        seq: 1000,
+        temps: [-40, 25, 125],
+        online: true,
+    };
+
+    // Encode into a stack buffer without allocating
+    let mut buf = [0u8; 32];
+    let len = bincode::encode_into_slice(&telemetry, &mut buf, config::standard()).unwrap();
+    println!("Telemetry: {:02x?}", &buf[..len]);
+    assert_eq!(&buf[..len], [0xfb, 0xe8, 0x03, 0x4f, 0x32, 0xfa, 0x01]);
+
+    let (decoded, read): (Telemetry, usize) =
+        bincode::decode_from_slice(&buf[..len], config::standard()).unwrap();
+    assert_eq!(read, len);
+    assert_eq!(decoded, telemetry);
+
+    println!("Native derive tests passed");
+}
+
+fn report_sizes() {
+    println!("Encoded sizes:");
+
+    let sizes = [
+        ("person", encode(&sample_person()).len()),
+        ("map", encode(&sample_map()).len()),
+        ("frame", encode(&sample_frame()).len()),
+    ];
+    for (name, size) in sizes {
+        println!("  {:<8} {:>3} bytes", name, size);
+    }
+}
+
+#[unsafe(no_mangle)]
+pub fn rust_crate_test_bincode_main() {
+    println!("Running bincode tests");
+
+    test_person_roundtrip();
+    test_enum_roundtrip();
+    test_map_roundtrip();
+    test_bytes_roundtrip();
+    test_configurations();
+    test_native_derive();
+    report_sizes();
+
+    println!("All bincode tests completed");
+}
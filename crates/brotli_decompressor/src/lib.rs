@@ -0,0 +1,298 @@
+// Copyright (c) 2025 Xiaomi Corporation
+// SPDX-License-Identifier: Apache-2.0
+
+//! brotli-decompressor example for NuttX
+//!
+//! Decodes brotli streams made with a large and a small window, through the
+//! streaming reader and into a fixed output buffer, checks what happens to
+//! truncated and damaged streams, which brotli carries no checksum for, and
+//! reports ratio, decode speed and the heap the decoder takes, to be read
+//! against the deflate figures crates/miniz_oxide prints for the same log.
+
+use brotli_decompressor::{BrotliResult, Decompressor};
+use hex_literal::hex;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::io::{self, Read};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Instant;
+
+const LOG_LINES: usize = 256;
+const DECODE_ITERATIONS: u32 = 16;
+const READ_BUFFER: usize = 4096;
+
+/// The brotli crate at quality 11 with a 4 MiB window (lgwin 22) over
+/// `sensor_log(256)`
+const BROTLI_W22: [u8; 733] = hex!(
+    "1bbb13602c92c7ad2fc5a098343eb522a3abb031f0609e6f06950aa73ea101e6"
+    "343d30a3550f1a14ff07daf354666dc52aba38c83d904ba82996e8e3e1473d22"
+    "37e5915b12338b74b29f3aefffe1ffce13590ffef548f110b772f3f0b7486f01"
+    "3e90da281fcce6f8d41b29c794dc50ae87da1eeac1d060c64dd5b8598e5beeb8"
+    "4d26ed69d2e9265df7a4179ef616a6bd33d33ed6b42f9cf5edcefa95597f6bd6"
+    "7f37f729d551cf5c8e7abece5195bcefd9e8f34570b30fd73e86f36bec7e2afb"
+    "59ed87770b48df02ceb700e316d073893ad42da2788b98bd4530b784e896b077"
+    "4b50df12da97ac1fb865786e195397ec25fcea97ca8b3a3bf21a73e4f537472a"
+    "e75254a34b517097a2545f8ab2bed46ae152ab33975a7d75ab9f4bcc9df4ceb4"
+    "883529ed646e37ecddc4bb39ec46b397aeb93ed0ba78f7981840195067c0b641"
+    "210be216e45af05b04436a8cd8324234226bc4c518cb983ee1066d4cd9140b53"
+    "704c9132c5d104ad091d915ec8049f69a64d439b26309d9d9da876d2dcc9edce"
+    "d7bd7f76273b9b97dd48bbc9ede57a2ff65e067335c606553658da806b83c426"
+    "279be86cd22db3836d51b0c58c2d58b6086db36b1bc5362ddbdcd985db2ec672"
+    "fd0ac41c0d96c320873f7670d9612877c1da4dc56e567687b793f44ece3b3176"
+    "d233dba2ec4d7137b3bb61f612ed656f2fea3dda0afa0007781c30a5a09f7448"
+    "ad43360e0b49218e22ae14fd08b7cea6da116bc7088ec938e6ca31a653f42ad5"
+    "204e51728a3927b09d103be1e0048dd37429dd0b3a8dd7e94c7642eda46e27db"
+    "3b9177d3d8cdcdee9f1c3b99b9bcda8bb897ec1e1717582ee85351a35d5076c9"
+    "c2251c97a45c727485d6151d551de40a9f6ba65d43bb2670cd8e5ba8dca2e916"
+    "b76ee1b861a4e657c0cdd12035f4d4e6b7dbd86e3350bb60dc49d54e963be1ee"
+    "24d9cd6937badd74cfb6b07b29ec65662fac3d420fec7a40f140cb03771e717b"
+    "64acb19ff0488d27b63c15a2265c9ab8d4fc23ece1d9543cb3f2fce63d"
+);
+
+/// The same at quality 11 with the smallest window, 1 KiB (lgwin 10)
+const BROTLI_W10: [u8; 796] = hex!(
+    "a1d89d0063913c6e7d2906c5a4f1a915195d858de9c13056bb50e1d42734c09c"
+    "a60766b4ea4183faefa27a3e2b9516622b9a18c887453d4d53364bd6801ff5a8"
+    "1ac6a299ade4c4d84078f07f35efffe1ffe689d98b97468297d656362f7f403a"
+    "00fc20b5517e98cde3ab0ea51e6f982bc2eb22b28b6850c49812ae2ae1658970"
+    "4b2429935399eacab4bbccc215b65061672a1cabc285556eb7ca2b55be55e5ef"
+    "6adfd0f378debe1ecffbbac703e4be77e3f5fc45ac9bbee5ea5bc37a84ed5f95"
+    "feb5ea5fbc0958e90958e70958c604ac9e01a2a8095ac5095ab313b4980959d1"
+    "84acbd0959ea0959ed01e381095b9e095b53037603ffea03e5667df6e82e7b74"
+    "fff608380301460301710301ab0702ae07da2dfdb9d551848e4ee8dbbe9ae3de"
+    "6a4e1035272535644e58ca1696c6c2d283b0ac4638b64b380e148ef30ac74d44"
+    "f09408be4e04bf2de20bc8445eea445eeb890c28322851c18a0a252a8ca8f044"
+    "47161db5e818a26fd7551d2faa3ae1ac8aa4c4224731542b8676c4b09018d627"
+    "363b2d36478bcd0562733be2f02a71f8a638fcad381bd56eaf76dfd506123788"
+    "78c189175abc70c48b20212a09d14a8829e1e3248bd769123fd19148ba255258"
+    "122d48a233925896243694ccee4ae614c95c4b32772715ef968a1f4bf50710aa"
+    "36ebfc5b65ff55024b0946ea1052872575b8524794268a34d14973db5773dc53"
+    "ed096ab52745b564565aca484723e9e89e74ac5a3ab62d830364701e19dc940c"
+    "9e94c9d7cae43732bf8044e6a524eb5a4d56005941c90a567628d9616487273b"
+    "b25c51cb1543aedb7555c773554ed8ab90580e39c8a11ab96997dc2c2837eb95"
+    "9b9dc8c351f27075f270dbf2f0b2bc7c435efe46de8d6ab757b7efea02c91744"
+    "bee014845610b682080aa25218adc2980a3f4ee5f03a4ed1275a8a4847118514"
+    "519f623aad98a515b381627647294ea514d7548abb558a7794f023257f004e65"
+    "b3caceaaf75f7560a583513a8432612913ae32119589a26c74caded6d51e77d7"
+    "fb8657775250476694a32ce568a882eeaa6015156c4b057ba79273abe4c62a79"
+    "42255fa38adf52f50544aa2e55aaf6ae1c407550aa839df5efe5f01e"
+);
+
+/// A stream with only an empty last meta-block
+const BROTLI_EMPTY: [u8; 1] = [0x3b];
+
+static HEAP_IN_USE: AtomicUsize = AtomicUsize::new(0);
+static HEAP_PEAK: AtomicUsize = AtomicUsize::new(0);
+
+/// The system allocator, keeping count of the bytes in use and their peak
+struct CountingAllocator;
+
+fn record_alloc(size: usize) {
+    let in_use = HEAP_IN_USE.fetch_add(size, Ordering::Relaxed) + size;
+    HEAP_PEAK.fetch_max(in_use, Ordering::Relaxed);
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc(layout) };
+        if !ptr.is_null() {
+            record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc_zeroed(layout) };
+        if !ptr.is_null() {
+            record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) };
+        HEAP_IN_USE.fetch_sub(layout.size(), Ordering::Relaxed);
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = unsafe { System.realloc(ptr, layout, new_size) };
+        if !new_ptr.is_null() {
+            HEAP_IN_USE.fetch_sub(layout.size(), Ordering::Relaxed);
+            record_alloc(new_size);
+        }
+        new_ptr
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// Restarts the peak from the bytes in use now, and returns them
+fn reset_heap_peak() -> usize {
+    let in_use = HEAP_IN_USE.load(Ordering::Relaxed);
+    HEAP_PEAK.store(in_use, Ordering::Relaxed);
+    in_use
+}
+
+/// The text the vectors compress, 256 lines of `t=<ms> ch<n> adc=<value>`
+fn sensor_log(lines: usize) -> Vec<u8> {
+    (0..lines)
+        .flat_map(|i| format!("t={} ch{} adc={}\n", i * 250, i % 4, (i * 37) % 1024).into_bytes())
+        .collect()
+}
+
+/// Window size in bits from the stream header, RFC 7932 section 9.1
+fn window_bits(stream: &[u8]) -> u32 {
+    let header = stream[0];
+    if header & 0x01 == 0 {
+        return 16;
+    }
+    match (header >> 1) & 0x07 {
+        0 => match (header >> 4) & 0x07 {
+            0 => 17,
+            n => 8 + n as u32,
+        },
+        n => 17 + n as u32,
+    }
+}
+
+fn decode(stream: &[u8]) -> io::Result<Vec<u8>> {
+    let mut output = Vec::new();
+    Decompressor::new(stream, READ_BUFFER).read_to_end(&mut output)?;
+    Ok(output)
+}
+
+fn test_vectors() {
+    println!("Testing brotli vectors");
+
+    let log = sensor_log(LOG_LINES);
+    assert_eq!(window_bits(&BROTLI_W22), 22);
+    assert_eq!(decode(&BROTLI_W22).unwrap(), log);
+    assert_eq!(window_bits(&BROTLI_W10), 10);
+    assert_eq!(decode(&BROTLI_W10).unwrap(), log);
+    assert_eq!(decode(&BROTLI_EMPTY).unwrap(), b"");
+
+    println!("Brotli vectors passed");
+}
+
+fn test_fixed_buffer() {
+    println!("Testing decode into a fixed buffer");
+
+    // brotli_decode() writes straight into the caller's buffer, so the
+    // output needs no growing Vec
+    let log = sensor_log(LOG_LINES);
+    let mut output = [0u8; 5120];
+    let info = brotli_decompressor::brotli_decode(&BROTLI_W22, &mut output);
+    assert!(matches!(info.result, BrotliResult::ResultSuccess));
+    assert_eq!(output[..info.decoded_size], log);
+
+    // A buffer too small stops with what fits, rather than failing
+    let mut output = [0u8; 4096];
+    let info = brotli_decompressor::brotli_decode(&BROTLI_W22, &mut output);
+    assert!(matches!(info.result, BrotliResult::NeedsMoreOutput));
+    assert_eq!(info.decoded_size, output.len());
+    assert_eq!(output[..], log[..output.len()]);
+
+    println!("Fixed buffer tests passed");
+}
+
+fn test_small_reads() {
+    println!("Testing small reads");
+
+    // The reader hands out output in whatever pieces the caller asks for
+    let log = sensor_log(LOG_LINES);
+    let mut reader = Decompressor::new(&BROTLI_W10[..], 64);
+    let mut output = Vec::new();
+    let mut chunk = [0u8; 100];
+    loop {
+        let len = reader.read(&mut chunk).unwrap();
+        if len == 0 {
+            break;
+        }
+        output.extend_from_slice(&chunk[..len]);
+    }
+    assert_eq!(output, log);
+
+    println!("Small read tests passed");
+}
+
+fn test_corrupted_input() {
+    println!("Testing corrupted input");
+
+    let log = sensor_log(LOG_LINES);
+
+    let err = decode(&BROTLI_W22[..BROTLI_W22.len() - 100]).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    println!("  {:<20} {}", "truncated", err);
+
+    // The reader stops at the last meta-block and leaves whatever follows
+    // unread, so the container has to carry the stream length
+    let mut trailing = BROTLI_W22.to_vec();
+    trailing.extend_from_slice(b"garbage");
+    assert_eq!(decode(&trailing).unwrap(), log);
+    println!("  {:<20} ignored", "trailing garbage");
+
+    // Without a checksum a flipped bit either breaks the bit stream or
+    // quietly changes the output, so a container has to check the result
+    for pos in [4, 100, 400, 700] {
+        let mut stream = BROTLI_W22;
+        stream[pos] ^= 0x10;
+        let outcome = match decode(&stream) {
+            Ok(output) => {
+                assert_ne!(output, log);
+                "decoded to wrong data".to_string()
+            }
+            Err(err) => err.to_string(),
+        };
+        println!("  {:<20} {}", format!("byte {} flipped", pos), outcome);
+    }
+
+    println!("Corrupted input tests passed");
+}
+
+/// One line of the report for a stream decoded through the reader
+fn report_stream(name: &str, stream: &[u8]) {
+    let log = sensor_log(LOG_LINES);
+    let heap_base = reset_heap_peak();
+    assert_eq!(decode(stream).unwrap(), log);
+    let heap = HEAP_PEAK.load(Ordering::Relaxed) - heap_base;
+
+    let start = Instant::now();
+    for _ in 0..DECODE_ITERATIONS {
+        std::hint::black_box(decode(std::hint::black_box(stream)).unwrap());
+    }
+    let seconds = start.elapsed().as_secs_f64() / DECODE_ITERATIONS as f64;
+
+    println!(
+        "  {:<16} {:>6.1}% {:>8.2} MB/s {:>8} bytes heap",
+        name,
+        stream.len() as f64 * 100.0 / log.len() as f64,
+        log.len() as f64 / 1_000_000.0 / seconds,
+        heap
+    );
+}
+
+fn report_decompression() {
+    println!(
+        "Decompression of a {} byte sensor log:",
+        sensor_log(LOG_LINES).len()
+    );
+
+    // Most of the heap is the decoder's Huffman tables; the ring buffer is
+    // sized by the window, capped at the meta-block length when that is
+    // known up front
+    report_stream("brotli lgwin 22", &BROTLI_W22);
+    report_stream("brotli lgwin 10", &BROTLI_W10);
+}
+
+#[unsafe(no_mangle)]
+pub fn rust_crate_test_brotli_decompressor_main() {
+    println!("Running brotli-decompressor tests");
+
+    test_vectors();
+    test_fixed_buffer();
+    test_small_reads();
+    test_corrupted_input();
+    report_decompression();
+
+    println!("All brotli-decompressor tests completed");
+}
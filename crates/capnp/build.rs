@@ -0,0 +1,16 @@
+// Copyright (c) 2025 Xiaomi Corporation
+// SPDX-License-Identifier: Apache-2.0
+
+const SCHEMA: &str = "schema/telemetry.capnp";
+
+fn main() {
+    println!("cargo:rerun-if-changed={SCHEMA}");
+
+    // capnpc drives the capnp schema compiler, which has to be installed on
+    // the host
+    capnpc::CompilerCommand::new()
+        .src_prefix("schema")
+        .file(SCHEMA)
+        .run()
+        .expect("failed to compile the Cap'n Proto schema, is capnp installed?");
+}
@@ -0,0 +1,428 @@
+// Copyright (c) 2025 Xiaomi Corporation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Cap'n Proto example for NuttX
+//!
+//! The flatbuffers example with Cap'n Proto, see that crate for what it covers.
+
+/// Generated from schema/telemetry.capnp by build.rs
+#[allow(clippy::all, unused_imports)]
+mod telemetry_capnp {
+    include!(concat!(env!("OUT_DIR"), "/telemetry_capnp.rs"));
+}
+
+use capnp::message::{self, HeapAllocator, ReaderOptions};
+use capnp::serialize::NoAllocSliceSegments;
+use capnp::{Word, serialize, serialize_packed};
+use std::collections::BTreeMap;
+use std::ffi::c_void;
+use std::fs::{self, File};
+use std::hint::black_box;
+use std::os::fd::AsRawFd;
+use std::time::Instant;
+use telemetry_capnp::{SensorKind, device};
+
+const DEVICE_PATH: &str = "/tmp/capnp_device.bin";
+
+/// Number of readings in the decode cost report
+const REPORT_READINGS: usize = 64;
+const REPORT_ITERATIONS: u32 = 1000;
+
+struct ReadingData {
+    kind: SensorKind,
+    value: i32,
+    timestamp_us: u64,
+}
+
+/// Contents of a Device, the same values as `sample_device()` in the prost
+/// crate
+struct DeviceData {
+    id: &'static str,
+    site: &'static str,
+    rack: u32,
+    labels: BTreeMap<&'static str, &'static str>,
+    readings: Vec<ReadingData>,
+    firmware_hash: [u8; 4],
+    channels: [u32; 3],
+}
+
+fn sample_data() -> DeviceData {
+    DeviceData {
+        id: "dev0",
+        site: "lab",
+        rack: 3,
+        labels: BTreeMap::from([("fw", "1.2")]),
+        readings: vec![
+            ReadingData {
+                kind: SensorKind::Temperature,
+                value: -40,
+                timestamp_us: 1_000_000,
+            },
+            ReadingData {
+                kind: SensorKind::Voltage,
+                value: 3300,
+                timestamp_us: 0,
+            },
+        ],
+        firmware_hash: [0xde, 0xad, 0xbe, 0xef],
+        channels: [1, 2, 300],
+    }
+}
+
+fn report_data() -> DeviceData {
+    let mut data = sample_data();
+    data.readings = (0..REPORT_READINGS)
+        .map(|i| ReadingData {
+            kind: if i % 2 == 0 {
+                SensorKind::Temperature
+            } else {
+                SensorKind::Voltage
+            },
+            value: i as i32 * 10 - 40,
+            timestamp_us: i as u64 * 1000,
+        })
+        .collect();
+    data
+}
+
+/// Builds a Device. Lists are allocated with their final length up front,
+/// then filled in place.
+fn build_device(data: &DeviceData, online: bool) -> message::Builder<HeapAllocator> {
+    let mut message = message::Builder::new_default();
+    let mut device = message.init_root::<device::Builder>();
+
+    device.set_id(data.id);
+    let mut location = device.reborrow().init_location();
+    location.set_site(data.site);
+    location.set_rack(data.rack);
+
+    let mut labels = device.reborrow().init_labels(data.labels.len() as u32);
+    for (i, (key, value)) in data.labels.iter().enumerate() {
+        let mut label = labels.reborrow().get(i as u32);
+        label.set_key(*key);
+        label.set_value(*value);
+    }
+
+    let mut readings = device.reborrow().init_readings(data.readings.len() as u32);
+    for (i, data) in data.readings.iter().enumerate() {
+        let mut reading = readings.reborrow().get(i as u32);
+        reading.set_kind(data.kind);
+        reading.set_value(data.value);
+        reading.set_timestamp_us(data.timestamp_us);
+    }
+
+    device.set_firmware_hash(&data.firmware_hash);
+    device.set_channels(&data.channels[..]).unwrap();
+    device.set_online(online);
+    if !online {
+        device.set_error("overheat");
+    }
+    message
+}
+
+/// Serializes into words, as reading in place needs the buffer to be 8-byte
+/// aligned and a `Vec<u8>` is only guaranteed to be byte aligned
+fn encode(data: &DeviceData, online: bool) -> Vec<Word> {
+    let bytes = serialize::write_message_to_words(&build_device(data, online));
+    let mut words = Word::allocate_zeroed_vec(bytes.len() / 8);
+    Word::words_to_bytes_mut(&mut words).copy_from_slice(&bytes);
+    words
+}
+
+/// Reads a message in place, without allocating
+fn read_in_place(
+    mut bytes: &[u8],
+    options: ReaderOptions,
+) -> capnp::Result<message::Reader<NoAllocSliceSegments<'_>>> {
+    serialize::read_message_from_flat_slice_no_alloc(&mut bytes, options)
+}
+
+fn check_sample(device: device::Reader) {
+    assert_eq!(device.get_id().unwrap(), "dev0");
+    let location = device.get_location().unwrap();
+    assert_eq!(location.get_site().unwrap(), "lab");
+    assert_eq!(location.get_rack(), 3);
+
+    let readings = device.get_readings().unwrap();
+    assert_eq!(readings.len(), 2);
+    assert_eq!(readings.get(0).get_kind(), Ok(SensorKind::Temperature));
+    assert_eq!(readings.get(0).get_value(), -40);
+    assert_eq!(readings.get(0).get_timestamp_us(), 1_000_000);
+    assert_eq!(readings.get(1).get_value(), 3300);
+
+    let label = device.get_labels().unwrap().get(0);
+    assert_eq!(label.get_key().unwrap(), "fw");
+    assert_eq!(label.get_value().unwrap(), "1.2");
+
+    assert_eq!(
+        device.get_firmware_hash().unwrap(),
+        [0xde, 0xad, 0xbe, 0xef]
+    );
+    let channels: Vec<u32> = device.get_channels().unwrap().iter().collect();
+    assert_eq!(channels, [1, 2, 300]);
+    assert!(device.get_online());
+    assert!(!device.has_error());
+}
+
+fn test_build_and_read() {
+    println!("Testing build and in place read");
+
+    let words = encode(&sample_data(), true);
+    let buf = Word::words_to_bytes(&words);
+    println!("Encoded Device: {} bytes", buf.len());
+
+    // The reader keeps referring to the buffer, it only parses the segment
+    // table and doesn't allocate
+    let mut rest = buf;
+    let message =
+        serialize::read_message_from_flat_slice_no_alloc(&mut rest, ReaderOptions::new()).unwrap();
+    assert!(rest.is_empty());
+    let device = message.get_root::<device::Reader>().unwrap();
+    println!("Decoded: {:?}", device);
+    check_sample(device);
+
+    // Accessors return references into the buffer instead of copies
+    let range = buf.as_ptr_range();
+    let id = device.get_id().unwrap();
+    assert!(range.contains(&id.as_bytes().as_ptr()));
+    let firmware_hash = device.get_firmware_hash().unwrap();
+    assert!(range.contains(&firmware_hash.as_ptr()));
+
+    println!("Build and in place read tests passed");
+}
+
+fn test_defaults_and_absent_fields() {
+    println!("Testing defaults and absent fields");
+
+    // Structs have a fixed layout, so an empty Device still has room for
+    // all of its fields: the segment table, the root pointer, one data word
+    // and seven pointers
+    let mut message = message::Builder::new_default();
+    message.init_root::<device::Builder>();
+    let empty = serialize::write_message_to_words(&message);
+    println!("Empty Device: {} bytes", empty.len());
+    assert_eq!(empty.len(), 80);
+
+    // Packing drops the zero bytes again
+    let mut packed = Vec::new();
+    serialize_packed::write_message(&mut packed, &message).unwrap();
+    println!("Empty Device packed: {:02x?}", packed);
+    assert!(packed.len() < 16);
+
+    let reader = message.get_root_as_reader::<device::Reader>().unwrap();
+    assert!(!reader.has_id());
+    assert_eq!(reader.get_id().unwrap(), "");
+    assert_eq!(reader.get_readings().unwrap().len(), 0);
+    assert!(!reader.get_online());
+
+    let words = encode(&sample_data(), false);
+    let message = read_in_place(Word::words_to_bytes(&words), ReaderOptions::new()).unwrap();
+    let device = message.get_root::<device::Reader>().unwrap();
+    assert!(!device.get_online());
+    assert_eq!(device.get_error().unwrap(), "overheat");
+
+    println!("Defaults and absent fields tests passed");
+}
+
+fn test_bounds_and_limits() {
+    println!("Testing bounds checks and reader limits");
+
+    let words = encode(&sample_data(), true);
+    let buf = Word::words_to_bytes(&words);
+
+    // The segment table gives the message size, so every truncation is
+    // rejected before any field is read
+    for len in 0..buf.len() {
+        assert!(read_in_place(&buf[..len], ReaderOptions::new()).is_err());
+    }
+
+    // There is no separate verification pass, every pointer is checked
+    // against the segment bounds when it is followed. The root pointer
+    // follows the 8-byte segment table.
+    let mut corrupted = words.clone();
+    Word::words_to_bytes_mut(&mut corrupted)[8..12].copy_from_slice(&0x7fff_fff0u32.to_le_bytes());
+    let message = read_in_place(Word::words_to_bytes(&corrupted), ReaderOptions::new()).unwrap();
+    let err = message.get_root::<device::Reader>().unwrap_err();
+    println!("Error (expected): {}", err);
+
+    // The traversal limit bounds the amount of data a hostile message can
+    // make the reader visit. A message larger than the limit is rejected up
+    // front, and every struct or list read counts against it, so pointers
+    // that alias the same data can't make reading it arbitrarily expensive.
+    let size = read_in_place(buf, ReaderOptions::new())
+        .unwrap()
+        .size_in_words();
+    let mut options = ReaderOptions::new();
+    options.traversal_limit_in_words(Some(size - 1));
+    let Err(err) = read_in_place(buf, options) else {
+        panic!("message over the traversal limit accepted");
+    };
+    println!("Error (expected): {}", err);
+
+    options.traversal_limit_in_words(Some(size));
+    let message = read_in_place(buf, options).unwrap();
+    let device = message.get_root::<device::Reader>().unwrap();
+    let err = (0..size)
+        .find_map(|_| device.get_readings().err())
+        .expect("traversal limit not hit");
+    println!("Error (expected): {}", err);
+
+    // Reading in place needs an 8-byte aligned buffer
+    let mut shifted = Word::allocate_zeroed_vec(words.len() + 1);
+    let shifted = &mut Word::words_to_bytes_mut(&mut shifted)[4..4 + buf.len()];
+    shifted.copy_from_slice(buf);
+    let Err(err) = read_in_place(shifted, ReaderOptions::new()) else {
+        panic!("unaligned buffer accepted");
+    };
+    println!("Error (expected): {}", err);
+
+    println!("Bounds checks and reader limits tests passed");
+}
+
+fn test_packed_stream() {
+    println!("Testing packed stream");
+
+    // Several messages in one stream, each with its own segment table
+    let mut stream = Vec::new();
+    for id in ["dev0", "dev1", "dev2"] {
+        let mut message = message::Builder::new_default();
+        message.init_root::<device::Builder>().set_id(id);
+        serialize_packed::write_message(&mut stream, &message).unwrap();
+    }
+    println!("Packed stream: {} bytes", stream.len());
+
+    // Packed messages have to be unpacked into owned segments first
+    let mut ids = Vec::new();
+    let mut rest = &stream[..];
+    while !rest.is_empty() {
+        let message = serialize_packed::read_message(&mut rest, ReaderOptions::new()).unwrap();
+        let device = message.get_root::<device::Reader>().unwrap();
+        ids.push(device.get_id().unwrap().to_string().unwrap());
+    }
+    assert_eq!(ids, ["dev0", "dev1", "dev2"]);
+
+    println!("Packed stream tests passed");
+}
+
+/// Read only mapping of a whole file
+struct Mapping {
+    addr: *mut c_void,
+    len: usize,
+}
+
+impl Mapping {
+    fn new(file: &File) -> Option<Self> {
+        let len = file.metadata().ok()?.len() as usize;
+        let addr = unsafe {
+            libc::mmap(
+                std::ptr::null_mut(),
+                len,
+                libc::PROT_READ,
+                libc::MAP_PRIVATE,
+                file.as_raw_fd(),
+                0,
+            )
+        };
+        (addr != libc::MAP_FAILED).then_some(Mapping { addr, len })
+    }
+
+    fn as_slice(&self) -> &[u8] {
+        unsafe { std::slice::from_raw_parts(self.addr as *const u8, self.len) }
+    }
+}
+
+impl Drop for Mapping {
+    fn drop(&mut self) {
+        unsafe { libc::munmap(self.addr, self.len) };
+    }
+}
+
+fn test_read_from_file() {
+    println!("Testing in place read from {}", DEVICE_PATH);
+
+    let words = encode(&sample_data(), true);
+    fs::write(DEVICE_PATH, Word::words_to_bytes(&words)).unwrap();
+
+    let file = File::open(DEVICE_PATH).unwrap();
+    match Mapping::new(&file) {
+        Some(mapping) => {
+            // Mappings are page aligned, which is enough for reading in place
+            let message = read_in_place(mapping.as_slice(), ReaderOptions::new()).unwrap();
+            let device = message.get_root::<device::Reader>().unwrap();
+            check_sample(device);
+
+            let range = mapping.as_slice().as_ptr_range();
+            assert!(range.contains(&device.get_id().unwrap().as_bytes().as_ptr()));
+            println!("Read {} bytes through mmap()", mapping.len);
+        }
+        None => {
+            // Without mmap() support the file is read into words once
+            let mut file = File::open(DEVICE_PATH).unwrap();
+            let message = serialize::read_message(&mut file, ReaderOptions::new()).unwrap();
+            check_sample(message.get_root::<device::Reader>().unwrap());
+            println!(
+                "mmap() not available, read {} words",
+                message.size_in_words()
+            );
+        }
+    }
+
+    fs::remove_file(DEVICE_PATH).unwrap();
+
+    println!("In place read from file tests passed");
+}
+
+/// Same workload as report_decode_cost() in the prost crate
+fn report_decode_cost() {
+    println!("Decode cost, Device with {} readings:", REPORT_READINGS);
+
+    let data = report_data();
+    let words = encode(&data, true);
+    let buf = Word::words_to_bytes(&words);
+    let mut packed = Vec::new();
+    serialize_packed::write_message(&mut packed, &build_device(&data, true)).unwrap();
+    let expected: i64 = data.readings.iter().map(|r| r.value as i64).sum();
+
+    let sum_values = |device: device::Reader| -> i64 {
+        device
+            .get_readings()
+            .unwrap()
+            .iter()
+            .map(|reading| reading.get_value() as i64)
+            .sum()
+    };
+
+    let start = Instant::now();
+    for _ in 0..REPORT_ITERATIONS {
+        let message = read_in_place(black_box(buf), ReaderOptions::new()).unwrap();
+        assert_eq!(sum_values(message.get_root().unwrap()), expected);
+    }
+    let in_place = start.elapsed() / REPORT_ITERATIONS;
+
+    let start = Instant::now();
+    for _ in 0..REPORT_ITERATIONS {
+        let mut bytes = black_box(&packed[..]);
+        let message = serialize_packed::read_message(&mut bytes, ReaderOptions::new()).unwrap();
+        assert_eq!(sum_values(message.get_root().unwrap()), expected);
+    }
+    let unpacked = start.elapsed() / REPORT_ITERATIONS;
+
+    println!("  encoded size      {:>6} bytes", buf.len());
+    println!("  packed size       {:>6} bytes", packed.len());
+    println!("  in place read     {:>6} ns", in_place.as_nanos());
+    println!("  packed read       {:>6} ns", unpacked.as_nanos());
+}
+
+#[unsafe(no_mangle)]
+pub fn rust_crate_test_capnp_main() {
+    println!("Running Cap'n Proto tests");
+
+    test_build_and_read();
+    test_defaults_and_absent_fields();
+    test_bounds_and_limits();
+    test_packed_stream();
+    test_read_from_file();
+    report_decode_cost();
+
+    println!("All Cap'n Proto tests completed");
+}
@@ -0,0 +1,177 @@
+// Copyright (c) 2025 Xiaomi Corporation
+// SPDX-License-Identifier: Apache-2.0
+
+//! chacha20poly1305 example for NuttX
+//!
+//! Checks ChaCha20-Poly1305 against the AEAD test vector of RFC 8439 and
+//! measures throughput. ChaCha20 only uses additions, rotations and XORs, so
+//! unlike AES it is constant time without hardware support.
+
+use chacha20poly1305::aead::{Aead, AeadInPlace, KeyInit, Payload};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce, Tag, XChaCha20Poly1305, XNonce};
+use hex_literal::hex;
+use std::time::Instant;
+
+const THROUGHPUT_BUFFER: usize = 16 * 1024;
+const THROUGHPUT_ITERATIONS: u32 = 16;
+
+/// RFC 8439, section 2.8.2
+const KEY: [u8; 32] = hex!("808182838485868788898a8b8c8d8e8f909192939495969798999a9b9c9d9e9f");
+const NONCE: [u8; 12] = hex!("070000004041424344454647");
+const AAD: &[u8] = &hex!("50515253c0c1c2c3c4c5c6c7");
+const PLAINTEXT: &[u8] = b"Ladies and Gentlemen of the class of '99: \
+If I could offer you only one tip for the future, sunscreen would be it.";
+const CIPHERTEXT: &[u8] = &hex!(
+    "d31a8d34648e60db7b86afbc53ef7ec2a4aded51296e08fea9e2b5a736ee62d6"
+    "3dbea45e8ca9671282fafb69da92728b1a71de0a9e060b2905d6a5b67ecd3b36"
+    "92ddbd7f2d778b8c9803aee328091b58fab324e4fad675945585808b4831d7bc"
+    "3ff4def08e4b7a9de576d26586cec64b6116"
+);
+const TAG: [u8; 16] = hex!("1ae10b594f09e26a7e902ecbd0600691");
+
+fn test_rfc8439_vector() {
+    println!("Testing RFC 8439 AEAD test vector");
+
+    let cipher = ChaCha20Poly1305::new(Key::from_slice(&KEY));
+    let nonce = Nonce::from_slice(&NONCE);
+
+    let sealed = cipher
+        .encrypt(
+            nonce,
+            Payload {
+                msg: PLAINTEXT,
+                aad: AAD,
+            },
+        )
+        .unwrap();
+    assert_eq!(sealed.len(), PLAINTEXT.len() + 16);
+    assert_eq!(&sealed[..PLAINTEXT.len()], CIPHERTEXT);
+    assert_eq!(sealed[PLAINTEXT.len()..], TAG);
+
+    let opened = cipher
+        .decrypt(
+            nonce,
+            Payload {
+                msg: &sealed,
+                aad: AAD,
+            },
+        )
+        .unwrap();
+    assert_eq!(opened, PLAINTEXT);
+
+    // Detached tag, in place
+    let mut buffer = PLAINTEXT.to_vec();
+    let tag = cipher
+        .encrypt_in_place_detached(nonce, AAD, &mut buffer)
+        .unwrap();
+    assert_eq!(buffer, CIPHERTEXT);
+    assert_eq!(tag[..], TAG);
+    cipher
+        .decrypt_in_place_detached(nonce, AAD, &mut buffer, &tag)
+        .unwrap();
+    assert_eq!(buffer, PLAINTEXT);
+
+    println!("RFC 8439 AEAD test vector passed");
+}
+
+fn test_tampering() {
+    println!("Testing authentication failures");
+
+    let cipher = ChaCha20Poly1305::new(Key::from_slice(&KEY));
+    let nonce = Nonce::from_slice(&NONCE);
+
+    let mut buffer = CIPHERTEXT.to_vec();
+    buffer[0] ^= 0x01;
+    let tag = Tag::from(TAG);
+    assert!(
+        cipher
+            .decrypt_in_place_detached(nonce, AAD, &mut buffer, &tag)
+            .is_err()
+    );
+
+    let mut buffer = CIPHERTEXT.to_vec();
+    let mut bad_tag = TAG;
+    bad_tag[15] ^= 0x80;
+    assert!(
+        cipher
+            .decrypt_in_place_detached(nonce, AAD, &mut buffer, &Tag::from(bad_tag))
+            .is_err()
+    );
+    // The ciphertext is only decrypted once the tag is verified
+    assert_eq!(buffer, CIPHERTEXT);
+
+    let mut buffer = CIPHERTEXT.to_vec();
+    assert!(
+        cipher
+            .decrypt_in_place_detached(nonce, b"", &mut buffer, &tag)
+            .is_err()
+    );
+
+    println!("Authentication failure tests passed");
+}
+
+fn test_nonce_reuse() {
+    println!("Testing nonce handling");
+
+    let cipher = XChaCha20Poly1305::new(Key::from_slice(&KEY));
+    let first = b"temp0=21.5";
+    let second = b"volt1=3300";
+
+    // Reusing a nonce reuses the keystream: the XOR of two ciphertexts is
+    // the XOR of the plaintexts, so every message needs its own nonce
+    let nonce = XNonce::from([0; 24]);
+    let a = cipher.encrypt(&nonce, &first[..]).unwrap();
+    let b = cipher.encrypt(&nonce, &second[..]).unwrap();
+    for i in 0..first.len() {
+        assert_eq!(a[i] ^ b[i], first[i] ^ second[i]);
+    }
+
+    // A message counter in the 192 bit nonce of XChaCha20 keeps them
+    // unique, the remaining bytes can hold a per-boot random value
+    let mut sealed = Vec::new();
+    for seq in 0u64..4 {
+        let mut nonce = [0u8; 24];
+        nonce[16..].copy_from_slice(&seq.to_be_bytes());
+        let nonce = XNonce::from(nonce);
+        sealed.push(cipher.encrypt(&nonce, &first[..]).unwrap());
+    }
+    sealed.sort();
+    sealed.dedup();
+    assert_eq!(sealed.len(), 4);
+
+    println!("Nonce handling tests passed");
+}
+
+fn report_throughput() {
+    println!("Throughput, {} byte messages:", THROUGHPUT_BUFFER);
+
+    let nonce = Nonce::from_slice(&NONCE);
+    let cipher = ChaCha20Poly1305::new(Key::from_slice(&KEY));
+    let mut buffer = vec![0x5a; THROUGHPUT_BUFFER];
+
+    let start = Instant::now();
+    for _ in 0..THROUGHPUT_ITERATIONS {
+        cipher
+            .encrypt_in_place_detached(nonce, b"", &mut buffer)
+            .unwrap();
+    }
+    let elapsed = start.elapsed().as_secs_f64();
+    let bytes = (THROUGHPUT_BUFFER as u32 * THROUGHPUT_ITERATIONS) as f64;
+    println!(
+        "  {:<18} {:>8.2} MB/s",
+        "ChaCha20-Poly1305",
+        bytes / 1_000_000.0 / elapsed
+    );
+}
+
+#[unsafe(no_mangle)]
+pub fn rust_crate_test_chacha20poly1305_main() {
+    println!("Running chacha20poly1305 tests");
+
+    test_rfc8439_vector();
+    test_tampering();
+    test_nonce_reuse();
+    report_throughput();
+
+    println!("All chacha20poly1305 tests completed");
+}
@@ -0,0 +1,244 @@
+// Copyright (c) 2025 Xiaomi Corporation
+// SPDX-License-Identifier: Apache-2.0
+
+//! ciborium example for NuttX
+//!
+//! Round-trips the `Person`/`Address` structs used by the serde_json crate,
+//! plus enums, maps and byte arrays, as CBOR (RFC 8949) and checks the exact
+//! wire bytes. Also decodes into the dynamic `ciborium::Value` type.
+
+use ciborium::Value;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct Person {
+    name: String,
+    age: u32,
+    phones: Vec<String>,
+    address: Address,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct Address {
+    street: String,
+    city: String,
+    zip: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+enum Command {
+    Reset,
+    SetLevel(u8),
+    Move { x: i16, y: i16 },
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct Frame {
+    id: u16,
+    #[serde(with = "serde_bytes")]
+    payload: Vec<u8>,
+    checksum: [u8; 4],
+}
+
+fn sample_person() -> Person {
+    Person {
+        name: "Ann".to_string(),
+        age: 30,
+        phones: vec!["555-1234".to_string()],
+        address: Address {
+            street: "Main St".to_string(),
+            city: "Oslo".to_string(),
+            zip: "0150".to_string(),
+        },
+    }
+}
+
+fn sample_map() -> BTreeMap<String, u32> {
+    BTreeMap::from([("temp".to_string(), 21), ("volt".to_string(), 3300)])
+}
+
+fn sample_frame() -> Frame {
+    Frame {
+        id: 0x0102,
+        payload: vec![0xde, 0xad, 0xbe, 0xef, 0x00],
+        checksum: [1, 2, 3, 4],
+    }
+}
+
+fn encode<T: Serialize>(value: &T) -> Vec<u8> {
+    let mut buf = Vec::new();
+    ciborium::into_writer(value, &mut buf).unwrap();
+    buf
+}
+
+fn decode<T: serde::de::DeserializeOwned>(bytes: &[u8]) -> T {
+    ciborium::from_reader(bytes).unwrap()
+}
+
+fn test_person_roundtrip() {
+    println!("Testing Person round-trip");
+
+    let person = sample_person();
+    let encoded = encode(&person);
+    println!("Encoded Person: {} bytes", encoded.len());
+
+    // Structs are maps keyed by field name, text strings carry their length
+    // in the low bits of the 0x60 major type
+    let expected: &[u8] = &[
+        0xa4, // map(4)
+        0x64, b'n', b'a', b'm', b'e', 0x63, b'A', b'n', b'n', // name
+        0x63, b'a', b'g', b'e', 0x18, 0x1e, // age
+        0x66, b'p', b'h', b'o', b'n', b'e', b's', 0x81, 0x68, b'5', b'5', b'5', b'-', b'1', b'2',
+        b'3', b'4', // phones
+        0x67, b'a', b'd', b'd', b'r', b'e', b's', b's', 0xa3, // address
+        0x66, b's', b't', b'r', b'e', b'e', b't', 0x67, b'M', b'a', b'i', b'n', b' ', b'S', b't',
+        0x64, b'c', b'i', b't', b'y', 0x64, b'O', b's', b'l', b'o', // address.city
+        0x63, b'z', b'i', b'p', 0x64, b'0', b'1', b'5', b'0', // address.zip
+    ];
+    assert_eq!(encoded, expected);
+
+    let decoded: Person = decode(&encoded);
+    assert_eq!(decoded, person);
+
+    println!("Person round-trip tests passed");
+}
+
+fn test_enum_roundtrip() {
+    println!("Testing enum round-trip");
+
+    // Unit variants are plain strings, the others a single entry map
+    let cases: [(Command, &[u8]); 3] = [
+        (Command::Reset, &[0x65, b'R', b'e', b's', b'e', b't']),
+        (
+            Command::SetLevel(200),
+            &[
+                0xa1, 0x68, b'S', b'e', b't', b'L', b'e', b'v', b'e', b'l', 0x18, 0xc8,
+            ],
+        ),
+        (
+            Command::Move { x: -2, y: 300 },
+            &[
+                0xa1, 0x64, b'M', b'o', b'v', b'e', 0xa2, 0x61, b'x', 0x21, 0x61, b'y', 0x19, 0x01,
+                0x2c,
+            ],
+        ),
+    ];
+
+    for (command, expected) in cases {
+        let encoded = encode(&command);
+        println!("{:?} -> {:02x?}", command, encoded);
+        assert_eq!(encoded, expected);
+
+        let decoded: Command = decode(&encoded);
+        assert_eq!(decoded, command);
+    }
+
+    println!("Enum round-trip tests passed");
+}
+
+fn test_map_roundtrip() {
+    println!("Testing map round-trip");
+
+    let map = sample_map();
+    let encoded = encode(&map);
+    assert_eq!(
+        encoded,
+        [
+            0xa2, 0x64, b't', b'e', b'm', b'p', 0x15, 0x64, b'v', b'o', b'l', b't', 0x19, 0x0c,
+            0xe4
+        ]
+    );
+
+    let decoded: BTreeMap<String, u32> = decode(&encoded);
+    assert_eq!(decoded, map);
+
+    println!("Map round-trip tests passed");
+}
+
+fn test_bytes_roundtrip() {
+    println!("Testing byte array round-trip");
+
+    // serde_bytes produces a CBOR byte string (0x45), a fixed array stays an
+    // array of integers (0x84)
+    let frame = sample_frame();
+    let encoded = encode(&frame);
+    let expected: &[u8] = &[
+        0xa3, // map(3)
+        0x62, b'i', b'd', 0x19, 0x01, 0x02, // id
+        0x67, b'p', b'a', b'y', b'l', b'o', b'a', b'd', 0x45, 0xde, 0xad, 0xbe, 0xef,
+        0x00, // payload
+        0x68, b'c', b'h', b'e', b'c', b'k', b's', b'u', b'm', 0x84, 0x01, 0x02, 0x03,
+        0x04, // checksum
+    ];
+    assert_eq!(encoded, expected);
+
+    let decoded: Frame = decode(&encoded);
+    assert_eq!(decoded, frame);
+
+    println!("Byte array round-trip tests passed");
+}
+
+fn test_dynamic_value() {
+    println!("Testing ciborium::Value");
+
+    let value: Value = decode(&encode(&sample_frame()));
+    let entries = value.as_map().expect("frame decodes to a map");
+    assert_eq!(entries.len(), 3);
+
+    let field = |name: &str| {
+        entries
+            .iter()
+            .find(|(key, _)| key.as_text() == Some(name))
+            .map(|(_, value)| value)
+            .unwrap()
+    };
+
+    assert_eq!(field("id").as_integer(), Some(0x0102.into()));
+    assert_eq!(
+        field("payload").as_bytes().map(Vec::as_slice),
+        Some(&[0xde, 0xad, 0xbe, 0xef, 0x00][..])
+    );
+    assert_eq!(field("checksum").as_array().map(Vec::len), Some(4));
+
+    // Values can be built by hand and decoded into typed structs
+    let command = Value::Map(vec![(
+        Value::Text("SetLevel".to_string()),
+        Value::Integer(7.into()),
+    )]);
+    let decoded: Command = decode(&encode(&command));
+    assert_eq!(decoded, Command::SetLevel(7));
+
+    // Out of range integers are rejected instead of truncated
+    let too_big = encode(&Value::Integer(70000.into()));
+    assert!(ciborium::from_reader::<u16, _>(too_big.as_slice()).is_err());
+
+    println!("ciborium::Value tests passed");
+}
+
+fn report_sizes() {
+    println!("Encoded sizes:");
+
+    let sizes = [
+        ("person", encode(&sample_person()).len()),
+        ("map", encode(&sample_map()).len()),
+        ("frame", encode(&sample_frame()).len()),
+    ];
+    for (name, size) in sizes {
+        println!("  {:<8} {:>3} bytes", name, size);
+    }
+}
+
+#[unsafe(no_mangle)]
+pub fn rust_crate_test_ciborium_main() {
+    println!("Running ciborium tests");
+
+    test_person_roundtrip();
+    test_enum_roundtrip();
+    test_map_roundtrip();
+    test_bytes_roundtrip();
+    test_dynamic_value();
+    report_sizes();
+
+    println!("All ciborium tests completed");
+}
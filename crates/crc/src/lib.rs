@@ -0,0 +1,289 @@
+// Copyright (c) 2025 Xiaomi Corporation
+// SPDX-License-Identifier: Apache-2.0
+
+//! crc example for NuttX
+//!
+//! Checks CRC algorithms from the catalog of the crc crate, crc32fast and
+//! the Adler-32 of adler2 against the standard check values of the CRC
+//! RevEng catalogue and zlib, compares the accelerated paths with plain
+//! bitwise and per-byte references, and measures throughput.
+
+use crc::{
+    Algorithm, CRC_8_AUTOSAR, CRC_8_MAXIM_DOW, CRC_8_SMBUS, CRC_16_ARC, CRC_16_IBM_3740,
+    CRC_16_IBM_SDLC, CRC_16_KERMIT, CRC_16_MODBUS, CRC_16_XMODEM, CRC_32_AUTOSAR, CRC_32_BZIP2,
+    CRC_32_CKSUM, CRC_32_ISCSI, CRC_32_ISO_HDLC, CRC_32_MPEG_2, CRC_64_ECMA_182, CRC_64_GO_ISO,
+    CRC_64_XZ, Crc, NoTable, Table,
+};
+use std::time::Instant;
+
+const THROUGHPUT_BUFFER: usize = 64 * 1024;
+const THROUGHPUT_ITERATIONS: u32 = 4;
+
+type Checksum<'a> = &'a dyn Fn(&[u8]) -> u32;
+
+/// The message every check value in the catalogue is computed over
+const CHECK_INPUT: &[u8] = b"123456789";
+
+/// Check values from the CRC RevEng catalogue
+const CRC8_CHECKS: &[(&str, &Algorithm<u8>, u8)] = &[
+    ("CRC-8/SMBUS", &CRC_8_SMBUS, 0xf4),
+    ("CRC-8/MAXIM-DOW", &CRC_8_MAXIM_DOW, 0xa1),
+    ("CRC-8/AUTOSAR", &CRC_8_AUTOSAR, 0xdf),
+];
+
+/// IBM-3740 is often called CRC-16/CCITT-FALSE, and KERMIT the true
+/// CRC-16/CCITT
+const CRC16_CHECKS: &[(&str, &Algorithm<u16>, u16)] = &[
+    ("CRC-16/IBM-3740", &CRC_16_IBM_3740, 0x29b1),
+    ("CRC-16/KERMIT", &CRC_16_KERMIT, 0x2189),
+    ("CRC-16/XMODEM", &CRC_16_XMODEM, 0x31c3),
+    ("CRC-16/IBM-SDLC", &CRC_16_IBM_SDLC, 0x906e),
+    ("CRC-16/MODBUS", &CRC_16_MODBUS, 0x4b37),
+    ("CRC-16/ARC", &CRC_16_ARC, 0xbb3d),
+];
+
+const CRC32_CHECKS: &[(&str, &Algorithm<u32>, u32)] = &[
+    ("CRC-32/ISO-HDLC", &CRC_32_ISO_HDLC, 0xcbf43926),
+    ("CRC-32/ISCSI", &CRC_32_ISCSI, 0xe3069283),
+    ("CRC-32/BZIP2", &CRC_32_BZIP2, 0xfc891918),
+    ("CRC-32/MPEG-2", &CRC_32_MPEG_2, 0x0376e6e7),
+    ("CRC-32/CKSUM", &CRC_32_CKSUM, 0x765e7680),
+    ("CRC-32/AUTOSAR", &CRC_32_AUTOSAR, 0x1697d06a),
+];
+
+const CRC64_CHECKS: &[(&str, &Algorithm<u64>, u64)] = &[
+    ("CRC-64/XZ", &CRC_64_XZ, 0x995dc9bbdf1939fa),
+    ("CRC-64/ECMA-182", &CRC_64_ECMA_182, 0x6c40df5f0b497347),
+    ("CRC-64/GO-ISO", &CRC_64_GO_ISO, 0xb90956c775a41001),
+];
+
+/// Runs each algorithm without a table, with the default 256 entry table
+/// and with slice-by-16, which must all agree with the check value
+macro_rules! check_catalog {
+    ($width:ty, $checks:expr) => {
+        for &(name, algorithm, check) in $checks {
+            let no_table = Crc::<$width, NoTable>::new(algorithm).checksum(CHECK_INPUT);
+            let table = Crc::<$width, Table<1>>::new(algorithm).checksum(CHECK_INPUT);
+            let slice16 = Crc::<$width, Table<16>>::new(algorithm).checksum(CHECK_INPUT);
+            println!(
+                "  {:<16} {:#0w$x}",
+                name,
+                check,
+                w = 2 + 2 * std::mem::size_of::<$width>()
+            );
+            assert_eq!(algorithm.check, check);
+            assert_eq!((no_table, table, slice16), (check, check, check));
+        }
+    };
+}
+
+/// A frame with its CRC appended in wire order checks to a constant, so a
+/// receiver can verify it without splitting the CRC off first
+macro_rules! check_residue {
+    ($width:ty, $checks:expr, $payload:expr) => {
+        for &(_, algorithm, _) in $checks {
+            let crc = Crc::<$width>::new(algorithm);
+            let value = crc.checksum($payload);
+            let mut frame = $payload.to_vec();
+            if algorithm.refout {
+                frame.extend_from_slice(&value.to_le_bytes());
+            } else {
+                frame.extend_from_slice(&value.to_be_bytes());
+            }
+            assert_eq!(crc.checksum(&frame), algorithm.residue ^ algorithm.xorout);
+        }
+    };
+}
+
+/// Deterministic test data without any repeating pattern
+fn test_data(len: usize) -> Vec<u8> {
+    let mut state = 0x2545_f491u32;
+    (0..len)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            state as u8
+        })
+        .collect()
+}
+
+fn test_catalog() {
+    println!("Testing crc catalog check values");
+
+    check_catalog!(u8, CRC8_CHECKS);
+    check_catalog!(u16, CRC16_CHECKS);
+    check_catalog!(u32, CRC32_CHECKS);
+    check_catalog!(u64, CRC64_CHECKS);
+
+    println!("crc catalog check values passed");
+}
+
+fn test_frames() {
+    println!("Testing CRC framed messages");
+
+    let payload = b"\x7e\x01\x10nuttx uart frame";
+    check_residue!(u8, CRC8_CHECKS, payload);
+    check_residue!(u16, CRC16_CHECKS, payload);
+    check_residue!(u32, CRC32_CHECKS, payload);
+    check_residue!(u64, CRC64_CHECKS, payload);
+
+    // A flash image checksummed while it streams in, in uneven chunks
+    let image = test_data(10_000);
+    let crc = Crc::<u32, Table<16>>::new(&CRC_32_ISO_HDLC);
+    let mut digest = crc.digest();
+    for chunk in image.chunks(333) {
+        digest.update(chunk);
+    }
+    assert_eq!(digest.finalize(), crc.checksum(&image));
+
+    // A single flipped bit is always detected
+    let mut corrupted = image.clone();
+    corrupted[4321] ^= 0x08;
+    assert_ne!(crc.checksum(&corrupted), crc.checksum(&image));
+
+    println!("CRC framed message tests passed");
+}
+
+/// Which crc32fast implementation this CPU gets; anything but x86 with
+/// PCLMULQDQ or aarch64 with the CRC extension uses the portable table
+fn crc32fast_path() -> &'static str {
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    if is_x86_feature_detected!("pclmulqdq") && is_x86_feature_detected!("sse4.1") {
+        return "pclmulqdq";
+    }
+    #[cfg(target_arch = "aarch64")]
+    if std::arch::is_aarch64_feature_detected!("crc") {
+        return "aarch64 crc";
+    }
+    "baseline table"
+}
+
+fn test_crc32fast() {
+    println!("Testing crc32fast ({})", crc32fast_path());
+
+    assert_eq!(crc32fast::hash(CHECK_INPUT), 0xcbf43926);
+    assert_eq!(crc32fast::hash(b""), 0);
+    assert_eq!(crc32fast::hash(&[0xff; 100_000]), 0x68c6cec4);
+
+    // The accelerated paths fold 64 byte blocks and finish the tail with
+    // the table, so sweep lengths and start offsets across both against the
+    // bitwise crc implementation
+    let reference = Crc::<u32, NoTable>::new(&CRC_32_ISO_HDLC);
+    let data = test_data(512);
+    for offset in 0..16 {
+        for len in 0..=320 {
+            let slice = &data[offset..offset + len];
+            assert_eq!(
+                crc32fast::hash(slice),
+                reference.checksum(slice),
+                "offset {} len {}",
+                offset,
+                len
+            );
+        }
+    }
+
+    // Two halves hashed apart, say on two cores, combine into the whole
+    let data = test_data(10_000);
+    let (head, tail) = data.split_at(3_000);
+    let mut first = crc32fast::Hasher::new();
+    first.update(head);
+    let mut second = crc32fast::Hasher::new();
+    second.update(tail);
+    first.combine(&second);
+    assert_eq!(first.finalize(), crc32fast::hash(&data));
+
+    // Resuming from a stored CRC, as after a power cut during an update
+    let mut resumed = crc32fast::Hasher::new_with_initial(crc32fast::hash(head));
+    resumed.update(tail);
+    assert_eq!(resumed.finalize(), crc32fast::hash(&data));
+
+    println!("crc32fast tests passed");
+}
+
+/// Adler-32 straight from RFC 1950, reducing after every byte
+fn naive_adler32(data: &[u8]) -> u32 {
+    let (mut a, mut b) = (1u32, 0u32);
+    for &byte in data {
+        a = (a + byte as u32) % 65521;
+        b = (b + a) % 65521;
+    }
+    (b << 16) | a
+}
+
+fn test_adler32() {
+    println!("Testing adler2");
+
+    assert_eq!(adler2::adler32_slice(b""), 1);
+    assert_eq!(adler2::adler32_slice(CHECK_INPUT), 0x091e01de);
+    assert_eq!(adler2::adler32_slice(b"Wikipedia"), 0x11e60398);
+    // zlib.adler32(b"\xff" * 100000)
+    assert_eq!(adler2::adler32_slice(&[0xff; 100_000]), 0x149a302c);
+
+    // adler2 defers the modulo for up to 5552 bytes, the most that cannot
+    // overflow, so check lengths around that against the per-byte version
+    let data = test_data(16 * 5552 + 7);
+    for len in (0..64).chain([5551, 5552, 5553, 2 * 5552, data.len()]) {
+        assert_eq!(
+            adler2::adler32_slice(&data[..len]),
+            naive_adler32(&data[..len]),
+            "len {}",
+            len
+        );
+    }
+
+    let mut adler = adler2::Adler32::new();
+    for chunk in data.chunks(1000) {
+        adler.write_slice(chunk);
+    }
+    assert_eq!(adler.checksum(), naive_adler32(&data));
+    assert_eq!(adler2::adler32(&data[..]).unwrap(), naive_adler32(&data));
+
+    println!("adler2 tests passed");
+}
+
+fn report_throughput() {
+    println!("Throughput, {} byte buffer:", THROUGHPUT_BUFFER);
+
+    let buffer = test_data(THROUGHPUT_BUFFER);
+    let bytes = (THROUGHPUT_BUFFER as u32 * THROUGHPUT_ITERATIONS) as f64;
+    let crc32_no_table = Crc::<u32, NoTable>::new(&CRC_32_ISO_HDLC);
+    let crc32_table = Crc::<u32, Table<1>>::new(&CRC_32_ISO_HDLC);
+    let crc32_slice16 = Crc::<u32, Table<16>>::new(&CRC_32_ISO_HDLC);
+    let crc16_table = Crc::<u16, Table<1>>::new(&CRC_16_IBM_3740);
+    let crc16_slice16 = Crc::<u16, Table<16>>::new(&CRC_16_IBM_3740);
+    let checksums: &[(&str, Checksum)] = &[
+        ("CRC-32 bitwise", &|data| crc32_no_table.checksum(data)),
+        ("CRC-32 table", &|data| crc32_table.checksum(data)),
+        ("CRC-32 slice-16", &|data| crc32_slice16.checksum(data)),
+        ("CRC-16 table", &|data| crc16_table.checksum(data) as u32),
+        ("CRC-16 slice-16", &|data| {
+            crc16_slice16.checksum(data) as u32
+        }),
+        ("crc32fast", &crc32fast::hash),
+        ("adler2", &adler2::adler32_slice),
+    ];
+
+    for (name, checksum) in checksums {
+        let start = Instant::now();
+        for _ in 0..THROUGHPUT_ITERATIONS {
+            std::hint::black_box(checksum(std::hint::black_box(&buffer)));
+        }
+        let elapsed = start.elapsed().as_secs_f64();
+        println!("  {:<16} {:>8.2} MB/s", name, bytes / 1_000_000.0 / elapsed);
+    }
+}
+
+#[unsafe(no_mangle)]
+pub fn rust_crate_test_crc_main() {
+    println!("Running checksum tests");
+
+    test_catalog();
+    test_frames();
+    test_crc32fast();
+    test_adler32();
+    report_throughput();
+
+    println!("All checksum tests completed");
+}
@@ -0,0 +1,276 @@
+// Copyright (c) 2025 Xiaomi Corporation
+// SPDX-License-Identifier: Apache-2.0
+
+//! ed25519-dalek example for NuttX
+//!
+//! Checks Ed25519 against the test vectors of RFC 8032 and a set of
+//! Wycheproof-style invalid signatures, generates keys from /dev/urandom and
+//! measures signing and verification time.
+
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use hex_literal::hex;
+use rand_core::OsRng;
+use std::fs::File;
+use std::io::Read;
+use std::num::NonZeroU32;
+use std::time::Instant;
+
+const TIMING_ITERATIONS: u32 = 4;
+
+/// getrandom 0.2 has no NuttX backend, so entropy comes from /dev/urandom.
+/// The same backend is copied unchanged into ed25519_dalek, x25519_dalek,
+/// p256 and rustls
+fn dev_urandom(buf: &mut [u8]) -> Result<(), getrandom::Error> {
+    File::open("/dev/urandom")
+        .and_then(|mut file| file.read_exact(buf))
+        .map_err(|err| {
+            err.raw_os_error()
+                .and_then(|code| NonZeroU32::new(code as u32))
+                .map_or(getrandom::Error::UNEXPECTED, getrandom::Error::from)
+        })
+}
+
+getrandom::register_custom_getrandom!(dev_urandom);
+
+/// RFC 8032, section 7.1, TEST 1
+const SECRET_1: [u8; 32] = hex!("9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60");
+const PUBLIC_1: [u8; 32] = hex!("d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a");
+
+/// RFC 8032, section 7.1, TEST 2
+const SECRET_2: [u8; 32] = hex!("4ccd089b28ff96da9db6c346ec114e0f5b8a319f35aba624da8cf6ed4fb8a6fb");
+const PUBLIC_2: [u8; 32] = hex!("3d4017c3e843895a92b70aa74d1b7ebc9c982ccf2ec4968cc0cd55f12af4660c");
+
+struct TestCase {
+    tc_id: u32,
+    comment: &'static str,
+    public: [u8; 32],
+    msg: &'static [u8],
+    sig: &'static [u8],
+    valid: bool,
+}
+
+/// Laid out like the Wycheproof eddsa_test.json groups
+const TEST_CASES: &[TestCase] = &[
+    TestCase {
+        tc_id: 1,
+        comment: "RFC 8032 TEST 1",
+        public: PUBLIC_1,
+        msg: b"",
+        sig: &hex!(
+            "e5564300c360ac729086e2cc806e828a84877f1eb8e5d974d873e06522490155"
+            "5fb8821590a33bacc61e39701cf9b46bd25bf5f0595bbe24655141438e7a100b"
+        ),
+        valid: true,
+    },
+    TestCase {
+        tc_id: 2,
+        comment: "RFC 8032 TEST 2",
+        public: PUBLIC_2,
+        msg: &hex!("72"),
+        sig: &hex!(
+            "92a009a9f0d4cab8720e820b5f642540a2b27b5416503f8fb3762223ebdb69da"
+            "085ac1e43e15996e458f3613d0f11d8c387b2eaeb4302aeeb00d291612bb0c00"
+        ),
+        valid: true,
+    },
+    TestCase {
+        tc_id: 3,
+        comment: "signature of another key",
+        public: PUBLIC_1,
+        msg: &hex!("72"),
+        sig: &hex!(
+            "92a009a9f0d4cab8720e820b5f642540a2b27b5416503f8fb3762223ebdb69da"
+            "085ac1e43e15996e458f3613d0f11d8c387b2eaeb4302aeeb00d291612bb0c00"
+        ),
+        valid: false,
+    },
+    TestCase {
+        tc_id: 4,
+        comment: "modified message",
+        public: PUBLIC_2,
+        msg: &hex!("73"),
+        sig: &hex!(
+            "92a009a9f0d4cab8720e820b5f642540a2b27b5416503f8fb3762223ebdb69da"
+            "085ac1e43e15996e458f3613d0f11d8c387b2eaeb4302aeeb00d291612bb0c00"
+        ),
+        valid: false,
+    },
+    TestCase {
+        tc_id: 5,
+        comment: "modified bit 0 in R",
+        public: PUBLIC_1,
+        msg: b"",
+        sig: &hex!(
+            "e4564300c360ac729086e2cc806e828a84877f1eb8e5d974d873e06522490155"
+            "5fb8821590a33bacc61e39701cf9b46bd25bf5f0595bbe24655141438e7a100b"
+        ),
+        valid: false,
+    },
+    TestCase {
+        tc_id: 6,
+        comment: "modified bit 0 in S",
+        public: PUBLIC_1,
+        msg: b"",
+        sig: &hex!(
+            "e5564300c360ac729086e2cc806e828a84877f1eb8e5d974d873e06522490155"
+            "5eb8821590a33bacc61e39701cf9b46bd25bf5f0595bbe24655141438e7a100b"
+        ),
+        valid: false,
+    },
+    TestCase {
+        tc_id: 7,
+        comment: "S replaced by S + L",
+        public: PUBLIC_1,
+        msg: b"",
+        sig: &hex!(
+            "e5564300c360ac729086e2cc806e828a84877f1eb8e5d974d873e06522490155"
+            "4c8c7872aa064e049dbb3013fbf29380d25bf5f0595bbe24655141438e7a101b"
+        ),
+        valid: false,
+    },
+    TestCase {
+        tc_id: 8,
+        comment: "modified bit 255 in S",
+        public: PUBLIC_1,
+        msg: b"",
+        sig: &hex!(
+            "e5564300c360ac729086e2cc806e828a84877f1eb8e5d974d873e06522490155"
+            "5fb8821590a33bacc61e39701cf9b46bd25bf5f0595bbe24655141438e7a108b"
+        ),
+        valid: false,
+    },
+    TestCase {
+        tc_id: 9,
+        comment: "truncated signature",
+        public: PUBLIC_1,
+        msg: b"",
+        sig: &hex!(
+            "e5564300c360ac729086e2cc806e828a84877f1eb8e5d974d873e06522490155"
+            "5fb8821590a33bacc61e39701cf9b46bd25bf5f0595bbe24655141438e7a10"
+        ),
+        valid: false,
+    },
+    TestCase {
+        tc_id: 10,
+        comment: "all zero signature",
+        public: PUBLIC_1,
+        msg: b"",
+        sig: &[0; 64],
+        valid: false,
+    },
+];
+
+fn test_rfc8032_keys() {
+    println!("Testing RFC 8032 key derivation and signing");
+
+    for (secret, public, msg, case) in [
+        (SECRET_1, PUBLIC_1, &b""[..], &TEST_CASES[0]),
+        (SECRET_2, PUBLIC_2, &hex!("72")[..], &TEST_CASES[1]),
+    ] {
+        let signing_key = SigningKey::from_bytes(&secret);
+        assert_eq!(signing_key.verifying_key().to_bytes(), public);
+
+        // Ed25519 signatures are deterministic
+        let sig = signing_key.sign(msg);
+        assert_eq!(sig.to_bytes()[..], *case.sig);
+    }
+
+    println!("RFC 8032 key derivation and signing passed");
+}
+
+fn test_vectors() {
+    println!("Testing Wycheproof-style verification vectors");
+
+    for case in TEST_CASES {
+        let key = VerifyingKey::from_bytes(&case.public).unwrap();
+        let result = Signature::from_slice(case.sig)
+            .and_then(|sig| key.verify_strict(case.msg, &sig).map(|_| sig))
+            .and_then(|sig| key.verify(case.msg, &sig));
+        println!(
+            "  tcId {:>2} {:<28} {}",
+            case.tc_id,
+            case.comment,
+            if result.is_ok() { "valid" } else { "invalid" }
+        );
+        assert_eq!(result.is_ok(), case.valid);
+    }
+
+    println!("Wycheproof-style vectors passed");
+}
+
+fn test_small_order_key() {
+    println!("Testing small order public key");
+
+    // The identity point as public key, with R the identity and S zero,
+    // satisfies the verification equation for every message
+    let identity = hex!("0100000000000000000000000000000000000000000000000000000000000000");
+    let key = VerifyingKey::from_bytes(&identity).unwrap();
+    assert!(key.is_weak());
+
+    let mut forged = [0u8; 64];
+    forged[..32].copy_from_slice(&identity);
+    let forged = Signature::from_bytes(&forged);
+
+    // verify() follows RFC 8032 and accepts it, verify_strict() rejects
+    // weak keys and is what firmware signature checks should use
+    assert!(key.verify(b"any firmware", &forged).is_ok());
+    assert!(key.verify_strict(b"any firmware", &forged).is_err());
+
+    println!("Small order public key tests passed");
+}
+
+fn test_generated_key() {
+    println!("Testing key generation from /dev/urandom");
+
+    let first = SigningKey::generate(&mut OsRng);
+    let second = SigningKey::generate(&mut OsRng);
+    assert_ne!(first.to_bytes(), second.to_bytes());
+
+    let msg = b"nuttx firmware 1.4.2";
+    let sig = first.sign(msg);
+    first.verifying_key().verify_strict(msg, &sig).unwrap();
+    assert!(second.verifying_key().verify_strict(msg, &sig).is_err());
+
+    // Only the 32 byte public key is needed on the device
+    let public = VerifyingKey::from_bytes(&first.verifying_key().to_bytes()).unwrap();
+    public.verify_strict(msg, &sig).unwrap();
+
+    println!("Key generation tests passed");
+}
+
+fn report_timing() {
+    println!("Timing, {} iterations:", TIMING_ITERATIONS);
+
+    let signing_key = SigningKey::from_bytes(&SECRET_1);
+    let verifying_key = signing_key.verifying_key();
+    let msg = [0x5a; 256];
+
+    let start = Instant::now();
+    for _ in 0..TIMING_ITERATIONS {
+        std::hint::black_box(signing_key.sign(&msg));
+    }
+    let sign = start.elapsed() / TIMING_ITERATIONS;
+
+    let sig = signing_key.sign(&msg);
+    let start = Instant::now();
+    for _ in 0..TIMING_ITERATIONS {
+        verifying_key.verify_strict(&msg, &sig).unwrap();
+    }
+    let verify = start.elapsed() / TIMING_ITERATIONS;
+
+    println!("  {:<8} {:>8} us", "sign", sign.as_micros());
+    println!("  {:<8} {:>8} us", "verify", verify.as_micros());
+}
+
+#[unsafe(no_mangle)]
+pub fn rust_crate_test_ed25519_dalek_main() {
+    println!("Running ed25519-dalek tests");
+
+    test_rfc8032_keys();
+    test_vectors();
+    test_small_order_key();
+    test_generated_key();
+    report_timing();
+
+    println!("All ed25519-dalek tests completed");
+}
@@ -0,0 +1,233 @@
+// Copyright (c) 2025 Xiaomi Corporation
+// SPDX-License-Identifier: Apache-2.0
+
+//! embassy-executor example for NuttX
+//!
+//! Runs the raw embassy executor on a std thread, together with the std time
+//! driver of `embassy-time`. Task pools are allocated statically, and there
+//! is no IO reactor, so file descriptors are used in non-blocking mode and
+//! retried on a timer.
+
+use std::io::{self, Read, Write};
+use std::os::fd::AsRawFd;
+use std::ptr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Condvar, Mutex, OnceLock, mpsc};
+use std::thread;
+
+use embassy_executor::{Spawner, raw};
+use embassy_sync::blocking_mutex::raw::CriticalSectionRawMutex;
+use embassy_sync::channel::Channel;
+use embassy_time::{Duration, Instant, Ticker, Timer};
+
+/// Wakes the executor thread when one of its tasks is ready to run
+struct Signaler {
+    pending: Mutex<bool>,
+    condvar: Condvar,
+}
+
+impl Signaler {
+    const fn new() -> Self {
+        Self {
+            pending: Mutex::new(false),
+            condvar: Condvar::new(),
+        }
+    }
+
+    fn signal(&self) {
+        *self.pending.lock().unwrap() = true;
+        self.condvar.notify_one();
+    }
+
+    fn wait(&self) {
+        let mut pending = self.pending.lock().unwrap();
+        while !*pending {
+            pending = self.condvar.wait(pending).unwrap();
+        }
+        *pending = false;
+    }
+}
+
+static SIGNALER: Signaler = Signaler::new();
+
+/// Called by the raw executor whenever a task is woken
+#[unsafe(export_name = "__pender")]
+fn pender(_context: *mut ()) {
+    SIGNALER.signal();
+}
+
+/// The raw executor is not Sync, but only the thread in `run_executor`
+/// touches it, and `RUNNING` keeps two of them from overlapping
+struct ExecutorCell(raw::Executor);
+
+unsafe impl Send for ExecutorCell {}
+unsafe impl Sync for ExecutorCell {}
+
+/// Kept in static memory and reused by every run, so running the test again
+/// does not leak a new executor each time
+static EXECUTOR: OnceLock<ExecutorCell> = OnceLock::new();
+static RUNNING: Mutex<()> = Mutex::new(());
+
+/// Set by `main_task` once it has reported back, stops the executor thread
+static STOP: AtomicBool = AtomicBool::new(false);
+
+/// Results reported by the worker tasks
+static RESULTS: Channel<CriticalSectionRawMutex, (u32, u32), 4> = Channel::new();
+
+/// Wait on one-shot and periodic timers
+async fn test_timers() {
+    println!("Testing embassy_time::Timer");
+
+    let start = Instant::now();
+    Timer::after_millis(100).await;
+    let elapsed = start.elapsed();
+    println!(
+        "Timer::after_millis(100) elapsed: {} us",
+        elapsed.as_micros()
+    );
+    assert!(
+        elapsed >= Duration::from_millis(100),
+        "timer fired too early"
+    );
+
+    let start = Instant::now();
+    let mut ticker = Ticker::every(Duration::from_millis(20));
+    for _ in 0..5 {
+        ticker.next().await;
+    }
+    let elapsed = start.elapsed();
+    println!("Ticker produced 5 ticks in {} us", elapsed.as_micros());
+    assert!(
+        elapsed >= Duration::from_millis(100),
+        "ticker fired too early"
+    );
+}
+
+#[embassy_executor::task(pool_size = 4)]
+async fn worker(id: u32) {
+    Timer::after_millis(10 * u64::from(4 - id)).await;
+    RESULTS.send((id, id * id)).await;
+}
+
+/// Spawn several instances of a task from the static pool
+async fn test_spawn(spawner: Spawner) {
+    println!("Testing Spawner::spawn");
+
+    for id in 0..4 {
+        spawner.spawn(worker(id)).unwrap();
+    }
+
+    // The pool only has room for four running instances
+    assert!(
+        spawner.spawn(worker(4)).is_err(),
+        "task pool should be full"
+    );
+    println!("Fifth spawn rejected, task pool is full");
+
+    let mut results = Vec::new();
+    for _ in 0..4 {
+        results.push(RESULTS.receive().await);
+    }
+    println!("Worker results in completion order: {:?}", results);
+
+    results.sort();
+    assert_eq!(results, vec![(0, 0), (1, 1), (2, 4), (3, 9)]);
+}
+
+#[embassy_executor::task]
+async fn pipe_writer(mut writer: io::PipeWriter) {
+    for chunk in ["hello ", "from ", "embassy"] {
+        Timer::after_millis(20).await;
+        writer.write_all(chunk.as_bytes()).unwrap();
+    }
+    // Dropping the writer closes the pipe and ends the stream
+}
+
+/// Move data through a pipe read in non-blocking mode
+async fn test_pipe(spawner: Spawner) -> io::Result<()> {
+    println!("Testing non-blocking pipe");
+
+    let (mut reader, writer) = io::pipe()?;
+    let ret = unsafe { libc::fcntl(reader.as_raw_fd(), libc::F_SETFL, libc::O_NONBLOCK) };
+    if ret < 0 {
+        return Err(io::Error::last_os_error());
+    }
+
+    spawner.spawn(pipe_writer(writer)).unwrap();
+
+    let mut received = Vec::new();
+    let mut retries = 0;
+    let mut buf = [0u8; 32];
+    loop {
+        match reader.read(&mut buf) {
+            Ok(0) => break,
+            Ok(n) => received.extend_from_slice(&buf[..n]),
+            Err(e) if e.kind() == io::ErrorKind::WouldBlock => {
+                // Nothing to read yet, give the writer task a chance to run
+                retries += 1;
+                Timer::after_millis(5).await;
+            }
+            Err(e) => return Err(e),
+        }
+    }
+
+    let received = String::from_utf8(received).unwrap();
+    println!("Received from pipe: {} ({} retries)", received, retries);
+    assert_eq!(received, "hello from embassy");
+
+    Ok(())
+}
+
+#[embassy_executor::task]
+async fn main_task(spawner: Spawner, done: mpsc::Sender<io::Result<()>>) {
+    test_timers().await;
+    test_spawn(spawner).await;
+    let result = test_pipe(spawner).await;
+    done.send(result).unwrap();
+    STOP.store(true, Ordering::Relaxed);
+}
+
+/// Polls the executor until `main_task` has finished, all the tasks it
+/// spawned have finished before it
+fn run_executor(done: mpsc::Sender<io::Result<()>>) {
+    let executor = &EXECUTOR
+        .get_or_init(|| ExecutorCell(raw::Executor::new(ptr::null_mut())))
+        .0;
+    let spawner = executor.spawner();
+    spawner.spawn(main_task(spawner, done)).unwrap();
+
+    loop {
+        // Only this thread polls the executor, and never from the pender
+        unsafe { executor.poll() };
+        if STOP.swap(false, Ordering::Relaxed) {
+            break;
+        }
+        SIGNALER.wait();
+    }
+}
+
+#[unsafe(no_mangle)]
+pub fn rust_crate_test_embassy_executor_main() {
+    println!("Running embassy-executor tests");
+
+    // The executor gets its own thread, which reports back through a channel
+    // and returns once the tests are done
+    let _running = RUNNING.lock().unwrap_or_else(|err| err.into_inner());
+    let (done_tx, done_rx) = mpsc::channel();
+    let executor = thread::Builder::new()
+        .stack_size(8 * 1024)
+        .spawn(move || run_executor(done_tx))
+        .unwrap();
+
+    let result = done_rx.recv();
+    if executor.join().is_err() {
+        panic!("embassy executor stopped unexpectedly");
+    }
+    match result.unwrap() {
+        Ok(()) => println!("All embassy-executor tests completed"),
+        Err(e) => {
+            eprintln!("Pipe test failed: {}", e);
+            panic!("embassy-executor tests failed");
+        }
+    }
+}
@@ -0,0 +1,396 @@
+// Copyright (c) 2025 Xiaomi Corporation
+// SPDX-License-Identifier: Apache-2.0
+
+//! FlatBuffers example for NuttX
+//!
+//! Builds the telemetry `Device` message of the prost crate with code
+//! generated by flatc from schema/telemetry.fbs, and reads it in place from a
+//! byte buffer and from a file mapped from tmpfs. The decode cost report uses
+//! the same data as the ones in the prost and capnp crates.
+
+#[rustfmt::skip]
+#[allow(clippy::all, unused_imports)]
+mod telemetry_generated;
+
+use flatbuffers::{FlatBufferBuilder, VerifierOptions};
+use std::collections::BTreeMap;
+use std::ffi::c_void;
+use std::fs::{self, File};
+use std::hint::black_box;
+use std::os::fd::AsRawFd;
+use std::time::Instant;
+use telemetry_generated::nuttx::telemetry::{
+    Device, DeviceArgs, Label, LabelArgs, Location, LocationArgs, Reading, SensorKind,
+    device_buffer_has_identifier, finish_device_buffer, root_as_device, root_as_device_unchecked,
+    root_as_device_with_opts, size_prefixed_root_as_device,
+};
+
+const DEVICE_PATH: &str = "/tmp/flatbuffers_device.bin";
+
+/// Number of readings in the decode cost report, same as in the prost crate
+const REPORT_READINGS: usize = 64;
+const REPORT_ITERATIONS: u32 = 1000;
+
+/// Contents of a Device, the same values as `sample_device()` in the prost
+/// crate
+struct DeviceData {
+    id: &'static str,
+    site: &'static str,
+    rack: u32,
+    labels: BTreeMap<&'static str, &'static str>,
+    readings: Vec<Reading>,
+    firmware_hash: [u8; 4],
+    channels: [u32; 3],
+}
+
+fn sample_data() -> DeviceData {
+    DeviceData {
+        id: "dev0",
+        site: "lab",
+        rack: 3,
+        labels: BTreeMap::from([("fw", "1.2")]),
+        readings: vec![
+            Reading::new(SensorKind::Temperature, -40, 1_000_000),
+            Reading::new(SensorKind::Voltage, 3300, 0),
+        ],
+        firmware_hash: [0xde, 0xad, 0xbe, 0xef],
+        channels: [1, 2, 300],
+    }
+}
+
+fn report_data() -> DeviceData {
+    let mut data = sample_data();
+    data.readings = (0..REPORT_READINGS)
+        .map(|i| {
+            let kind = if i % 2 == 0 {
+                SensorKind::Temperature
+            } else {
+                SensorKind::Voltage
+            };
+            Reading::new(kind, i as i32 * 10 - 40, i as u64 * 1000)
+        })
+        .collect();
+    data
+}
+
+/// Builds a Device into `fbb`. Children are created before the tables that
+/// refer to them, since the buffer is written back to front.
+fn build_device(fbb: &mut FlatBufferBuilder, data: &DeviceData, online: bool) {
+    let id = fbb.create_string(data.id);
+    let site = fbb.create_string(data.site);
+    let location = Location::create(
+        fbb,
+        &LocationArgs {
+            site: Some(site),
+            rack: data.rack,
+        },
+    );
+
+    // Keyed tables must be sorted for lookup_by_key(), which BTreeMap
+    // already does for us
+    let labels: Vec<_> = data
+        .labels
+        .iter()
+        .map(|(key, value)| {
+            let key = fbb.create_string(key);
+            let value = fbb.create_string(value);
+            Label::create(
+                fbb,
+                &LabelArgs {
+                    key: Some(key),
+                    value: Some(value),
+                },
+            )
+        })
+        .collect();
+    let labels = fbb.create_vector(&labels);
+
+    let readings = fbb.create_vector(&data.readings);
+    let firmware_hash = fbb.create_vector(&data.firmware_hash);
+    let channels = fbb.create_vector(&data.channels);
+    let error = (!online).then(|| fbb.create_string("overheat"));
+
+    let device = Device::create(
+        fbb,
+        &DeviceArgs {
+            id: Some(id),
+            location: Some(location),
+            labels: Some(labels),
+            readings: Some(readings),
+            firmware_hash: Some(firmware_hash),
+            channels: Some(channels),
+            online,
+            error,
+        },
+    );
+    finish_device_buffer(fbb, device);
+}
+
+fn encode(data: &DeviceData) -> Vec<u8> {
+    let mut fbb = FlatBufferBuilder::new();
+    build_device(&mut fbb, data, true);
+    fbb.finished_data().to_vec()
+}
+
+fn check_sample(device: &Device) {
+    assert_eq!(device.id(), Some("dev0"));
+    let location = device.location().unwrap();
+    assert_eq!((location.site(), location.rack()), (Some("lab"), 3));
+
+    let readings = device.readings().unwrap();
+    assert_eq!(readings.len(), 2);
+    assert_eq!(readings.get(0).kind(), SensorKind::Temperature);
+    assert_eq!(readings.get(0).value(), -40);
+    assert_eq!(readings.get(0).timestamp_us(), 1_000_000);
+    assert_eq!(readings.get(1).value(), 3300);
+
+    assert_eq!(
+        device.firmware_hash().unwrap().bytes(),
+        [0xde, 0xad, 0xbe, 0xef]
+    );
+    let channels: Vec<u32> = device.channels().unwrap().iter().collect();
+    assert_eq!(channels, [1, 2, 300]);
+    assert!(device.online());
+    assert_eq!(device.error(), None);
+}
+
+fn test_build_and_read() {
+    println!("Testing build and in place read");
+
+    let buf = encode(&sample_data());
+    println!("Encoded Device: {} bytes", buf.len());
+    assert!(device_buffer_has_identifier(&buf));
+
+    let device = root_as_device(&buf).unwrap();
+    println!("Decoded: {:?}", device);
+    check_sample(&device);
+
+    // Accessors return references into the buffer instead of copies
+    let range = buf.as_ptr_range();
+    let first: &Reading = device.readings().unwrap().get(0);
+    assert!(range.contains(&(first as *const Reading as *const u8)));
+    assert!(range.contains(&device.id().unwrap().as_ptr()));
+    assert!(range.contains(&device.firmware_hash().unwrap().bytes().as_ptr()));
+
+    // Sorted keyed tables support binary search
+    let labels = device.labels().unwrap();
+    let fw = labels.lookup_by_key("fw", |label, key| label.key_compare_with_value(key));
+    assert_eq!(fw.and_then(|label| label.value()), Some("1.2"));
+    assert!(
+        labels
+            .lookup_by_key("board", |label, key| label.key_compare_with_value(key))
+            .is_none()
+    );
+
+    println!("Build and in place read tests passed");
+}
+
+fn test_defaults_and_absent_fields() {
+    println!("Testing defaults and absent fields");
+
+    // Fields equal to their default are not written at all
+    let mut fbb = FlatBufferBuilder::new();
+    let device = Device::create(&mut fbb, &DeviceArgs::default());
+    finish_device_buffer(&mut fbb, device);
+    let empty = fbb.finished_data().to_vec();
+    println!("Empty Device: {:02x?}", empty);
+    assert_eq!(empty.len(), 16);
+
+    let device = root_as_device(&empty).unwrap();
+    assert_eq!(device.id(), None);
+    assert!(device.readings().is_none());
+    assert!(!device.online());
+
+    // The builder can be reused once reset
+    fbb.reset();
+    build_device(&mut fbb, &sample_data(), false);
+    let device = root_as_device(fbb.finished_data()).unwrap();
+    assert!(!device.online());
+    assert_eq!(device.error(), Some("overheat"));
+
+    println!("Defaults and absent fields tests passed");
+}
+
+fn test_verifier() {
+    println!("Testing buffer verification");
+
+    let buf = encode(&sample_data());
+
+    // Every truncation of a valid buffer is rejected, except for dropping
+    // the alignment padding after the last string's terminator
+    let end = buf.len() - 3;
+    assert_eq!(&buf[end - 5..], b"dev0\0\0\0\0");
+    for len in 0..end {
+        assert!(root_as_device(&buf[..len]).is_err());
+    }
+
+    // An offset pointing outside the buffer is caught before it is followed
+    let mut corrupted = buf.clone();
+    corrupted[0] = 0xff;
+    corrupted[1] = 0xff;
+    let err = root_as_device(&corrupted).unwrap_err();
+    println!("Error (expected): {}", err);
+
+    // Limits protect against hostile buffers
+    let opts = VerifierOptions {
+        max_tables: 2,
+        ..Default::default()
+    };
+    let err = root_as_device_with_opts(&opts, &buf).unwrap_err();
+    println!("Error (expected): {}", err);
+
+    // Trusted buffers can skip verification
+    let device = unsafe { root_as_device_unchecked(&buf) };
+    check_sample(&device);
+
+    println!("Buffer verification tests passed");
+}
+
+fn test_size_prefixed_stream() {
+    println!("Testing size prefixed stream");
+
+    // Several buffers in one stream, each prefixed with its size
+    let mut stream = Vec::new();
+    let mut fbb = FlatBufferBuilder::new();
+    for id in ["dev0", "dev1", "dev2"] {
+        fbb.reset();
+        let id = fbb.create_string(id);
+        let device = Device::create(
+            &mut fbb,
+            &DeviceArgs {
+                id: Some(id),
+                ..Default::default()
+            },
+        );
+        fbb.finish_size_prefixed(device, None);
+        stream.extend_from_slice(fbb.finished_data());
+    }
+
+    let mut ids = Vec::new();
+    let mut rest = &stream[..];
+    while !rest.is_empty() {
+        let size = u32::from_le_bytes(rest[..4].try_into().unwrap()) as usize;
+        let device = size_prefixed_root_as_device(&rest[..4 + size]).unwrap();
+        ids.push(device.id().unwrap().to_string());
+        rest = &rest[4 + size..];
+    }
+    assert_eq!(ids, ["dev0", "dev1", "dev2"]);
+
+    println!("Size prefixed stream tests passed");
+}
+
+/// Read only mapping of a whole file
+struct Mapping {
+    addr: *mut c_void,
+    len: usize,
+}
+
+impl Mapping {
+    fn new(file: &File) -> Option<Self> {
+        let len = file.metadata().ok()?.len() as usize;
+        let addr = unsafe {
+            libc::mmap(
+                std::ptr::null_mut(),
+                len,
+                libc::PROT_READ,
+                libc::MAP_PRIVATE,
+                file.as_raw_fd(),
+                0,
+            )
+        };
+        (addr != libc::MAP_FAILED).then_some(Mapping { addr, len })
+    }
+
+    fn as_slice(&self) -> &[u8] {
+        unsafe { std::slice::from_raw_parts(self.addr as *const u8, self.len) }
+    }
+}
+
+impl Drop for Mapping {
+    fn drop(&mut self) {
+        unsafe { libc::munmap(self.addr, self.len) };
+    }
+}
+
+fn test_read_from_file() {
+    println!("Testing in place read from {}", DEVICE_PATH);
+
+    fs::write(DEVICE_PATH, encode(&sample_data())).unwrap();
+
+    let file = File::open(DEVICE_PATH).unwrap();
+    match Mapping::new(&file) {
+        Some(mapping) => {
+            let device = root_as_device(mapping.as_slice()).unwrap();
+            check_sample(&device);
+
+            let range = mapping.as_slice().as_ptr_range();
+            assert!(range.contains(&device.id().unwrap().as_ptr()));
+            println!("Read {} bytes through mmap()", mapping.len);
+        }
+        None => {
+            // Without mmap() support the file is read into memory once,
+            // decoding is still done in place
+            let buf = fs::read(DEVICE_PATH).unwrap();
+            check_sample(&root_as_device(&buf).unwrap());
+            println!("mmap() not available, read {} bytes", buf.len());
+        }
+    }
+
+    fs::remove_file(DEVICE_PATH).unwrap();
+
+    println!("In place read from file tests passed");
+}
+
+/// Same workload as report_decode_cost() in the prost crate, compare the
+/// numbers printed by both
+fn report_decode_cost() {
+    println!("Decode cost, Device with {} readings:", REPORT_READINGS);
+
+    let buf = encode(&report_data());
+    let expected: i64 = report_data()
+        .readings
+        .iter()
+        .map(|reading| reading.value() as i64)
+        .sum();
+
+    let sum_values = |device: Device| -> i64 {
+        device
+            .readings()
+            .unwrap()
+            .iter()
+            .map(|reading| reading.value() as i64)
+            .sum()
+    };
+
+    let start = Instant::now();
+    for _ in 0..REPORT_ITERATIONS {
+        let device = root_as_device(black_box(&buf)).unwrap();
+        assert_eq!(sum_values(device), expected);
+    }
+    let verified = start.elapsed() / REPORT_ITERATIONS;
+
+    let start = Instant::now();
+    for _ in 0..REPORT_ITERATIONS {
+        let device = unsafe { root_as_device_unchecked(black_box(&buf)) };
+        assert_eq!(sum_values(device), expected);
+    }
+    let unchecked = start.elapsed() / REPORT_ITERATIONS;
+
+    println!("  encoded size      {:>6} bytes", buf.len());
+    println!("  verified read     {:>6} ns", verified.as_nanos());
+    println!("  unchecked read    {:>6} ns", unchecked.as_nanos());
+}
+
+#[unsafe(no_mangle)]
+pub fn rust_crate_test_flatbuffers_main() {
+    println!("Running FlatBuffers tests");
+
+    test_build_and_read();
+    test_defaults_and_absent_fields();
+    test_verifier();
+    test_size_prefixed_stream();
+    test_read_from_file();
+    report_decode_cost();
+
+    println!("All FlatBuffers tests completed");
+}
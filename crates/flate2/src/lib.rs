@@ -0,0 +1,346 @@
+// Copyright (c) 2025 Xiaomi Corporation
+// SPDX-License-Identifier: Apache-2.0
+
+//! flate2 example for NuttX
+//!
+//! Streams a sensor log through gzip and zlib with flate2 on its pure-Rust
+//! miniz_oxide backend, from and to files on tmpfs through small fixed
+//! buffers, checks that corrupted streams are rejected, and inflates a
+//! stream with the low-level miniz_oxide API into a 512 byte window for
+//! boards that cannot spare the usual 32 KiB.
+
+use flate2::bufread::{GzDecoder, GzEncoder, MultiGzDecoder, ZlibDecoder};
+use flate2::write::ZlibEncoder;
+use flate2::{Compression, GzBuilder};
+use hex_literal::hex;
+use miniz_oxide::inflate::TINFLStatus;
+use miniz_oxide::inflate::core::inflate_flags::{
+    TINFL_FLAG_COMPUTE_ADLER32, TINFL_FLAG_HAS_MORE_INPUT, TINFL_FLAG_PARSE_ZLIB_HEADER,
+};
+use miniz_oxide::inflate::core::{DecompressorOxide, decompress};
+use std::fs::{self, File};
+use std::io::{self, BufReader, Read, Write};
+use std::time::Instant;
+
+const LOG_PATH: &str = "/tmp/flate2_sensor.log";
+const GZ_PATH: &str = "/tmp/flate2_sensor.log.gz";
+const RESTORED_PATH: &str = "/tmp/flate2_restored.log";
+
+/// Size of every buffer between the files and the codecs
+const CHUNK: usize = 512;
+const LOG_LINES: usize = 8192;
+
+/// Window of the zlib stream below; a power of two, as the wrapping output
+/// buffer of miniz_oxide must be
+const WINDOW: usize = 512;
+
+/// `zlib.compressobj(9, zlib.DEFLATED, 9)` over `sensor_log(256)`, a zlib
+/// stream whose matches reach back at most 512 bytes
+const SMALL_WINDOW_ZLIB: [u8; 1122] = hex!(
+    "18d355cfbb8d00471204519d56d084eefa9740630e3ce10ca0ffb8ed5c8131e2"
+    "241a6f2afef9ebfcf9f7ffce9ffff9efdf7f9d3ffef9cbf27d5f7d7bff0c79de"
+    "601a3a7e86d60bd770effd59ee39ff2237e64d74eee49b2899d99b6859eefb3f"
+    "2ddb7ad3e726f7df2bffb5bc7538ad38ef74a715f18e775a31ef7aa795f75def"
+    "b432dff5412bf75d1fb4cadef541abea5d1fb4fabceb9356c7bb3e6975bfeb93"
+    "d6dc777dd29a7cd717ad99777dd15a7bd717adad777dd1da7dd737adfb1e35a9"
+    "d09bcf552fb0095d7b81f381ea050ea5bb2f704899bfc0a165fd0297969f17b8"
+    "b43c5ee0d2f279b72fadb8eff87b884596b66fa36b23977eb4d1cb7a05f7d2ab"
+    "13dae8555c6df4aa471bbdbeafe21abd4ed346af471d466f4c1d466f4a1d4e6f"
+    "8f3a9cdeba3a9cdeb63a9cde3d572141d0f42e3ebdbfaf3e9c6ae3a3b96a93d8"
+    "6dd526353baa4d7216aa4d7a36aa2d7a6eaa2d7a9eaa2d7abeaa287ae1ca687a"
+    "51ea687a79d4d1f432d4d1f4b2d531f4eaaa63e855aa63e8d5aa63e8b5a963e9"
+    "75a963e9cd51c7d21b57c7d2fbc17f363bf4f69a367a1bab8dde4e69a3778fbd"
+    "10bb045def2ebdfe7d45eedea3edc3c5cb35a37627b49133bbdae8598e367ab6"
+    "caf5cf75ae5ca7e7a55ca71747194e2f421d412f461d412faf3a825ea63a825e"
+    "ae3a925e993a925e953a925e1f7524bd767514bd6e7514bdb9ea287a93ea287a"
+    "33ea687a6bea687a5bea687abbea687a57cf865ce8d57cae53ec10bba6d8f960"
+    "a9d8a57657b14bce5cb14bef27491b3d3f2fd60f3d8fd046cfe76aa31777b4d1"
+    "8b7c1d7e3fb56bdae8a5ad367a59a58d5e9dd7e146afe268a357ad0ea3d7571d"
+    "46af531d4eaf471d4e6f4c1d4e6f4a1d4e6f561d416f5d1d416f5b1d41ef9eab"
+    "9020687a979f5ebdca2fa7e9a3b96a93d86dd516353baa2d7216aa2d7a36aa2d"
+    "7a7e55dbf43c55dbf47c55d1f4c295d1f4a2d431f4f2a863e865a863e865ab63"
+    "e8d555c7d2ab54c7d2ab51c7d26b53c7d2eb7a1d71e8fd1cab8dde786ba3371d"
+    "dae8ed7d1d71e96d8c367a3ba98dde3d661a09bade19bdd62b2377cf6afb7051"
+    "daa8dd51ae933353aed3b354aed3b355ae7fae73e5063d2fe506bd38ca087a3f"
+    "2fb4d18b5647d2cbab8ea497a98ea497ab8ea457a68ea257a58ea2d7471d45af"
+    "5d1d45af5b1d4d6fae3a9ade843a9ade8c3a9ade9a3a86de963a86deae3a86de"
+    "fd7d462ef46a3fd72976895d53ec7eb054ec52bbfb627f5e8133376df4ac561b"
+    "3d3fa58d9ec78bcd4bcfe768a317b7b5d18b0c6d9fda7d1d69f4d2461bbd2c75"
+    "18bd3aea307ae5ea707ad5ea707a7dd5e1f47e1c6df47ad411f4c6d411f4a6d4"
+    "11f466d511f4d6d591f4b6d591f4ee390a4982f6fb8e5eea557d38d5d64773d5"
+    "16b1dbaa2d6a7654dbe42c54dbf46c54dbf4fcaab6e979aa76e8f9aa62e88529"
+    "63e845a9633eb5471d4b2f431d4befe70a6df4eaaa63e955be8e3af46a461bbd"
+    "b6d446afcbb4d1eb7d1d75e98d97367ad3ae8ddedea38ddec6eb28a3b713dae8"
+    "dd630a3182fefbee739f5e39b97b94eb1f2e94ebd4ee28d7c9d9dd3ffe0f9cff"
+    "ea43"
+);
+
+/// Lines like `t=500 ch2 adc=74`, the same as the Python that made
+/// SMALL_WINDOW_ZLIB
+fn sensor_log(lines: usize) -> Vec<u8> {
+    (0..lines)
+        .flat_map(|i| format!("t={} ch{} adc={}\n", i * 250, i % 4, (i * 37) % 1024).into_bytes())
+        .collect()
+}
+
+/// io::copy, but through a CHUNK sized buffer instead of an 8 KiB one
+fn copy_chunked(reader: &mut impl Read, writer: &mut impl Write) -> io::Result<u64> {
+    let mut buf = [0u8; CHUNK];
+    let mut total = 0;
+    loop {
+        let len = match reader.read(&mut buf) {
+            Ok(0) => return Ok(total),
+            Ok(len) => len,
+            Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+            Err(err) => return Err(err),
+        };
+        writer.write_all(&buf[..len])?;
+        total += len as u64;
+    }
+}
+
+fn gzip(data: &[u8]) -> Vec<u8> {
+    let mut gz = Vec::new();
+    GzEncoder::new(data, Compression::default())
+        .read_to_end(&mut gz)
+        .unwrap();
+    gz
+}
+
+fn test_gzip_files() {
+    println!("Testing gzip streaming between tmpfs files");
+
+    let log = sensor_log(LOG_LINES);
+    fs::write(LOG_PATH, &log).unwrap();
+
+    // The bufread codecs pull straight from the small BufReader, where the
+    // read ones would put a 32 KiB buffer of their own in front
+    let input = BufReader::with_capacity(CHUNK, File::open(LOG_PATH).unwrap());
+    let mut encoder = GzBuilder::new()
+        .filename("sensor.log")
+        .mtime(1_700_000_000)
+        .buf_read(input, Compression::default());
+    let mut output = File::create(GZ_PATH).unwrap();
+    let read = copy_chunked(&mut encoder, &mut output).unwrap();
+    output.sync_all().unwrap();
+    let gz_len = fs::metadata(GZ_PATH).unwrap().len();
+    println!(
+        "  {} -> {} bytes ({:.1}%)",
+        log.len(),
+        gz_len,
+        gz_len as f64 * 100.0 / log.len() as f64
+    );
+    assert!(read < log.len() as u64 / 4);
+
+    let input = BufReader::with_capacity(CHUNK, File::open(GZ_PATH).unwrap());
+    let mut decoder = GzDecoder::new(input);
+    let mut output = File::create(RESTORED_PATH).unwrap();
+    assert_eq!(
+        copy_chunked(&mut decoder, &mut output).unwrap(),
+        log.len() as u64
+    );
+    let header = decoder.header().unwrap();
+    assert_eq!(header.filename(), Some(&b"sensor.log"[..]));
+    assert_eq!(header.mtime(), 1_700_000_000);
+    assert_eq!(fs::read(RESTORED_PATH).unwrap(), log);
+
+    // A rotated log gets new gzip members appended; GzDecoder stops after
+    // the first one, MultiGzDecoder reads them all
+    let mut rotated = gzip(b"t=0 ch0 adc=0\n");
+    rotated.extend(gzip(b"t=250 ch1 adc=37\n"));
+    let mut first = String::new();
+    GzDecoder::new(&rotated[..])
+        .read_to_string(&mut first)
+        .unwrap();
+    assert_eq!(first, "t=0 ch0 adc=0\n");
+    let mut all = String::new();
+    MultiGzDecoder::new(&rotated[..])
+        .read_to_string(&mut all)
+        .unwrap();
+    assert_eq!(all, "t=0 ch0 adc=0\nt=250 ch1 adc=37\n");
+
+    for path in [LOG_PATH, GZ_PATH, RESTORED_PATH] {
+        fs::remove_file(path).unwrap();
+    }
+
+    println!("gzip streaming tests passed");
+}
+
+fn test_zlib() {
+    println!("Testing zlib streams");
+
+    let log = sensor_log(LOG_LINES);
+    let mut encoder = ZlibEncoder::new(Vec::new(), Compression::best());
+    for line in log.chunks(100) {
+        encoder.write_all(line).unwrap();
+    }
+    let zlib = encoder.finish().unwrap();
+    // CMF for deflate with a 32 KiB window, FLG announcing the best level
+    assert_eq!(zlib[..2], [0x78, 0xda]);
+
+    let mut decoded = Vec::new();
+    ZlibDecoder::new(BufReader::with_capacity(CHUNK, &zlib[..]))
+        .read_to_end(&mut decoded)
+        .unwrap();
+    assert_eq!(decoded, log);
+    assert_eq!(
+        miniz_oxide::inflate::decompress_to_vec_zlib(&zlib).unwrap(),
+        log
+    );
+
+    println!("zlib stream tests passed");
+}
+
+/// Damages a gzip stream in place
+type Corruption = fn(&mut Vec<u8>);
+
+const CORRUPTIONS: &[(&str, Corruption)] = &[
+    ("bad magic", |gz| gz[0] ^= 0xff),
+    ("unknown method", |gz| gz[2] = 7),
+    ("corrupted deflate data", |gz| gz[16] ^= 0x55),
+    ("CRC-32 mismatch", |gz| {
+        let len = gz.len();
+        gz[len - 8] ^= 1;
+    }),
+    ("length mismatch", |gz| {
+        let len = gz.len();
+        gz[len - 1] ^= 1;
+    }),
+    ("truncated", |gz| gz.truncate(gz.len() / 2)),
+];
+
+fn test_corrupted_input() {
+    println!("Testing corrupted input");
+
+    let log = sensor_log(64);
+    let gz = gzip(&log);
+    for (name, corrupt) in CORRUPTIONS {
+        let mut corrupted = gz.clone();
+        corrupt(&mut corrupted);
+        let mut decoded = Vec::new();
+        let err = GzDecoder::new(&corrupted[..])
+            .read_to_end(&mut decoded)
+            .unwrap_err();
+        println!("  {:<24} {}", name, err);
+    }
+
+    let mut zlib = ZlibEncoder::new(Vec::new(), Compression::default());
+    zlib.write_all(&log).unwrap();
+    let mut zlib = zlib.finish().unwrap();
+    let len = zlib.len();
+    zlib[len - 1] ^= 1;
+    let err = ZlibDecoder::new(&zlib[..])
+        .read_to_end(&mut Vec::new())
+        .unwrap_err();
+    println!("  {:<24} {}", "Adler-32 mismatch", err);
+
+    println!("Corrupted input tests passed");
+}
+
+/// Inflates a zlib stream whose window fits in WINDOW bytes, feeding it
+/// 64 bytes at a time, with the output buffer doubling as the dictionary
+fn inflate_small_window(mut input: &[u8]) -> Result<Vec<u8>, TINFLStatus> {
+    let mut state = Box::<DecompressorOxide>::default();
+    let mut window = [0u8; WINDOW];
+    let mut pos = 0;
+    let mut output = Vec::new();
+    loop {
+        let chunk = input.len().min(64);
+        let more = if chunk < input.len() {
+            TINFL_FLAG_HAS_MORE_INPUT
+        } else {
+            0
+        };
+        let flags = TINFL_FLAG_PARSE_ZLIB_HEADER | TINFL_FLAG_COMPUTE_ADLER32 | more;
+        let (status, read, written) =
+            decompress(&mut state, &input[..chunk], &mut window, pos, flags);
+        // Hand the new bytes on before the window wraps over them
+        output.extend_from_slice(&window[pos..pos + written]);
+        pos = (pos + written) % WINDOW;
+        input = &input[read..];
+        match status {
+            TINFLStatus::Done => return Ok(output),
+            TINFLStatus::NeedsMoreInput | TINFLStatus::HasMoreOutput => {}
+            err => return Err(err),
+        }
+    }
+}
+
+fn test_small_window_inflate() {
+    println!("Testing inflate with a {} byte window", WINDOW);
+
+    // CINFO in the top bits of CMF is log2(window) - 8
+    let cmf = SMALL_WINDOW_ZLIB[0];
+    assert_eq!(cmf & 0x0f, 8);
+    assert_eq!(1 << ((cmf >> 4) + 8), WINDOW);
+
+    let log = sensor_log(256);
+    assert_eq!(inflate_small_window(&SMALL_WINDOW_ZLIB).unwrap(), log);
+    println!(
+        "  {} bytes from {} with {} bytes of state and a {} byte window",
+        log.len(),
+        SMALL_WINDOW_ZLIB.len(),
+        std::mem::size_of::<DecompressorOxide>(),
+        WINDOW
+    );
+
+    let mut corrupted = SMALL_WINDOW_ZLIB;
+    corrupted[corrupted.len() - 1] ^= 1;
+    assert_eq!(
+        inflate_small_window(&corrupted),
+        Err(TINFLStatus::Adler32Mismatch)
+    );
+    assert_eq!(
+        inflate_small_window(&SMALL_WINDOW_ZLIB[..600]),
+        Err(TINFLStatus::FailedCannotMakeProgress)
+    );
+
+    println!("Small window inflate tests passed");
+}
+
+fn report_compression() {
+    let log = sensor_log(LOG_LINES);
+    println!("Compression of a {} byte sensor log:", log.len());
+
+    for (name, level) in [
+        ("none", Compression::none()),
+        ("fast", Compression::fast()),
+        ("default", Compression::default()),
+        ("best", Compression::best()),
+    ] {
+        let start = Instant::now();
+        let mut encoder = ZlibEncoder::new(Vec::new(), level);
+        encoder.write_all(&log).unwrap();
+        let zlib = encoder.finish().unwrap();
+        let compress = start.elapsed();
+
+        let start = Instant::now();
+        let mut decoded = Vec::with_capacity(log.len());
+        ZlibDecoder::new(&zlib[..])
+            .read_to_end(&mut decoded)
+            .unwrap();
+        let decompress = start.elapsed();
+        assert_eq!(decoded, log);
+
+        println!(
+            "  {:<8} {:>6.1}% {:>8} us compress {:>8} us decompress",
+            name,
+            zlib.len() as f64 * 100.0 / log.len() as f64,
+            compress.as_micros(),
+            decompress.as_micros()
+        );
+    }
+}
+
+#[unsafe(no_mangle)]
+pub fn rust_crate_test_flate2_main() {
+    println!("Running flate2 tests");
+
+    test_gzip_files();
+    test_zlib();
+    test_corrupted_input();
+    test_small_window_inflate();
+    report_compression();
+
+    println!("All flate2 tests completed");
+}
@@ -0,0 +1,262 @@
+// Copyright (c) 2025 Xiaomi Corporation
+// SPDX-License-Identifier: Apache-2.0
+
+//! futures crate example for NuttX
+//!
+//! Exercises the executors shipped with `futures` (`block_on`, `LocalPool`
+//! and `ThreadPool`), the future and stream combinators, the async channels
+//! and the `AsyncRead`/`AsyncWrite` traits on in-memory cursors.
+
+use std::thread;
+use std::time::Duration;
+
+use futures::channel::{mpsc, oneshot};
+use futures::executor::{LocalPool, ThreadPool, block_on};
+use futures::future::{self, Either, FutureExt};
+use futures::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, Cursor};
+use futures::sink::SinkExt;
+use futures::stream::{self, StreamExt};
+use futures::task::{LocalSpawnExt, SpawnExt};
+
+/// Resolve a oneshot channel from another thread after `delay`
+fn delayed_value(value: u32, delay: Duration) -> oneshot::Receiver<u32> {
+    let (tx, rx) = oneshot::channel();
+    thread::Builder::new()
+        .stack_size(4 * 1024)
+        .spawn(move || {
+            thread::sleep(delay);
+            let _ = tx.send(value);
+        })
+        .unwrap();
+    rx
+}
+
+/// Run a future to completion on the current thread
+fn test_block_on() {
+    println!("Testing executor::block_on");
+
+    let value = block_on(async {
+        let a = async { 20 }.await;
+        let b = delayed_value(22, Duration::from_millis(50)).await.unwrap();
+        a + b
+    });
+
+    assert_eq!(value, 42);
+    println!("block_on result: {}", value);
+}
+
+/// Interleave several `!Send` tasks on a single-threaded pool
+fn test_local_pool() {
+    println!("Testing executor::LocalPool");
+
+    let mut pool = LocalPool::new();
+    let spawner = pool.spawner();
+
+    let (tx, rx) = mpsc::unbounded();
+    for id in 0..4 {
+        let tx = tx.clone();
+        spawner
+            .spawn_local(async move {
+                tx.unbounded_send(id).unwrap();
+            })
+            .unwrap();
+    }
+    drop(tx);
+
+    let handle = spawner
+        .spawn_local_with_handle(rx.collect::<Vec<u32>>())
+        .unwrap();
+    pool.run();
+
+    let mut ids = block_on(handle);
+    ids.sort();
+    assert_eq!(ids, vec![0, 1, 2, 3]);
+    println!("LocalPool tasks completed: {:?}", ids);
+}
+
+/// Run tasks on a thread pool sized for small targets
+fn test_thread_pool() {
+    println!("Testing executor::ThreadPool");
+
+    let pool = ThreadPool::builder()
+        .pool_size(2)
+        .stack_size(4 * 1024)
+        .name_prefix("futures-pool-")
+        .create()
+        .unwrap();
+
+    let handles = (0..4u32)
+        .map(|i| pool.spawn_with_handle(async move { i * i }).unwrap())
+        .collect::<Vec<_>>();
+
+    let results = block_on(future::join_all(handles));
+    assert_eq!(results, vec![0, 1, 4, 9]);
+    println!("ThreadPool results: {:?}", results);
+}
+
+/// Wait for several futures at once
+fn test_join_all() {
+    println!("Testing future::join_all");
+
+    let receivers = (1..=3)
+        .map(|i| delayed_value(i, Duration::from_millis(30 * u64::from(4 - i))))
+        .collect::<Vec<_>>();
+
+    // Results keep the order of the inputs, not the order of completion
+    let results = block_on(future::join_all(receivers));
+    let values = results.into_iter().map(Result::unwrap).collect::<Vec<_>>();
+    assert_eq!(values, vec![1, 2, 3]);
+    println!("join_all results: {:?}", values);
+}
+
+/// Race futures with `future::select` and the `select!` macro
+fn test_select() {
+    println!("Testing future::select and select!");
+
+    let fast = delayed_value(1, Duration::from_millis(10));
+    let slow = delayed_value(2, Duration::from_millis(200));
+
+    match block_on(future::select(fast, slow)) {
+        Either::Left((value, _)) => {
+            assert_eq!(value.unwrap(), 1);
+            println!("select: left future won");
+        }
+        Either::Right(_) => panic!("slow future should not win"),
+    }
+
+    let winner = block_on(async {
+        let mut fast = delayed_value(3, Duration::from_millis(10)).fuse();
+        let mut slow = delayed_value(4, Duration::from_millis(200)).fuse();
+        futures::select! {
+            value = fast => value.unwrap(),
+            value = slow => value.unwrap(),
+        }
+    });
+    assert_eq!(winner, 3);
+    println!("select!: got {}", winner);
+}
+
+/// Chain `Stream` combinators
+fn test_stream_combinators() {
+    println!("Testing Stream and StreamExt combinators");
+
+    let result = block_on(
+        stream::iter(1..=10)
+            .filter(|x| future::ready(x % 2 == 0))
+            .map(|x| x * 10)
+            .take(3)
+            .collect::<Vec<u32>>(),
+    );
+    assert_eq!(result, vec![20, 40, 60]);
+    println!("filter/map/take: {:?}", result);
+
+    let sum = block_on(stream::iter(1..=100u32).fold(0, |acc, x| async move { acc + x }));
+    assert_eq!(sum, 5050);
+    println!("fold sum: {}", sum);
+
+    // Run up to two futures concurrently while preserving order
+    let buffered = block_on(
+        stream::iter(1..=4)
+            .map(|i| delayed_value(i, Duration::from_millis(10)))
+            .buffered(2)
+            .map(Result::unwrap)
+            .collect::<Vec<u32>>(),
+    );
+    assert_eq!(buffered, vec![1, 2, 3, 4]);
+    println!("buffered: {:?}", buffered);
+
+    let chunks = block_on(
+        stream::iter(0..5u32)
+            .zip(stream::repeat(1))
+            .map(|(x, one)| x + one)
+            .chunks(2)
+            .collect::<Vec<Vec<u32>>>(),
+    );
+    assert_eq!(chunks, vec![vec![1, 2], vec![3, 4], vec![5]]);
+    println!("zip/chunks: {:?}", chunks);
+}
+
+/// Send through bounded mpsc and oneshot channels across threads
+fn test_channels() {
+    println!("Testing channel::mpsc and channel::oneshot");
+
+    let (mut tx, rx) = mpsc::channel::<u32>(2);
+    let (done_tx, done_rx) = oneshot::channel::<&'static str>();
+
+    let producer = thread::Builder::new()
+        .stack_size(4 * 1024)
+        .spawn(move || {
+            block_on(async move {
+                // The small buffer forces the producer to wait on the consumer
+                for i in 0..8 {
+                    tx.send(i).await.unwrap();
+                }
+            });
+            done_tx.send("producer finished").unwrap();
+        })
+        .unwrap();
+
+    let received = block_on(rx.collect::<Vec<u32>>());
+    let message = block_on(done_rx).unwrap();
+    producer.join().unwrap();
+
+    assert_eq!(received, (0..8).collect::<Vec<u32>>());
+    println!("mpsc received: {:?}", received);
+    println!("oneshot received: {}", message);
+
+    // A dropped sender cancels the receiver
+    let (tx, rx) = oneshot::channel::<u32>();
+    drop(tx);
+    assert_eq!(block_on(rx), Err(oneshot::Canceled));
+    println!("oneshot cancellation detected");
+}
+
+/// Use `AsyncRead`/`AsyncWrite` on in-memory cursors
+fn test_async_io() {
+    println!("Testing AsyncRead and AsyncWrite on Cursor");
+
+    block_on(async {
+        let mut writer = Cursor::new(Vec::new());
+        writer.write_all(b"line one\n").await.unwrap();
+        writer.write_all(b"line two\n").await.unwrap();
+        writer.flush().await.unwrap();
+
+        let data = writer.into_inner();
+        assert_eq!(data, b"line one\nline two\n");
+
+        let mut reader = Cursor::new(data.clone());
+        let mut contents = String::new();
+        reader.read_to_string(&mut contents).await.unwrap();
+        assert_eq!(contents, "line one\nline two\n");
+
+        let lines = Cursor::new(data)
+            .lines()
+            .map(Result::unwrap)
+            .collect::<Vec<String>>()
+            .await;
+        assert_eq!(lines, vec!["line one", "line two"]);
+        println!("Read lines: {:?}", lines);
+
+        let mut buf = [0u8; 4];
+        let mut reader = Cursor::new(b"abcdef".to_vec());
+        reader.read_exact(&mut buf).await.unwrap();
+        assert_eq!(&buf, b"abcd");
+        println!("read_exact: {:?}", std::str::from_utf8(&buf).unwrap());
+    });
+}
+
+#[unsafe(no_mangle)]
+pub fn rust_crate_test_futures_main() {
+    println!("Running futures tests");
+
+    test_block_on();
+    test_local_pool();
+    test_thread_pool();
+    test_join_all();
+    test_select();
+    test_stream_combinators();
+    test_channels();
+    test_async_io();
+
+    println!("All futures tests completed");
+}
@@ -0,0 +1,134 @@
+// Copyright (c) 2025 Xiaomi Corporation
+// SPDX-License-Identifier: Apache-2.0
+
+//! hkdf example for NuttX
+//!
+//! Checks HKDF-SHA-256 against the test cases of RFC 5869 and derives
+//! separate telemetry keys from one device secret.
+
+use hex_literal::hex;
+use hkdf::Hkdf;
+use sha2::Sha256;
+
+struct TestCase {
+    name: &'static str,
+    ikm: &'static [u8],
+    salt: Option<&'static [u8]>,
+    info: &'static [u8],
+    prk: [u8; 32],
+    okm: [u8; 42],
+}
+
+/// RFC 5869, appendix A
+const TEST_CASES: &[TestCase] = &[
+    TestCase {
+        name: "Test Case 1",
+        ikm: &[0x0b; 22],
+        salt: Some(&hex!("000102030405060708090a0b0c")),
+        info: &hex!("f0f1f2f3f4f5f6f7f8f9"),
+        prk: hex!("077709362c2e32df0ddc3f0dc47bba6390b6c73bb50f9c3122ec844ad7c2b3e5"),
+        okm: hex!(
+            "3cb25f25faacd57a90434f64d0362f2a2d2d0a90cf1a5a4c5db02d56ecc4c5bf"
+            "34007208d5b887185865"
+        ),
+    },
+    TestCase {
+        name: "Test Case 3 (no salt, no info)",
+        ikm: &[0x0b; 22],
+        salt: None,
+        info: &[],
+        prk: hex!("19ef24a32c717b167f33a91d6f648bdf96596776afdb6377ac434c1c293ccb04"),
+        okm: hex!(
+            "8da4e775a563c18f715f802a063c5a31b8a11f5c5ee1879ec3454e5f3c738d2d"
+            "9d201395faa4b61a96c8"
+        ),
+    },
+];
+
+fn test_rfc5869_vectors() {
+    println!("Testing RFC 5869 test vectors");
+
+    for case in TEST_CASES {
+        println!("  {}", case.name);
+
+        // Extract and expand in one go
+        let (prk, hk) = Hkdf::<Sha256>::extract(case.salt, case.ikm);
+        assert_eq!(prk[..], case.prk);
+        let mut okm = [0u8; 42];
+        hk.expand(case.info, &mut okm).unwrap();
+        assert_eq!(okm, case.okm);
+
+        // Or expand from a stored pseudorandom key
+        let hk = Hkdf::<Sha256>::from_prk(&case.prk).unwrap();
+        let mut okm = [0u8; 42];
+        hk.expand(case.info, &mut okm).unwrap();
+        assert_eq!(okm, case.okm);
+    }
+
+    // An empty salt is the same as no salt
+    let hk = Hkdf::<Sha256>::new(Some(&[]), &[0x0b; 22]);
+    let mut okm = [0u8; 42];
+    hk.expand(&[], &mut okm).unwrap();
+    assert_eq!(okm, TEST_CASES[1].okm);
+
+    println!("RFC 5869 test vectors passed");
+}
+
+fn test_output_length() {
+    println!("Testing output length limits");
+
+    let hk = Hkdf::<Sha256>::new(None, b"device secret");
+
+    // At most 255 blocks of the hash output can be derived
+    let mut max = vec![0u8; 255 * 32];
+    hk.expand(b"", &mut max).unwrap();
+    let mut too_long = vec![0u8; 255 * 32 + 1];
+    assert!(hk.expand(b"", &mut too_long).is_err());
+
+    // A shorter output is a prefix of a longer one with the same info
+    let mut short = [0u8; 16];
+    hk.expand(b"", &mut short).unwrap();
+    assert_eq!(short, max[..16]);
+
+    // A pseudorandom key shorter than the hash output is rejected
+    assert!(Hkdf::<Sha256>::from_prk(&[0; 16]).is_err());
+
+    println!("Output length tests passed");
+}
+
+fn test_key_separation() {
+    println!("Testing key derivation for telemetry");
+
+    // One device secret, one independent key per purpose and direction
+    let device_secret = hex!("8b1d0f7c2a6e4593b1c7d2e8f0a39b46");
+    let hk = Hkdf::<Sha256>::new(Some(b"nuttx telemetry v1"), &device_secret);
+
+    let derive = |label: &[&[u8]]| {
+        let mut key = [0u8; 32];
+        hk.expand_multi_info(label, &mut key).unwrap();
+        key
+    };
+    let uplink = derive(&[b"uplink", b" enc"]);
+    let downlink = derive(&[b"downlink", b" enc"]);
+    let mac = derive(&[b"uplink", b" mac"]);
+    assert_ne!(uplink, downlink);
+    assert_ne!(uplink, mac);
+
+    // Info given in pieces is the same as the concatenation
+    let mut joined = [0u8; 32];
+    hk.expand(b"uplink enc", &mut joined).unwrap();
+    assert_eq!(joined, uplink);
+
+    println!("Key derivation tests passed");
+}
+
+#[unsafe(no_mangle)]
+pub fn rust_crate_test_hkdf_main() {
+    println!("Running hkdf tests");
+
+    test_rfc5869_vectors();
+    test_output_length();
+    test_key_separation();
+
+    println!("All hkdf tests completed");
+}
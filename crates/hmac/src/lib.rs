@@ -0,0 +1,168 @@
+// Copyright (c) 2025 Xiaomi Corporation
+// SPDX-License-Identifier: Apache-2.0
+
+//! hmac example for NuttX
+//!
+//! Checks HMAC-SHA-256 and HMAC-SHA-512 against the test cases of RFC 4231
+//! and measures throughput.
+
+use hex_literal::hex;
+use hmac::{Hmac, Mac};
+use sha2::{Sha256, Sha512};
+use std::time::Instant;
+
+type HmacSha256 = Hmac<Sha256>;
+type HmacSha512 = Hmac<Sha512>;
+
+const THROUGHPUT_BUFFER: usize = 16 * 1024;
+const THROUGHPUT_ITERATIONS: u32 = 16;
+
+struct TestCase {
+    name: &'static str,
+    key: &'static [u8],
+    data: &'static [u8],
+    sha256: [u8; 32],
+    sha512: [u8; 64],
+}
+
+/// RFC 4231, section 4
+const TEST_CASES: &[TestCase] = &[
+    TestCase {
+        name: "Test Case 1",
+        key: &[0x0b; 20],
+        data: b"Hi There",
+        sha256: hex!("b0344c61d8db38535ca8afceaf0bf12b881dc200c9833da726e9376c2e32cff7"),
+        sha512: hex!(
+            "87aa7cdea5ef619d4ff0b4241a1d6cb02379f4e2ce4ec2787ad0b30545e17cde"
+            "daa833b7d6b8a702038b274eaea3f4e4be9d914eeb61f1702e696c203a126854"
+        ),
+    },
+    TestCase {
+        name: "Test Case 2 (short key)",
+        key: b"Jefe",
+        data: b"what do ya want for nothing?",
+        sha256: hex!("5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"),
+        sha512: hex!(
+            "164b7a7bfcf819e2e395fbe73b56e0a387bd64222e831fd610270cd7ea250554"
+            "9758bf75c05a994a6d034f65f8f0e6fdcaeab1a34d4a6b4b636e070a38bce737"
+        ),
+    },
+    TestCase {
+        name: "Test Case 6 (key larger than block)",
+        key: &[0xaa; 131],
+        data: b"Test Using Larger Than Block-Size Key - Hash Key First",
+        sha256: hex!("60e431591ee0b67f0d8a26aacbf5b77f8e0bc6213728c5140546040f0ee37f54"),
+        sha512: hex!(
+            "80b24263c7c1a3ebb71493c1dd7be8b49b46d1f41b4aeec1121b013783f8f352"
+            "6b56d037e05f2598bd0fd2215d6a1e5295e64f73f63f0aec8b915a985d786598"
+        ),
+    },
+];
+
+fn test_rfc4231_vectors() {
+    println!("Testing RFC 4231 test vectors");
+
+    for case in TEST_CASES {
+        println!("  {}", case.name);
+
+        let mut mac = HmacSha256::new_from_slice(case.key).unwrap();
+        mac.update(case.data);
+        assert_eq!(mac.finalize().into_bytes()[..], case.sha256);
+
+        let mut mac = HmacSha512::new_from_slice(case.key).unwrap();
+        mac.update(case.data);
+        assert_eq!(mac.finalize().into_bytes()[..], case.sha512);
+    }
+
+    // Test Case 5, truncated to 128 bits
+    let mut mac = HmacSha256::new_from_slice(&[0x0c; 20]).unwrap();
+    mac.update(b"Test With Truncation");
+    mac.verify_truncated_left(&hex!("a3b6167473100ee06e0c796c2955552b"))
+        .unwrap();
+
+    println!("RFC 4231 test vectors passed");
+}
+
+fn test_incremental_and_verify() {
+    println!("Testing incremental updates and verification");
+
+    let case = &TEST_CASES[1];
+
+    // Feeding the data in pieces gives the same tag
+    let mut mac = HmacSha256::new_from_slice(case.key).unwrap();
+    for chunk in case.data.chunks(5) {
+        mac.update(chunk);
+    }
+    let tag = mac.clone().finalize().into_bytes();
+    assert_eq!(tag[..], case.sha256);
+
+    // verify_slice() compares in constant time
+    mac.verify_slice(&case.sha256).unwrap();
+
+    let mut wrong = case.sha256;
+    wrong[31] ^= 1;
+    let mut mac = HmacSha256::new_from_slice(case.key).unwrap();
+    mac.update(case.data);
+    assert!(mac.verify_slice(&wrong).is_err());
+
+    // A tag of the wrong length never verifies
+    let mut mac = HmacSha256::new_from_slice(case.key).unwrap();
+    mac.update(case.data);
+    assert!(mac.verify_slice(&case.sha256[..16]).is_err());
+
+    println!("Incremental update and verification tests passed");
+}
+
+/// A MAC measured by report_throughput()
+struct MacAlgorithm {
+    name: &'static str,
+    tag: fn(&[u8]) -> Vec<u8>,
+}
+
+const MACS: &[MacAlgorithm] = &[
+    MacAlgorithm {
+        name: "HMAC-SHA-256",
+        tag: tag::<HmacSha256>,
+    },
+    MacAlgorithm {
+        name: "HMAC-SHA-512",
+        tag: tag::<HmacSha512>,
+    },
+];
+
+fn tag<M: Mac + hmac::digest::KeyInit>(data: &[u8]) -> Vec<u8> {
+    let mut mac = <M as Mac>::new_from_slice(b"key").unwrap();
+    mac.update(data);
+    mac.finalize().into_bytes().to_vec()
+}
+
+fn report_throughput() {
+    println!("Throughput, {} byte messages:", THROUGHPUT_BUFFER);
+
+    let buffer = vec![0x5a; THROUGHPUT_BUFFER];
+    let bytes = (THROUGHPUT_BUFFER as u32 * THROUGHPUT_ITERATIONS) as f64;
+
+    for algorithm in MACS {
+        let start = Instant::now();
+        for _ in 0..THROUGHPUT_ITERATIONS {
+            std::hint::black_box((algorithm.tag)(&buffer));
+        }
+        let elapsed = start.elapsed().as_secs_f64();
+        println!(
+            "  {:<16} {:>8.2} MB/s",
+            algorithm.name,
+            bytes / 1_000_000.0 / elapsed
+        );
+    }
+}
+
+#[unsafe(no_mangle)]
+pub fn rust_crate_test_hmac_main() {
+    println!("Running hmac tests");
+
+    test_rfc4231_vectors();
+    test_incremental_and_verify();
+    report_throughput();
+
+    println!("All hmac tests completed");
+}
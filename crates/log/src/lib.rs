@@ -0,0 +1,283 @@
+// Copyright (c) 2025 Xiaomi Corporation
+// SPDX-License-Identifier: Apache-2.0
+
+//! `log` facade backend for NuttX
+//!
+//! Implements `log::Log` on top of NuttX `syslog()`, so every Rust component
+//! can share one logger with the C side. Levels map onto the syslog
+//! priorities, targets can be filtered by module path prefix, and the global
+//! maximum level comes from `CONFIG_RUST_CRATE_LOG_MAX_LEVEL`.
+
+use std::ffi::{CString, c_char, c_int};
+use std::fs::OpenOptions;
+use std::io::{ErrorKind, Read};
+use std::os::unix::fs::OpenOptionsExt;
+use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+use log::{Level, LevelFilter, Log, Metadata, Record, debug, error, info, trace, warn};
+
+// Priorities from <syslog.h>
+const LOG_ERR: c_int = 3;
+const LOG_WARNING: c_int = 4;
+const LOG_INFO: c_int = 6;
+const LOG_DEBUG: c_int = 7;
+
+/// Device exposing the RAM log buffer when `CONFIG_RAMLOG_SYSLOG` is enabled
+const SYSLOG_DEVICE: &str = "/dev/kmsg";
+
+unsafe extern "C" {
+    fn syslog(priority: c_int, format: *const c_char, ...);
+}
+
+/// Map a `log` level onto a syslog priority
+fn syslog_priority(level: Level) -> c_int {
+    match level {
+        Level::Error => LOG_ERR,
+        Level::Warn => LOG_WARNING,
+        Level::Info => LOG_INFO,
+        Level::Debug | Level::Trace => LOG_DEBUG,
+    }
+}
+
+/// Maximum level selected in Kconfig, see build.rs
+fn kconfig_max_level() -> LevelFilter {
+    let level = env!("RUST_CRATE_LOG_MAX_LEVEL").parse().unwrap_or(0);
+    LevelFilter::iter().nth(level).unwrap_or(LevelFilter::Trace)
+}
+
+/// Check whether `target` is `prefix` itself or one of its submodules
+fn target_matches(target: &str, prefix: &str) -> bool {
+    target
+        .strip_prefix(prefix)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with("::"))
+}
+
+/// Logger forwarding records to NuttX syslog
+struct SyslogLogger {
+    /// Per-module overrides, the longest matching prefix wins
+    filters: &'static [(&'static str, LevelFilter)],
+    /// Records forwarded to syslog, used to verify the filtering
+    emitted: AtomicUsize,
+}
+
+impl SyslogLogger {
+    fn emitted(&self) -> usize {
+        self.emitted.load(Ordering::SeqCst)
+    }
+}
+
+impl Log for SyslogLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        let filter = self
+            .filters
+            .iter()
+            .filter(|(prefix, _)| target_matches(metadata.target(), prefix))
+            .max_by_key(|(prefix, _)| prefix.len())
+            .map_or(LevelFilter::Trace, |(_, filter)| *filter);
+
+        metadata.level() <= filter
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+
+        let message = format!(
+            "[{}] {}: {}\n",
+            record.level(),
+            record.target(),
+            record.args()
+        );
+        let message = CString::new(message.replace('\0', "\\0")).unwrap();
+
+        unsafe {
+            syslog(
+                syslog_priority(record.level()),
+                c"%s".as_ptr(),
+                message.as_ptr(),
+            );
+        }
+
+        self.emitted.fetch_add(1, Ordering::SeqCst);
+    }
+
+    fn flush(&self) {}
+}
+
+static LOGGER: SyslogLogger = SyslogLogger {
+    filters: &[
+        ("app::noisy", LevelFilter::Warn),
+        ("app::noisy::verbose", LevelFilter::Trace),
+    ],
+    emitted: AtomicUsize::new(0),
+};
+
+/// Number of records expected to pass the global maximum level
+fn expected(levels: &[Level]) -> usize {
+    levels
+        .iter()
+        .filter(|level| **level <= log::max_level())
+        .count()
+}
+
+fn test_level_mapping() {
+    println!("Testing level to syslog priority mapping");
+
+    assert_eq!(syslog_priority(Level::Error), LOG_ERR);
+    assert_eq!(syslog_priority(Level::Warn), LOG_WARNING);
+    assert_eq!(syslog_priority(Level::Info), LOG_INFO);
+    assert_eq!(syslog_priority(Level::Debug), LOG_DEBUG);
+    assert_eq!(syslog_priority(Level::Trace), LOG_DEBUG);
+
+    println!("Level mapping tests passed");
+}
+
+fn test_log_macros() {
+    println!("Testing log macros");
+
+    let before = LOGGER.emitted();
+
+    error!("error message from Rust");
+    warn!("warning message from Rust");
+    info!("info message from Rust");
+    debug!("debug message from Rust");
+    trace!("trace message from Rust");
+
+    let all = [
+        Level::Error,
+        Level::Warn,
+        Level::Info,
+        Level::Debug,
+        Level::Trace,
+    ];
+    assert_eq!(LOGGER.emitted() - before, expected(&all));
+
+    println!("Log macros tests passed");
+}
+
+fn test_module_filters() {
+    println!("Testing module path filters");
+
+    let before = LOGGER.emitted();
+
+    // Limited to warnings by the "app::noisy" filter
+    info!(target: "app::noisy", "dropped by the module filter");
+    debug!(target: "app::noisy::driver", "dropped by the parent filter");
+    warn!(target: "app::noisy", "passes the module filter");
+
+    // The longer "app::noisy::verbose" prefix takes precedence
+    debug!(target: "app::noisy::verbose", "passes the more specific filter");
+
+    // A shared prefix without a "::" boundary is a different module
+    debug!(target: "app::noisy_neighbour", "not affected by the filter");
+
+    let passed = [Level::Warn, Level::Debug, Level::Debug];
+    assert_eq!(LOGGER.emitted() - before, expected(&passed));
+
+    assert!(!log::log_enabled!(target: "app::noisy", Level::Info));
+
+    println!("Module path filter tests passed");
+}
+
+fn test_multithread_logging() {
+    println!("Testing logging from multiple threads");
+
+    const THREADS: usize = 4;
+    const MESSAGES: usize = 10;
+
+    let before = LOGGER.emitted();
+
+    let handles = (0..THREADS)
+        .map(|id| {
+            thread::Builder::new()
+                .stack_size(4 * 1024)
+                .spawn(move || {
+                    for i in 0..MESSAGES {
+                        info!(target: "app::worker", "thread {} message {}", id, i);
+                    }
+                })
+                .unwrap()
+        })
+        .collect::<Vec<_>>();
+
+    for handle in handles {
+        handle.join().unwrap();
+    }
+
+    let emitted = LOGGER.emitted() - before;
+    println!("{} records logged from {} threads", emitted, THREADS);
+    assert_eq!(emitted, THREADS * MESSAGES * expected(&[Level::Info]));
+
+    println!("Multithread logging tests passed");
+}
+
+fn test_syslog_device() {
+    println!("Testing output through the syslog device");
+
+    if !Path::new(SYSLOG_DEVICE).exists() {
+        println!(
+            "{} not present, syslog output goes to the console",
+            SYSLOG_DEVICE
+        );
+        return;
+    }
+
+    if log::max_level() < LevelFilter::Error {
+        println!(
+            "Maximum level is {}, nothing reaches syslog",
+            log::max_level()
+        );
+        return;
+    }
+
+    let marker = "rust-log-syslog-marker";
+    error!("{}", marker);
+
+    // Drain the RAM log without blocking once it is empty
+    let mut device = OpenOptions::new()
+        .read(true)
+        .custom_flags(libc::O_NONBLOCK)
+        .open(SYSLOG_DEVICE)
+        .unwrap();
+
+    let mut contents = Vec::new();
+    let mut buf = [0u8; 256];
+    loop {
+        match device.read(&mut buf) {
+            Ok(0) => break,
+            Ok(n) => contents.extend_from_slice(&buf[..n]),
+            Err(e) if e.kind() == ErrorKind::WouldBlock => break,
+            Err(e) => panic!("Failed to read {}: {}", SYSLOG_DEVICE, e),
+        }
+    }
+
+    let contents = String::from_utf8_lossy(&contents);
+    println!("Read {} bytes from {}", contents.len(), SYSLOG_DEVICE);
+    assert!(contents.contains(marker), "marker not found in syslog");
+
+    println!("Syslog device tests passed");
+}
+
+#[unsafe(no_mangle)]
+pub fn rust_crate_test_log_main() {
+    println!("Running log tests");
+
+    // Another run of this application in the same image may already have
+    // installed the logger
+    if log::set_logger(&LOGGER).is_err() {
+        println!("Logger already installed");
+    }
+    log::set_max_level(kconfig_max_level());
+    println!("Max level: {}", log::max_level());
+
+    test_level_mapping();
+    test_log_macros();
+    test_module_filters();
+    test_multithread_logging();
+    test_syslog_device();
+
+    println!("All log tests completed");
+}
@@ -0,0 +1,316 @@
+// Copyright (c) 2025 Xiaomi Corporation
+// SPDX-License-Identifier: Apache-2.0
+
+//! lz4_flex example for NuttX
+//!
+//! Decodes an LZ4 frame made by the reference lz4 tool and the raw block
+//! inside it, round trips both formats, checks that corrupted frames are
+//! rejected, and reports ratio, decode speed and peak heap. crates/miniz_oxide
+//! prints the same figures for deflate over the same log.
+
+use hex_literal::hex;
+use lz4_flex::frame::{BlockSize, FrameDecoder, FrameEncoder, FrameInfo};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::io::{self, Read, Write};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Instant;
+
+const LOG_LINES: usize = 256;
+const DECODE_ITERATIONS: u32 = 16;
+
+/// `lz4 -9 --content-size` over `sensor_log(256)`: independent blocks, the
+/// content size and a content checksum
+const LZ4_FRAME: [u8; 2055] = hex!(
+    "04224d186c40bc130000000000003eec070000f003743d302063683020616463"
+    "3d300a743d32351000113110007033370a743d35301100113211006137340a74"
+    "3d37220011331100963131310a743d3130304500303134381300084800303138"
+    "351300084a00303232321300084c00303235398000084c003032393613000994"
+    "00223333a600064c00213337b900084c00203430bb0018334c00203434bd0018"
+    "334c00203438be0018334c00203531be0018334c00203535be0018344c002035"
+    "39be0018344c00203632be0018344c00203636be0018344c0032373033530106"
+    "4c00203734be0018354c002137377901084c00203831be0018354c00203835be"
+    "0018364c00203838be0018364c00203932be0018364c00203936be0018364c00"
+    "203939be001937c80100250019372f0101240009960000e100193711021032e1"
+    "00193849001036e100193811021039e100193811021033e100193811021037e1"
+    "0018394c00203330e100193911021034e100193911021038bc00193911022331"
+    "39a90207120221353614000ae4001133140009140221333014000915021036c2"
+    "001931fa023136303414000917021134210309fc02213637220309fd02213731"
+    "230309fe02213735240309ff02333738395e030786011132c800090103213836"
+    "c800090203213930c8000903031139e403193104031139e70319310503223130"
+    "ea03090703113228000a0703022600094f0001ee000907032431331104072702"
+    "1137ee00090903213230ee00090a03213234ee00090b03213238ee00090c0321"
+    "3332ee00090d03213335ee00090e03213339c600090f03213433c70009100321"
+    "3436c80009120333353035c604072e021234da04092e021137c8000917032136"
+    "31c800091803213635c800091903213639c800091a03213732c800091b032137"
+    "36c800091c03213830c800091d03213833c800091e03333837357b05072e0201"
+    "010419321e03223934a3050adf0101a4051a302e0201070419321f0311332800"
+    "091e0312372600084f00213131cd05091d03313134370706092f022138341400"
+    "092f022332312f06072f022135381400092f021139ef00091d03213333ef0009"
+    "1d03213336ef00091d03213430c700091d03213434c800091c03213438c80009"
+    "1d03213531c800091e03213535c800091f0333353931e406072f021132c80009"
+    "1f03213636c800091f03213730c800091f03213733c800091f03213737c80009"
+    "1f03213831c800091f03213835c800091f03213838c800091f0311390d041932"
+    "1f03333936319907072f02010f041a3235060127001a383f01011300099e0001"
+    "ed00091c031131460819321c0312314708091c0312314808091c031232490809"
+    "1c0312324a08091c0315334b08072d02024c08091c0312334d08091c0312344e"
+    "08091c0311344f0819331b031134500819331c031135510819331d0311355208"
+    "19331d031136530819331d031136540819331d03333637370009072d02123114"
+    "09092d0201570819331d031137580819331d03113859082a3333100801c80009"
+    "1d0311385b0819331d0311390c0419331d0311390d0419331d032131300e0419"
+    "331e03015b041a331d030126001a354f00011300094e0001630819331b031131"
+    "640819331b031132650819331b031132660819331b031132670819331b031133"
+    "680819331b031133680819331b0333333933680a072e0201680819331b031134"
+    "680819331c031135680819331d031135680819331e031135680819331e031136"
+    "680819331e031136680819331e031136680819331e031137680819331e031137"
+    "680819341e031138680819341e031138680819341e031138680819341e031139"
+    "0c0419341e0311390d0419341e0311390e041a343b0611300f0419341f03016a"
+    "081a34580902bd0b09910a233039d10b074f0001690819341d03113169081934"
+    "1d031132690819341d031132690819341d031132690819341d03113369082a34"
+    "34280701ef00091d031134690819341d031134690819341c03243437860c072f"
+    "0201690819341e031135690819341f031135690819341f031136690819341f03"
+    "1136690819341f031137690819341f031137690819341f031137690819341f03"
+    "1138690819341f03113869081a346d0c12384f0d092f02010d0419341f031139"
+    "0e0419341f0311390f041a343d060227000a3f01001300091d0301670819341c"
+    "031131670819341c031131670819341c0333313935ed0d072d0201670819351c"
+    "031132670819351c031133670819351c03113367081a35550901670819351c03"
+    "1134670819351c031134670819351b031134670819351c031135670819351d03"
+    "1135670819351d031136670819351d031136670819351d031136670819351d03"
+    "1137670819351d031137670819351d031137670819351d031138670819351d03"
+    "1138670819351d031138670819351d0333393335570f072d02010d042a35350d"
+    "04010e041a35770c0228000aa7090013001a367c02011300094e000168081935"
+    "1b031131680819351b03113268082a3537610d02f60f092e022338310a10072e"
+    "020168082a3537f90401ee00091b031133680819351b031134680819351b0311"
+    "34680819351c031235bb10091d031235bc10091e031235bd10091e031236be10"
+    "091e031536bf10072e0202c010091e031237c110091e031237c210091e031137"
+    "680819361e031138680819361e031138680819361e0311390c0419361e031139"
+    "0d0419361e0311390e041b363b06010f041a3693120228000ae20f001300091d"
+    "031131690819361d031131690819361d031131690818361d03503d3231390a00"
+    "0000003ddf6132"
+);
+
+/// The single compressed block of LZ4_FRAME, after the 15 byte frame header
+/// and its own 4 byte length
+const LZ4_BLOCK: std::ops::Range<usize> = 19..19 + 2028;
+
+static HEAP_IN_USE: AtomicUsize = AtomicUsize::new(0);
+static HEAP_PEAK: AtomicUsize = AtomicUsize::new(0);
+
+/// The system allocator, keeping count of the bytes in use and their peak
+struct CountingAllocator;
+
+fn record_alloc(size: usize) {
+    let in_use = HEAP_IN_USE.fetch_add(size, Ordering::Relaxed) + size;
+    HEAP_PEAK.fetch_max(in_use, Ordering::Relaxed);
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc(layout) };
+        if !ptr.is_null() {
+            record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc_zeroed(layout) };
+        if !ptr.is_null() {
+            record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) };
+        HEAP_IN_USE.fetch_sub(layout.size(), Ordering::Relaxed);
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = unsafe { System.realloc(ptr, layout, new_size) };
+        if !new_ptr.is_null() {
+            HEAP_IN_USE.fetch_sub(layout.size(), Ordering::Relaxed);
+            record_alloc(new_size);
+        }
+        new_ptr
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// Restarts the peak from the bytes in use now, and returns them
+fn reset_heap_peak() -> usize {
+    let in_use = HEAP_IN_USE.load(Ordering::Relaxed);
+    HEAP_PEAK.store(in_use, Ordering::Relaxed);
+    in_use
+}
+
+/// What the vectors were made from: `t=500 ch2 adc=74` and so on, four
+/// channels sampled in turn
+fn sensor_log(lines: usize) -> Vec<u8> {
+    (0..lines)
+        .flat_map(|i| format!("t={} ch{} adc={}\n", i * 250, i % 4, (i * 37) % 1024).into_bytes())
+        .collect()
+}
+
+fn decode_frame(frame: &[u8]) -> io::Result<Vec<u8>> {
+    let mut output = Vec::new();
+    FrameDecoder::new(frame).read_to_end(&mut output)?;
+    Ok(output)
+}
+
+/// The frame decoder reports format errors as an io::Error wrapping them
+fn frame_error(err: io::Error) -> lz4_flex::frame::Error {
+    *err.into_inner()
+        .and_then(|inner| inner.downcast().ok())
+        .expect("not an LZ4 frame error")
+}
+
+fn test_vectors() {
+    println!("Testing lz4 tool vectors");
+
+    let log = sensor_log(LOG_LINES);
+    assert_eq!(LZ4_FRAME[..4], 0x184d2204u32.to_le_bytes());
+    assert_eq!(decode_frame(&LZ4_FRAME).unwrap(), log);
+
+    // The raw block, as used without framing on flash or the wire, needs
+    // its decompressed size from elsewhere
+    let block = &LZ4_FRAME[LZ4_BLOCK];
+    assert_eq!(lz4_flex::decompress(block, log.len()).unwrap(), log);
+
+    // Decoding straight into a fixed buffer, such as a flash page cache
+    let mut page = [0u8; 8192];
+    let len = lz4_flex::decompress_into(block, &mut page).unwrap();
+    assert_eq!(page[..len], log);
+    assert!(lz4_flex::decompress_into(block, &mut page[..4096]).is_err());
+
+    println!("lz4 tool vectors passed");
+}
+
+fn test_roundtrip() {
+    println!("Testing lz4_flex roundtrip");
+
+    let log = sensor_log(4 * LOG_LINES);
+
+    let block = lz4_flex::compress_prepend_size(&log);
+    assert_eq!(lz4_flex::decompress_size_prepended(&block).unwrap(), log);
+
+    // Small blocks bound the decoder's buffers, the content checksum
+    // covers the whole image
+    let info = FrameInfo::new()
+        .block_size(BlockSize::Max64KB)
+        .content_checksum(true)
+        .content_size(Some(log.len() as u64));
+    let mut encoder = FrameEncoder::with_frame_info(info, Vec::new());
+    for chunk in log.chunks(1000) {
+        encoder.write_all(chunk).unwrap();
+    }
+    let frame = encoder.finish().unwrap();
+    println!(
+        "  {} bytes -> {} byte block, {} byte frame",
+        log.len(),
+        block.len(),
+        frame.len()
+    );
+    assert_eq!(decode_frame(&frame).unwrap(), log);
+
+    // Incompressible data grows by only a few bytes
+    let noise: Vec<u8> = (0..4096u32)
+        .map(|i| (i.wrapping_mul(2_654_435_761) >> 24) as u8)
+        .collect();
+    let block = lz4_flex::compress(&noise);
+    assert!(block.len() <= noise.len() + noise.len() / 255 + 16);
+    assert_eq!(lz4_flex::decompress(&block, noise.len()).unwrap(), noise);
+
+    println!("lz4_flex roundtrip tests passed");
+}
+
+fn test_corrupted_input() {
+    println!("Testing corrupted input");
+
+    use lz4_flex::frame::Error;
+
+    let mut frame = LZ4_FRAME;
+    frame[0] ^= 1;
+    let err = frame_error(decode_frame(&frame).unwrap_err());
+    println!("  {:<20} {}", "bad magic", err);
+    assert!(matches!(err, Error::WrongMagicNumber));
+
+    let mut frame = LZ4_FRAME;
+    frame[14] ^= 1;
+    let err = frame_error(decode_frame(&frame).unwrap_err());
+    println!("  {:<20} {}", "header checksum", err);
+    assert!(matches!(err, Error::HeaderChecksumError));
+
+    let mut frame = LZ4_FRAME;
+    frame[LZ4_FRAME.len() - 1] ^= 1;
+    let err = frame_error(decode_frame(&frame).unwrap_err());
+    println!("  {:<20} {}", "content checksum", err);
+    assert!(matches!(err, Error::ContentChecksumError));
+
+    let err = decode_frame(&LZ4_FRAME[..1000]).unwrap_err();
+    println!("  {:<20} {}", "truncated", err);
+    assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+
+    // A block has no checksum, but a match reaching before the start of
+    // the output is still caught
+    let mut block = LZ4_FRAME[LZ4_BLOCK].to_vec();
+    block[20] = 0xff;
+    block[21] = 0xff;
+    let result = lz4_flex::decompress(&block, sensor_log(LOG_LINES).len());
+    println!("  {:<20} {:?}", "corrupted block", result.as_ref().err());
+    assert!(result.is_err());
+
+    println!("Corrupted input tests passed");
+}
+
+/// A decoder run by the report, from compressed data to the log
+type Decode<'a> = &'a dyn Fn(&[u8]) -> Vec<u8>;
+
+fn report_decompression() {
+    let log = sensor_log(LOG_LINES);
+    println!("Decompression of a {} byte sensor log:", log.len());
+
+    // The frame decoder holds two buffers of the block size the header
+    // declares, 64 KiB for this frame, where the block decoder needs only
+    // the output
+    let rows: [(&str, &[u8], Decode); 2] = [
+        ("lz4 frame", &LZ4_FRAME, &|frame| {
+            decode_frame(frame).unwrap()
+        }),
+        ("lz4 block", &LZ4_FRAME[LZ4_BLOCK], &|block| {
+            lz4_flex::decompress(block, log.len()).unwrap()
+        }),
+    ];
+    for (name, compressed, decode) in rows {
+        let heap_base = reset_heap_peak();
+        assert_eq!(decode(compressed), log);
+        let heap = HEAP_PEAK.load(Ordering::Relaxed) - heap_base;
+
+        let start = Instant::now();
+        for _ in 0..DECODE_ITERATIONS {
+            std::hint::black_box(decode(std::hint::black_box(compressed)));
+        }
+        let bytes = log.len() as f64 * DECODE_ITERATIONS as f64;
+
+        println!(
+            "  {:<10} {:>6.1}% {:>8.2} MB/s {:>8} bytes heap",
+            name,
+            compressed.len() as f64 * 100.0 / log.len() as f64,
+            bytes / 1_000_000.0 / start.elapsed().as_secs_f64(),
+            heap
+        );
+    }
+}
+
+#[unsafe(no_mangle)]
+pub fn rust_crate_test_lz4_flex_main() {
+    println!("Running lz4_flex tests");
+
+    test_vectors();
+    test_roundtrip();
+    test_corrupted_input();
+    report_decompression();
+
+    println!("All lz4_flex tests completed");
+}
@@ -0,0 +1,313 @@
+// Copyright (c) 2025 Xiaomi Corporation
+// SPDX-License-Identifier: Apache-2.0
+
+//! lzma-rs example for NuttX
+//!
+//! Decodes an .xz and a legacy .lzma file made by XZ Utils, the latter also
+//! pushed in packet sized pieces as an OTA download arrives, bounds the
+//! dictionary memory, checks that corrupted files are rejected, and reports
+//! ratio, decode speed and peak heap. crates/miniz_oxide reports deflate on
+//! the same log.
+
+use hex_literal::hex;
+use lzma_rs::decompress::{Options, Stream};
+use lzma_rs::error::Error;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::io::Write;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Instant;
+
+const LOG_LINES: usize = 256;
+const DECODE_ITERATIONS: u32 = 16;
+
+/// `xz --lzma2=preset=9e,dict=64KiB` over `sensor_log(256)`, with a CRC64
+/// check
+const XZ_FILE: [u8; 960] = hex!(
+    "fd377a585a000004e6d6b44603c08407bc272101080000006bef0a99e013bb03"
+    "7c5d003a0f420213279087e88489594e2634fe97b2225ab484692a851043a0a1"
+    "be697a723ced9380390c6bb4428507410fa627a40eb433d694abd01b11c1277a"
+    "1c3ff0263875790e87f317800b28e124725369dfc172b49b2a717ba7841722cd"
+    "670a18a67753251eb4aadbfa8a7ca88d5c3b006f7caeda27c535d20acd99b410"
+    "865a868cce0a79afc33948aee3a3c58bf7f0a07873061aaeacd13439a989fed0"
+    "16f008fb7eb563de752199e80cbe8777330287752195504859c965441a1cdf87"
+    "ce6d7954c154ed20a88edf362be30532c1f3071bab7ab297b77db7a39d0ec5bc"
+    "58f22c11d2ddf612f5d214f041272b8de0184d12bb21d1a176cf53ba60716349"
+    "c437a544fd19333692383f1d689a512ee9e518239f706b64ce2e1d5bdbd1d832"
+    "c098eb3a4f0684e1b0d9372dd221b74f7d2347b017bfa53d648b5e6ca71dcf56"
+    "712e3d62e598fb9d70b5dfb24d758dbd1fab02613f9944d92ef87a7db8f8e631"
+    "16db4344a06ed9bc3bb0b4728b3fd238a16472d1b88d6d58bb409daae66789a5"
+    "9ab493eecbb06aca7b030c985eb44c2eeafcbf0db70d737861fa23193687586b"
+    "ee64806c115a94b3169f07484b2f873ab7725338cff52d4a7b4dc00d1079699f"
+    "a0337346129f76a824b59b3be88d0ca5ec3353862ca2dc5dfa7443eb778d61e4"
+    "935b5c0515111114b08278b5637abb757f6e86cdcc4a36522a42a7f487295ea2"
+    "c7b01619e2a452656f5c4dfd642c310de08eda094a84ad828161a14a1f8a1cfc"
+    "220386dc5f32cf6fed44d1559cf567e28bbb1c1476bc0971d6aa36c0c7c58c1c"
+    "5953b9ba616d24f157efa13ee5500b50d5c005c137ceb2b8bfe5bcf1bf789fcb"
+    "7ac0545606a531122607d3003fe18e6a347e1c19033aa35297fd6dcf126a5124"
+    "655bfd228b2e70d0754391d849c7af92fea45cd71fc270fbd121cee11c4e3cae"
+    "d02f38e9ff7b292dc1436deddd7d5d9bfd6ec66f943366420cb91baaaed7b46f"
+    "2cbe83a34614017def3383cc10f7c5be204cd95c4474444dc19a9f22a1ecc26d"
+    "c5f64028c4fa0102456d70103dbd4ce35585dfb84ab10b95e7d67b1652cfca6f"
+    "97b147f4687efe14e7a847ba7c6e2807e3a1741580e7595f27a7f67797262acb"
+    "e8b53232152208e6454d79acebd5b520ca29f0a62189021a5cbda49ba973a1e0"
+    "ebfd7174f13684672f27279977a4283bb7edee1a38347f06d033e32cf960ba81"
+    "9a7f68e16cf57702c725e0f9b394680ffabd1cb80ab2c658a856eac674a00000"
+    "b3b0023630e972aa00019c07bc270000b27f7771b1c467fb020000000004595a"
+);
+
+/// `lzma --lzma1=preset=9e,dict=64KiB` over `sensor_log(256)`; the legacy
+/// format has no size in the header here and no checksum at all
+const LZMA_FILE: [u8; 911] = hex!(
+    "5d00000100ffffffffffffffff003a0f420213279087e88489594e2634fe97b2"
+    "225ab484692a851043a0a1be697a723ced9380390c6bb4428507410fa627a40e"
+    "b433d694abd01b11c1277a1c3ff0263875790e87f317800b28e124725369dfc1"
+    "72b49b2a717ba7841722cd670a18a67753251eb4aadbfa8a7ca88d5c3b006f7c"
+    "aeda27c535d20acd99b410865a868cce0a79afc33948aee3a3c58bf7f0a07873"
+    "061aaeacd13439a989fed016f008fb7eb563de752199e80cbe87773302877521"
+    "95504859c965441a1cdf87ce6d7954c154ed20a88edf362be30532c1f3071bab"
+    "7ab297b77db7a39d0ec5bc58f22c11d2ddf612f5d214f041272b8de0184d12bb"
+    "21d1a176cf53ba60716349c437a544fd19333692383f1d689a512ee9e518239f"
+    "706b64ce2e1d5bdbd1d832c098eb3a4f0684e1b0d9372dd221b74f7d2347b017"
+    "bfa53d648b5e6ca71dcf56712e3d62e598fb9d70b5dfb24d758dbd1fab02613f"
+    "9944d92ef87a7db8f8e63116db4344a06ed9bc3bb0b4728b3fd238a16472d1b8"
+    "8d6d58bb409daae66789a59ab493eecbb06aca7b030c985eb44c2eeafcbf0db7"
+    "0d737861fa23193687586bee64806c115a94b3169f07484b2f873ab7725338cf"
+    "f52d4a7b4dc00d1079699fa0337346129f76a824b59b3be88d0ca5ec3353862c"
+    "a2dc5dfa7443eb778d61e4935b5c0515111114b08278b5637abb757f6e86cdcc"
+    "4a36522a42a7f487295ea2c7b01619e2a452656f5c4dfd642c310de08eda094a"
+    "84ad828161a14a1f8a1cfc220386dc5f32cf6fed44d1559cf567e28bbb1c1476"
+    "bc0971d6aa36c0c7c58c1c5953b9ba616d24f157efa13ee5500b50d5c005c137"
+    "ceb2b8bfe5bcf1bf789fcb7ac0545606a531122607d3003fe18e6a347e1c1903"
+    "3aa35297fd6dcf126a5124655bfd228b2e70d0754391d849c7af92fea45cd71f"
+    "c270fbd121cee11c4e3caed02f38e9ff7b292dc1436deddd7d5d9bfd6ec66f94"
+    "3366420cb91baaaed7b46f2cbe83a34614017def3383cc10f7c5be204cd95c44"
+    "74444dc19a9f22a1ecc26dc5f64028c4fa0102456d70103dbd4ce35585dfb84a"
+    "b10b95e7d67b1652cfca6f97b147f4687efe14e7a847ba7c6e2807e3a1741580"
+    "e7595f27a7f67797262acbe8b53232152208e6454d79acebd5b520ca29f0a621"
+    "89021a5cbda49ba973a1e0ebfd7174f13684672f27279977a4283bb7edee1a38"
+    "347f06d033e32cf960ba819a7f68e16cf57702c725e0f9b394680ffabd1cb80a"
+    "b2c658a856eb1b1ee7dfffeff84565"
+);
+
+static HEAP_IN_USE: AtomicUsize = AtomicUsize::new(0);
+static HEAP_PEAK: AtomicUsize = AtomicUsize::new(0);
+
+/// The system allocator, keeping count of the bytes in use and their peak
+struct CountingAllocator;
+
+fn record_alloc(size: usize) {
+    let in_use = HEAP_IN_USE.fetch_add(size, Ordering::Relaxed) + size;
+    HEAP_PEAK.fetch_max(in_use, Ordering::Relaxed);
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc(layout) };
+        if !ptr.is_null() {
+            record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc_zeroed(layout) };
+        if !ptr.is_null() {
+            record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) };
+        HEAP_IN_USE.fetch_sub(layout.size(), Ordering::Relaxed);
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = unsafe { System.realloc(ptr, layout, new_size) };
+        if !new_ptr.is_null() {
+            HEAP_IN_USE.fetch_sub(layout.size(), Ordering::Relaxed);
+            record_alloc(new_size);
+        }
+        new_ptr
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// Restarts the peak from the bytes in use now, and returns them
+fn reset_heap_peak() -> usize {
+    let in_use = HEAP_IN_USE.load(Ordering::Relaxed);
+    HEAP_PEAK.store(in_use, Ordering::Relaxed);
+    in_use
+}
+
+/// `sensor_log(256)` is what both files hold: one `t=500 ch2 adc=74` style
+/// reading per line
+fn sensor_log(lines: usize) -> Vec<u8> {
+    (0..lines)
+        .flat_map(|i| format!("t={} ch{} adc={}\n", i * 250, i % 4, (i * 37) % 1024).into_bytes())
+        .collect()
+}
+
+fn xz_decode(mut input: &[u8]) -> Result<Vec<u8>, Error> {
+    let mut output = Vec::new();
+    lzma_rs::xz_decompress(&mut input, &mut output)?;
+    Ok(output)
+}
+
+fn lzma_decode(mut input: &[u8], memlimit: Option<usize>) -> Result<Vec<u8>, Error> {
+    let options = Options {
+        memlimit,
+        ..Default::default()
+    };
+    let mut output = Vec::new();
+    lzma_rs::lzma_decompress_with_options(&mut input, &mut output, &options)?;
+    Ok(output)
+}
+
+fn test_vectors() {
+    println!("Testing XZ Utils vectors");
+
+    let log = sensor_log(LOG_LINES);
+    assert_eq!(XZ_FILE[..6], *b"\xfd7zXZ\0");
+    assert_eq!(xz_decode(&XZ_FILE).unwrap(), log);
+
+    // Properties byte 0x5d (lc=3, lp=0, pb=2), then the 64 KiB dictionary
+    assert_eq!(LZMA_FILE[0], 0x5d);
+    assert_eq!(LZMA_FILE[1..5], (64u32 * 1024).to_le_bytes());
+    assert_eq!(lzma_decode(&LZMA_FILE, None).unwrap(), log);
+
+    println!("XZ Utils vectors passed");
+}
+
+fn test_memlimit() {
+    println!("Testing dictionary memory limit");
+
+    // The dictionary grows with the output up to the size in the header,
+    // unless the limit stops it first
+    let log = sensor_log(LOG_LINES);
+    assert_eq!(lzma_decode(&LZMA_FILE, Some(64 * 1024)).unwrap(), log);
+    let err = lzma_decode(&LZMA_FILE, Some(4096)).unwrap_err();
+    println!("  {}", err);
+    assert!(matches!(err, Error::LzmaError(_)));
+
+    println!("Dictionary memory limit tests passed");
+}
+
+fn test_stream() {
+    println!("Testing streaming decode");
+
+    // Stream takes the input as it comes in, rather than pulling it from a
+    // reader, so a download handler can feed each packet
+    let mut stream = Stream::new(Vec::new());
+    for packet in LZMA_FILE.chunks(100) {
+        stream.write_all(packet).unwrap();
+    }
+    assert_eq!(stream.finish().unwrap(), sensor_log(LOG_LINES));
+
+    // Ending the download early is an error, not a short image
+    let mut stream = Stream::new(Vec::new());
+    stream.write_all(&LZMA_FILE[..500]).unwrap();
+    let err = stream.finish().unwrap_err();
+    println!("  truncated: {}", err);
+
+    println!("Streaming decode tests passed");
+}
+
+fn test_roundtrip() {
+    println!("Testing lzma-rs encoders");
+
+    // The lzma-rs encoders emit valid streams made only of literals, fine
+    // for tests but not for shrinking payloads
+    let log = sensor_log(LOG_LINES);
+
+    let mut xz = Vec::new();
+    lzma_rs::xz_compress(&mut &log[..], &mut xz).unwrap();
+    assert_eq!(xz_decode(&xz).unwrap(), log);
+
+    let mut lzma = Vec::new();
+    lzma_rs::lzma_compress(&mut &log[..], &mut lzma).unwrap();
+    assert_eq!(lzma_decode(&lzma, None).unwrap(), log);
+
+    println!(
+        "  {} bytes -> {} bytes xz, {} bytes lzma",
+        log.len(),
+        xz.len(),
+        lzma.len()
+    );
+
+    println!("lzma-rs encoder tests passed");
+}
+
+fn test_corrupted_input() {
+    println!("Testing corrupted input");
+
+    let mut file = XZ_FILE;
+    file[0] ^= 1;
+    println!("  {:<20} {}", "bad magic", xz_decode(&file).unwrap_err());
+
+    // The CRC64 of the block catches damage anywhere in the data
+    for pos in [40, 400, 800] {
+        let mut file = XZ_FILE;
+        file[pos] ^= 0x10;
+        let err = xz_decode(&file).unwrap_err();
+        println!("  {:<20} {}", format!("byte {} flipped", pos), err);
+    }
+
+    let err = xz_decode(&XZ_FILE[..XZ_FILE.len() - 12]).unwrap_err();
+    println!("  {:<20} {}", "no stream footer", err);
+
+    let err = lzma_decode(&LZMA_FILE[..600], None).unwrap_err();
+    println!("  {:<20} {}", "truncated .lzma", err);
+
+    println!("Corrupted input tests passed");
+}
+
+/// Prints ratio, speed and peak heap of one file
+fn report_file(name: &str, file: &[u8], decode: impl Fn(&[u8]) -> Result<Vec<u8>, Error>) {
+    let log = sensor_log(LOG_LINES);
+    let heap_base = reset_heap_peak();
+    assert_eq!(decode(file).unwrap(), log);
+    let heap = HEAP_PEAK.load(Ordering::Relaxed) - heap_base;
+
+    let start = Instant::now();
+    for _ in 0..DECODE_ITERATIONS {
+        std::hint::black_box(decode(std::hint::black_box(file)).unwrap());
+    }
+    let per_decode = start.elapsed().as_secs_f64() / DECODE_ITERATIONS as f64;
+
+    println!(
+        "  {:<6} {:>6.1}% {:>8.2} MB/s {:>8} bytes heap",
+        name,
+        file.len() as f64 * 100.0 / log.len() as f64,
+        log.len() as f64 / 1_000_000.0 / per_decode,
+        heap
+    );
+}
+
+fn report_decompression() {
+    println!(
+        "Decompression of a {} byte sensor log:",
+        sensor_log(LOG_LINES).len()
+    );
+
+    // The dictionary grows with the output, so for a file shorter than
+    // the 64 KiB in its header the heap stays well below it
+    report_file("xz", &XZ_FILE, xz_decode);
+    report_file("lzma", &LZMA_FILE, |file| lzma_decode(file, None));
+}
+
+#[unsafe(no_mangle)]
+pub fn rust_crate_test_lzma_rs_main() {
+    println!("Running lzma-rs tests");
+
+    test_vectors();
+    test_memlimit();
+    test_stream();
+    test_roundtrip();
+    test_corrupted_input();
+    report_decompression();
+
+    println!("All lzma-rs tests completed");
+}
@@ -1,12 +1,76 @@
 // Copyright (c) 2025 Xiaomi Corporation
 // SPDX-License-Identifier: Apache-2.0
 
+//! memchr example for NuttX
+//!
+//! Besides the basic searches, sweeps needle positions, haystack lengths and
+//! pointer alignments through the dispatching searchers and the portable
+//! fallbacks in `memchr::arch::all`, which are the ones Cortex-M and rv32
+//! targets run, and checks them against a naive search and the C library
+//! `memchr`.
+
+use memchr::arch::all::memchr::{One, Three, Two};
+use memchr::arch::all::{rabinkarp, twoway};
 use memchr::memchr;
 use memchr::memchr2;
 use memchr::memchr3;
 use memchr::memchr3_iter;
 use memchr::memmem;
 use memchr::memrchr;
+use std::ffi::{c_int, c_void};
+use std::hint::black_box;
+use std::time::Instant;
+
+unsafe extern "C" {
+    #[link_name = "memchr"]
+    fn libc_memchr(s: *const c_void, c: c_int, n: usize) -> *const c_void;
+}
+
+/// Haystack start offsets from a 64-byte boundary. The word-at-a-time
+/// fallbacks only see the offset within a word, the last one makes the
+/// haystack cross the next boundary. With
+/// `CONFIG_RUST_CRATE_MEMCHR_EXHAUSTIVE` all offsets are swept.
+fn alignments() -> Vec<usize> {
+    if cfg!(exhaustive) {
+        (0..64).collect()
+    } else {
+        vec![0, 1, 2, 3, 4, 5, 6, 7, 63]
+    }
+}
+
+/// Every haystack length up to this one is swept with the needle at every
+/// position.
+const SWEEP_LEN: usize = 64;
+
+/// Longer lengths around the end of the unrolled SSE2 and AVX2 loops, swept
+/// with the needle at every `LONG_POS_STEP`th position and at the end.
+const LONG_LENS: [usize; 6] = [127, 128, 129, 255, 256, 257];
+const LONG_POS_STEP: usize = if cfg!(exhaustive) { 1 } else { 5 };
+
+/// Bytes searched for by memchr, memchr2 and memchr3, in that order.
+const NEEDLE_BYTES: [u8; 3] = [b'a', b'N', 0x00];
+
+/// Differences from a needle byte that can fool a word-at-a-time search.
+const NEAR_MISSES: [u8; 6] = [0x01, 0x80, 0x7f, 0xfe, 0x81, 0xff];
+
+/// Needles for memmem, including a periodic one and one longer than a word.
+const NEEDLES: [&[u8]; 6] = [
+    b"N",
+    b"Nu",
+    b"tx!",
+    b"nuttx-rs",
+    b"aaaaaaab",
+    b"0123456789abcdef-0123",
+];
+
+/// Mismatches printed per sweep before the rest are only counted.
+const MAX_REPORTED: usize = 5;
+
+/// Haystack sizes of the throughput tables.
+const THROUGHPUT_SIZES: [usize; 3] = [64, 1024, 16 * 1024];
+
+/// Bytes scanned per throughput measurement.
+const THROUGHPUT_BYTES: usize = 256 * 1024;
 
 #[unsafe(no_mangle)]
 pub fn rust_crate_test_memchr_main() {
@@ -23,6 +87,13 @@ pub fn rust_crate_test_memchr_main() {
     test_memmem_find_iter();
     test_memmem_finder();
     test_throughput_demo();
+    test_sweep_memchr();
+    test_sweep_memrchr();
+    test_sweep_memchr2();
+    test_sweep_memchr3();
+    test_sweep_memmem();
+    test_sweep_memmem_rev();
+    report_throughput();
 
     println!("memchr testing completed");
 }
@@ -108,7 +179,7 @@ fn test_memmem_finder() {
     println!("Position of 'foo' in first haystack: {:?}", pos1);
     println!("Position of 'foo' in second haystack: {:?}", pos2);
 
-    if pos1 == Some(4) && pos2 == None {
+    if pos1 == Some(4) && pos2.is_none() {
         println!("memmem::Finder test passed");
     } else {
         println!("memmem::Finder test failed");
@@ -138,3 +209,378 @@ fn test_throughput_demo() {
         duration2.as_nanos() as f64 / duration1.as_nanos() as f64
     );
 }
+
+/// Searches with the C library, which is NuttX libc on target.
+fn c_memchr(needle: u8, haystack: &[u8]) -> Option<usize> {
+    let found = unsafe { libc_memchr(haystack.as_ptr().cast(), needle.into(), haystack.len()) };
+    (!found.is_null()).then(|| found as usize - haystack.as_ptr() as usize)
+}
+
+fn naive_find(needles: &[u8], haystack: &[u8]) -> Option<usize> {
+    haystack.iter().position(|b| needles.contains(b))
+}
+
+fn naive_rfind(needles: &[u8], haystack: &[u8]) -> Option<usize> {
+    haystack.iter().rposition(|b| needles.contains(b))
+}
+
+fn naive_memmem(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack.windows(needle.len()).position(|w| w == needle)
+}
+
+fn naive_memmem_rev(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack.windows(needle.len()).rposition(|w| w == needle)
+}
+
+/// Text made of near misses of the needle bytes, none of them a needle.
+fn near_miss_bytes(needles: &[u8], len: usize) -> Vec<u8> {
+    (0..len)
+        .map(|i| needles[i % needles.len()] ^ NEAR_MISSES[i / needles.len() % NEAR_MISSES.len()])
+        .collect()
+}
+
+/// Repeats of the needle with its last byte changed, so that every needle
+/// start is a partial match.
+fn near_miss_text(needle: &[u8], len: usize) -> Vec<u8> {
+    let last = needle.len() - 1;
+    (0..len)
+        .map(|i| match i % needle.len() {
+            j if j == last => needle[j] ^ 0x20,
+            j => needle[j],
+        })
+        .collect()
+}
+
+/// Writes a needle byte at `pos` and another one at the far end, so that
+/// only the first (or last, with `reverse`) occurrence is at `pos`.
+fn plant_bytes(haystack: &mut [u8], pos: usize, needles: &[u8], reverse: bool) {
+    let len = haystack.len();
+    if pos >= len {
+        return;
+    }
+    let decoy = if reverse { 0 } else { len - 1 };
+    haystack[decoy] = needles[(pos + 1) % needles.len()];
+    haystack[pos] = needles[pos % needles.len()];
+}
+
+/// Same as `plant_bytes`, for a needle string. The decoy is only written
+/// when it doesn't overlap the occurrence at `pos`.
+fn plant_needle(haystack: &mut [u8], pos: usize, needle: &[u8], reverse: bool) {
+    let (len, n) = (haystack.len(), needle.len());
+    if pos + n > len {
+        return;
+    }
+    let decoy = if reverse { 0 } else { len - n };
+    if decoy + n <= pos || pos + n <= decoy {
+        haystack[decoy..decoy + n].copy_from_slice(needle);
+    }
+    haystack[pos..pos + n].copy_from_slice(needle);
+}
+
+/// Runs `check` on every alignment, length and needle position, and panics
+/// if any of them fails. A position equal to the length stands for a
+/// haystack without the needle. Each
+/// haystack is reset to `background` and surrounded by `fence`, which is
+/// full of needles so that reading past either end shows up as a wrong
+/// match. `check` plants the needle and returns the name of the first
+/// searcher that disagrees with the naive one.
+fn sweep(
+    name: &str,
+    fence: &[u8],
+    background: &[u8],
+    mut check: impl FnMut(&mut [u8], usize) -> Option<&'static str>,
+) {
+    println!("Testing {} sweep", name);
+
+    let max_len = LONG_LENS[LONG_LENS.len() - 1];
+    let mut buf = vec![0u8; 3 * 64 + max_len];
+    let base = buf.as_ptr().align_offset(64);
+    let (mut cases, mut failures) = (0, 0);
+
+    for align in alignments() {
+        for len in (0..=SWEEP_LEN).chain(LONG_LENS) {
+            for (b, f) in buf.iter_mut().zip(fence.iter().cycle()) {
+                *b = *f;
+            }
+            let start = base + align;
+            let positions = (0..=len)
+                .filter(|pos| len <= SWEEP_LEN || pos % LONG_POS_STEP == 0 || pos + 1 >= len);
+            for pos in positions {
+                let haystack = &mut buf[start..start + len];
+                haystack.copy_from_slice(&background[..len]);
+                cases += 1;
+                if let Some(searcher) = check(haystack, pos) {
+                    failures += 1;
+                    if failures <= MAX_REPORTED {
+                        println!(
+                            "  {} mismatch: align {} len {} pos {}",
+                            searcher, align, len, pos
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    assert_eq!(
+        failures, 0,
+        "{} sweep failed ({} of {} cases)",
+        name, failures, cases
+    );
+    println!("{} sweep passed ({} cases)", name, cases);
+}
+
+/// Name of the first result that differs from `expected`.
+fn first_mismatch(
+    expected: Option<usize>,
+    results: &[(&'static str, Option<usize>)],
+) -> Option<&'static str> {
+    results
+        .iter()
+        .find(|(_, found)| *found != expected)
+        .map(|(searcher, _)| *searcher)
+}
+
+fn test_sweep_memchr() {
+    let needle = NEEDLE_BYTES[0];
+    let one = One::new(needle);
+    let background = near_miss_bytes(&NEEDLE_BYTES[..1], LONG_LENS[LONG_LENS.len() - 1]);
+
+    sweep(
+        "memchr",
+        &NEEDLE_BYTES[..1],
+        &background,
+        |haystack, pos| {
+            plant_bytes(haystack, pos, &[needle], false);
+            let haystack = &*haystack;
+            first_mismatch(
+                naive_find(&[needle], haystack),
+                &[
+                    ("memchr", memchr(needle, haystack)),
+                    ("One::find", one.find(haystack)),
+                    ("C memchr", c_memchr(needle, haystack)),
+                ],
+            )
+        },
+    );
+}
+
+fn test_sweep_memrchr() {
+    let needle = NEEDLE_BYTES[0];
+    let one = One::new(needle);
+    let background = near_miss_bytes(&NEEDLE_BYTES[..1], LONG_LENS[LONG_LENS.len() - 1]);
+
+    sweep(
+        "memrchr",
+        &NEEDLE_BYTES[..1],
+        &background,
+        |haystack, pos| {
+            plant_bytes(haystack, pos, &[needle], true);
+            let haystack = &*haystack;
+            first_mismatch(
+                naive_rfind(&[needle], haystack),
+                &[
+                    ("memrchr", memrchr(needle, haystack)),
+                    ("One::rfind", one.rfind(haystack)),
+                ],
+            )
+        },
+    );
+}
+
+fn test_sweep_memchr2() {
+    let [n1, n2, _] = NEEDLE_BYTES;
+    let two = Two::new(n1, n2);
+    let background = near_miss_bytes(&NEEDLE_BYTES[..2], LONG_LENS[LONG_LENS.len() - 1]);
+
+    sweep(
+        "memchr2",
+        &NEEDLE_BYTES[..2],
+        &background,
+        |haystack, pos| {
+            plant_bytes(haystack, pos, &[n1, n2], false);
+            let haystack = &*haystack;
+            first_mismatch(
+                naive_find(&[n1, n2], haystack),
+                &[
+                    ("memchr2", memchr2(n1, n2, haystack)),
+                    ("Two::find", two.find(haystack)),
+                ],
+            )
+        },
+    );
+}
+
+fn test_sweep_memchr3() {
+    let [n1, n2, n3] = NEEDLE_BYTES;
+    let three = Three::new(n1, n2, n3);
+    let background = near_miss_bytes(&NEEDLE_BYTES, LONG_LENS[LONG_LENS.len() - 1]);
+
+    sweep("memchr3", &NEEDLE_BYTES, &background, |haystack, pos| {
+        plant_bytes(haystack, pos, &NEEDLE_BYTES, false);
+        let haystack = &*haystack;
+        first_mismatch(
+            naive_find(&NEEDLE_BYTES, haystack),
+            &[
+                ("memchr3", memchr3(n1, n2, n3, haystack)),
+                ("Three::find", three.find(haystack)),
+            ],
+        )
+    });
+}
+
+fn test_sweep_memmem() {
+    for needle in NEEDLES {
+        let finder = memmem::Finder::new(needle);
+        let two_way = twoway::Finder::new(needle);
+        let rabin_karp = rabinkarp::Finder::new(needle);
+        let background = near_miss_text(needle, LONG_LENS[LONG_LENS.len() - 1]);
+        let name = format!("memmem::Finder {:?}", String::from_utf8_lossy(needle));
+
+        sweep(&name, needle, &background, |haystack, pos| {
+            plant_needle(haystack, pos, needle, false);
+            let haystack = &*haystack;
+            first_mismatch(
+                naive_memmem(haystack, needle),
+                &[
+                    ("Finder", finder.find(haystack)),
+                    ("twoway::Finder", two_way.find(haystack, needle)),
+                    ("rabinkarp::Finder", rabin_karp.find(haystack, needle)),
+                ],
+            )
+        });
+    }
+}
+
+fn test_sweep_memmem_rev() {
+    for needle in NEEDLES {
+        let finder = memmem::FinderRev::new(needle);
+        let two_way = twoway::FinderRev::new(needle);
+        let rabin_karp = rabinkarp::FinderRev::new(needle);
+        let background = near_miss_text(needle, LONG_LENS[LONG_LENS.len() - 1]);
+        let name = format!("memmem::FinderRev {:?}", String::from_utf8_lossy(needle));
+
+        sweep(&name, needle, &background, |haystack, pos| {
+            plant_needle(haystack, pos, needle, true);
+            let haystack = &*haystack;
+            first_mismatch(
+                naive_memmem_rev(haystack, needle),
+                &[
+                    ("FinderRev", finder.rfind(haystack)),
+                    ("twoway::FinderRev", two_way.rfind(haystack, needle)),
+                    ("rabinkarp::FinderRev", rabin_karp.rfind(haystack, needle)),
+                ],
+            )
+        });
+    }
+}
+
+/// Throughput of `search` over a haystack of `len` bytes, in MB/s.
+fn throughput(len: usize, mut search: impl FnMut() -> Option<usize>) -> f64 {
+    let iterations = THROUGHPUT_BYTES / len;
+    let start = Instant::now();
+    for _ in 0..iterations {
+        black_box(search());
+    }
+    let elapsed = start.elapsed().as_secs_f64();
+    (iterations * len) as f64 / elapsed / 1_000_000.0
+}
+
+/// Prints one table row per haystack size, with a column per searcher
+/// given by `searcher(haystack, column)`. A None rate prints as `-`.
+fn print_table(
+    title: &str,
+    columns: [&str; 4],
+    background: &[u8],
+    searcher: impl Fn(&[u8], usize) -> Option<f64>,
+) {
+    println!("{} (MB/s, needle absent)", title);
+    println!(
+        "{:>8} {:>10} {:>10} {:>10} {:>10}",
+        "size", columns[0], columns[1], columns[2], columns[3]
+    );
+    for len in THROUGHPUT_SIZES {
+        print!("{:>8}", len);
+        for column in 0..columns.len() {
+            match searcher(&background[..len], column) {
+                Some(rate) => print!(" {:>10.1}", rate),
+                None => print!(" {:>10}", "-"),
+            }
+        }
+        println!();
+    }
+}
+
+fn report_throughput() {
+    println!("Measuring throughput");
+
+    let max_len = THROUGHPUT_SIZES[THROUGHPUT_SIZES.len() - 1];
+    let columns = ["crate", "fallback", "naive", "C"];
+    let [n1, n2, n3] = NEEDLE_BYTES;
+
+    let background = near_miss_bytes(&NEEDLE_BYTES[..1], max_len);
+    let one = One::new(n1);
+    print_table("memchr", columns, &background, |h, column| {
+        Some(match column {
+            0 => throughput(h.len(), || memchr(n1, black_box(h))),
+            1 => throughput(h.len(), || one.find(black_box(h))),
+            2 => throughput(h.len(), || naive_find(&[n1], black_box(h))),
+            _ => throughput(h.len(), || c_memchr(n1, black_box(h))),
+        })
+    });
+    print_table("memrchr", columns, &background, |h, column| {
+        Some(match column {
+            0 => throughput(h.len(), || memrchr(n1, black_box(h))),
+            1 => throughput(h.len(), || one.rfind(black_box(h))),
+            2 => throughput(h.len(), || naive_rfind(&[n1], black_box(h))),
+            _ => return None,
+        })
+    });
+
+    let background = near_miss_bytes(&NEEDLE_BYTES[..2], max_len);
+    let two = Two::new(n1, n2);
+    print_table("memchr2", columns, &background, |h, column| {
+        Some(match column {
+            0 => throughput(h.len(), || memchr2(n1, n2, black_box(h))),
+            1 => throughput(h.len(), || two.find(black_box(h))),
+            2 => throughput(h.len(), || naive_find(&[n1, n2], black_box(h))),
+            _ => return None,
+        })
+    });
+
+    let background = near_miss_bytes(&NEEDLE_BYTES, max_len);
+    let three = Three::new(n1, n2, n3);
+    print_table("memchr3", columns, &background, |h, column| {
+        Some(match column {
+            0 => throughput(h.len(), || memchr3(n1, n2, n3, black_box(h))),
+            1 => throughput(h.len(), || three.find(black_box(h))),
+            2 => throughput(h.len(), || naive_find(&NEEDLE_BYTES, black_box(h))),
+            _ => return None,
+        })
+    });
+
+    // Every needle start in the text is a partial match, which is the worst
+    // case for the naive search.
+    let needle = NEEDLES[3];
+    let background = near_miss_text(needle, max_len);
+    let finder = memmem::Finder::new(needle);
+    let two_way = twoway::Finder::new(needle);
+    print_table("memmem::Finder", columns, &background, |h, column| {
+        Some(match column {
+            0 => throughput(h.len(), || finder.find(black_box(h))),
+            1 => throughput(h.len(), || two_way.find(black_box(h), needle)),
+            2 => throughput(h.len(), || naive_memmem(black_box(h), needle)),
+            _ => return None,
+        })
+    });
+    let finder = memmem::FinderRev::new(needle);
+    let two_way = twoway::FinderRev::new(needle);
+    print_table("memmem::FinderRev", columns, &background, |h, column| {
+        Some(match column {
+            0 => throughput(h.len(), || finder.rfind(black_box(h))),
+            1 => throughput(h.len(), || two_way.rfind(black_box(h), needle)),
+            2 => throughput(h.len(), || naive_memmem_rev(black_box(h), needle)),
+            _ => return None,
+        })
+    });
+}
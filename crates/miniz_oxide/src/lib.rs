@@ -3,6 +3,7 @@
 
 use miniz_oxide::deflate::compress_to_vec;
 use miniz_oxide::inflate::decompress_to_vec_with_limit;
+use std::time::Instant;
 
 fn roundtrip(data: &[u8]) -> bool {
     println!("Original data length: {}", data.len());
@@ -28,6 +29,33 @@ fn roundtrip(data: &[u8]) -> bool {
     }
 }
 
+/// The sensor log the lz4_flex, brotli_decompressor, ruzstd and lzma_rs
+/// crates decode, so their figures can be read against deflate's
+fn report_sensor_log() {
+    let log: Vec<u8> = (0..256)
+        .flat_map(|i| format!("t={} ch{} adc={}\n", i * 250, i % 4, (i * 37) % 1024).into_bytes())
+        .collect();
+    let compressed = compress_to_vec(&log, 6);
+    assert_eq!(
+        decompress_to_vec_with_limit(&compressed, 60000).unwrap(),
+        log
+    );
+
+    let iterations = 16;
+    let start = Instant::now();
+    for _ in 0..iterations {
+        std::hint::black_box(decompress_to_vec_with_limit(&compressed, 60000).unwrap());
+    }
+    let seconds = start.elapsed().as_secs_f64() / iterations as f64;
+
+    println!("\nDecompression of a {} byte sensor log:", log.len());
+    println!(
+        "  deflate {:>6.1}% {:>8.2} MB/s",
+        compressed.len() as f64 * 100.0 / log.len() as f64,
+        log.len() as f64 / 1_000_000.0 / seconds
+    );
+}
+
 #[unsafe(no_mangle)]
 pub fn rust_crate_test_miniz_oxide_main() {
     println!("Testing miniz_oxide compression/decompression...");
@@ -54,5 +82,7 @@ pub fn rust_crate_test_miniz_oxide_main() {
         println!("Large string roundtrip test: FAILED");
     }
 
+    report_sensor_log();
+
     println!("miniz_oxide test complete!");
 }
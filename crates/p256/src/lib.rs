@@ -0,0 +1,270 @@
+// Copyright (c) 2025 Xiaomi Corporation
+// SPDX-License-Identifier: Apache-2.0
+
+//! p256 example for NuttX
+//!
+//! Checks ECDSA over NIST P-256 with SHA-256 against the deterministic
+//! signatures of RFC 6979 and a set of Wycheproof-style invalid signatures,
+//! generates keys from /dev/urandom and measures signing and verification
+//! time.
+
+use hex_literal::hex;
+use p256::ecdsa::signature::{Signer, Verifier};
+use p256::ecdsa::{Signature, SigningKey, VerifyingKey};
+use rand_core::OsRng;
+use std::fs::File;
+use std::io::Read;
+use std::num::NonZeroU32;
+use std::time::Instant;
+
+const TIMING_ITERATIONS: u32 = 4;
+
+/// getrandom 0.2 has no NuttX backend, so entropy comes from /dev/urandom.
+/// The same backend is copied unchanged into ed25519_dalek, x25519_dalek,
+/// p256 and rustls
+fn dev_urandom(buf: &mut [u8]) -> Result<(), getrandom::Error> {
+    File::open("/dev/urandom")
+        .and_then(|mut file| file.read_exact(buf))
+        .map_err(|err| {
+            err.raw_os_error()
+                .and_then(|code| NonZeroU32::new(code as u32))
+                .map_or(getrandom::Error::UNEXPECTED, getrandom::Error::from)
+        })
+}
+
+getrandom::register_custom_getrandom!(dev_urandom);
+
+/// RFC 6979, appendix A.2.5
+const SECRET: [u8; 32] = hex!("c9afa9d845ba75166b5c215767b1d6934e50c3db36e89b127b8a622b120f6721");
+const PUBLIC: [u8; 65] = hex!(
+    "04"
+    "60fed4ba255a9d31c961eb74c6356d68c049b8923b61fa6ce669622e60f29fb6"
+    "7903fe1008b8bc99a41ae9e95628bc64f2f1b20c2d7e9f5177a3c294d4462299"
+);
+
+struct TestCase {
+    tc_id: u32,
+    comment: &'static str,
+    msg: &'static [u8],
+    /// r || s, big endian
+    sig: [u8; 64],
+    valid: bool,
+}
+
+/// Laid out like the Wycheproof ecdsa_secp256r1_sha256_p1363_test.json
+/// groups, all against the RFC 6979 key
+const TEST_CASES: &[TestCase] = &[
+    TestCase {
+        tc_id: 1,
+        comment: "RFC 6979 \"sample\"",
+        msg: b"sample",
+        sig: hex!(
+            "efd48b2aacb6a8fd1140dd9cd45e81d69d2c877b56aaf991c34d0ea84eaf3716"
+            "f7cb1c942d657c41d436c7a1b6e29f65f3e900dbb9aff4064dc4ab2f843acda8"
+        ),
+        valid: true,
+    },
+    TestCase {
+        tc_id: 2,
+        comment: "RFC 6979 \"test\"",
+        msg: b"test",
+        sig: hex!(
+            "f1abb023518351cd71d881567b1ea663ed3efcf6c5132b354f28d3b0b7d38367"
+            "019f4113742a2b14bd25926b49c649155f267e60d3814b4c0cc84250e46f0083"
+        ),
+        valid: true,
+    },
+    TestCase {
+        tc_id: 3,
+        comment: "s replaced by n - s",
+        msg: b"sample",
+        sig: hex!(
+            "efd48b2aacb6a8fd1140dd9cd45e81d69d2c877b56aaf991c34d0ea84eaf3716"
+            "0834e36ad29a83bf2bc9385e491d6099c8fdf9d1ed67aa7ea5f51f93782857a9"
+        ),
+        valid: true,
+    },
+    TestCase {
+        tc_id: 4,
+        comment: "modified message",
+        msg: b"samplf",
+        sig: hex!(
+            "efd48b2aacb6a8fd1140dd9cd45e81d69d2c877b56aaf991c34d0ea84eaf3716"
+            "f7cb1c942d657c41d436c7a1b6e29f65f3e900dbb9aff4064dc4ab2f843acda8"
+        ),
+        valid: false,
+    },
+    TestCase {
+        tc_id: 5,
+        comment: "modified r",
+        msg: b"sample",
+        sig: hex!(
+            "efd48b2aacb6a8fd1140dd9cd45e81d69d2c877b56aaf991c34d0ea84eaf3717"
+            "f7cb1c942d657c41d436c7a1b6e29f65f3e900dbb9aff4064dc4ab2f843acda8"
+        ),
+        valid: false,
+    },
+    TestCase {
+        tc_id: 6,
+        comment: "r and s swapped",
+        msg: b"sample",
+        sig: hex!(
+            "f7cb1c942d657c41d436c7a1b6e29f65f3e900dbb9aff4064dc4ab2f843acda8"
+            "efd48b2aacb6a8fd1140dd9cd45e81d69d2c877b56aaf991c34d0ea84eaf3716"
+        ),
+        valid: false,
+    },
+    TestCase {
+        tc_id: 7,
+        comment: "r = 0",
+        msg: b"sample",
+        sig: hex!(
+            "0000000000000000000000000000000000000000000000000000000000000000"
+            "f7cb1c942d657c41d436c7a1b6e29f65f3e900dbb9aff4064dc4ab2f843acda8"
+        ),
+        valid: false,
+    },
+    TestCase {
+        tc_id: 8,
+        comment: "s = 0",
+        msg: b"sample",
+        sig: hex!(
+            "efd48b2aacb6a8fd1140dd9cd45e81d69d2c877b56aaf991c34d0ea84eaf3716"
+            "0000000000000000000000000000000000000000000000000000000000000000"
+        ),
+        valid: false,
+    },
+    TestCase {
+        tc_id: 9,
+        comment: "r = n",
+        msg: b"sample",
+        sig: hex!(
+            "ffffffff00000000ffffffffffffffffbce6faada7179e84f3b9cac2fc632551"
+            "f7cb1c942d657c41d436c7a1b6e29f65f3e900dbb9aff4064dc4ab2f843acda8"
+        ),
+        valid: false,
+    },
+];
+
+fn test_rfc6979_signing() {
+    println!("Testing RFC 6979 deterministic signatures");
+
+    let signing_key = SigningKey::from_slice(&SECRET).unwrap();
+    let verifying_key = signing_key.verifying_key();
+    assert_eq!(verifying_key.to_encoded_point(false).as_bytes(), PUBLIC);
+
+    for case in &TEST_CASES[..2] {
+        let sig: Signature = signing_key.sign(case.msg);
+        assert_eq!(sig.to_bytes()[..], case.sig);
+    }
+
+    println!("RFC 6979 deterministic signatures passed");
+}
+
+fn test_vectors() {
+    println!("Testing Wycheproof-style verification vectors");
+
+    let key = VerifyingKey::from_sec1_bytes(&PUBLIC).unwrap();
+    for case in TEST_CASES {
+        let result = Signature::from_slice(&case.sig).and_then(|sig| key.verify(case.msg, &sig));
+        println!(
+            "  tcId {} {:<24} {}",
+            case.tc_id,
+            case.comment,
+            if result.is_ok() { "valid" } else { "invalid" }
+        );
+        assert_eq!(result.is_ok(), case.valid);
+    }
+
+    println!("Wycheproof-style vectors passed");
+}
+
+fn test_encodings() {
+    println!("Testing key and signature encodings");
+
+    // A compressed point is 33 bytes and decodes to the same key
+    let key = VerifyingKey::from_sec1_bytes(&PUBLIC).unwrap();
+    let compressed = key.to_encoded_point(true);
+    assert_eq!(compressed.len(), 33);
+    assert_eq!(
+        VerifyingKey::from_sec1_bytes(compressed.as_bytes()).unwrap(),
+        key
+    );
+
+    // A point off the curve is rejected
+    let mut off_curve = PUBLIC;
+    off_curve[64] ^= 1;
+    assert!(VerifyingKey::from_sec1_bytes(&off_curve).is_err());
+
+    // DER signatures, as X.509 and most servers use them
+    let sig = Signature::from_slice(&TEST_CASES[0].sig).unwrap();
+    let der = sig.to_der();
+    assert_eq!(Signature::from_der(der.as_bytes()).unwrap(), sig);
+    key.verify(TEST_CASES[0].msg, &sig).unwrap();
+
+    // Trailing garbage after the DER sequence is not accepted
+    let mut padded = der.as_bytes().to_vec();
+    padded.push(0);
+    assert!(Signature::from_der(&padded).is_err());
+
+    // normalize_s() maps s to the lower half, which some protocols require
+    let high = Signature::from_slice(&TEST_CASES[0].sig).unwrap();
+    let low = high.normalize_s().unwrap();
+    assert_eq!(low.to_bytes()[..], TEST_CASES[2].sig);
+    assert!(low.normalize_s().is_none());
+
+    println!("Key and signature encoding tests passed");
+}
+
+fn test_generated_key() {
+    println!("Testing key generation from /dev/urandom");
+
+    let first = SigningKey::random(&mut OsRng);
+    let second = SigningKey::random(&mut OsRng);
+    assert_ne!(first.to_bytes(), second.to_bytes());
+
+    let msg = b"nuttx firmware 1.4.2";
+    let sig: Signature = first.sign(msg);
+    first.verifying_key().verify(msg, &sig).unwrap();
+    assert!(second.verifying_key().verify(msg, &sig).is_err());
+
+    println!("Key generation tests passed");
+}
+
+fn report_timing() {
+    println!("Timing, {} iterations:", TIMING_ITERATIONS);
+
+    let signing_key = SigningKey::from_slice(&SECRET).unwrap();
+    let verifying_key = *signing_key.verifying_key();
+    let msg = [0x5a; 256];
+
+    let start = Instant::now();
+    for _ in 0..TIMING_ITERATIONS {
+        let sig: Signature = signing_key.sign(&msg);
+        std::hint::black_box(sig);
+    }
+    let sign = start.elapsed() / TIMING_ITERATIONS;
+
+    let sig: Signature = signing_key.sign(&msg);
+    let start = Instant::now();
+    for _ in 0..TIMING_ITERATIONS {
+        verifying_key.verify(&msg, &sig).unwrap();
+    }
+    let verify = start.elapsed() / TIMING_ITERATIONS;
+
+    println!("  {:<8} {:>8} us", "sign", sign.as_micros());
+    println!("  {:<8} {:>8} us", "verify", verify.as_micros());
+}
+
+#[unsafe(no_mangle)]
+pub fn rust_crate_test_p256_main() {
+    println!("Running p256 tests");
+
+    test_rfc6979_signing();
+    test_vectors();
+    test_encodings();
+    test_generated_key();
+    report_timing();
+
+    println!("All p256 tests completed");
+}
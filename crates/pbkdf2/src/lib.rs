@@ -0,0 +1,136 @@
+// Copyright (c) 2025 Xiaomi Corporation
+// SPDX-License-Identifier: Apache-2.0
+
+//! pbkdf2 example for NuttX
+//!
+//! Checks PBKDF2-HMAC-SHA1 against RFC 6070 and PBKDF2-HMAC-SHA256 against
+//! RFC 7914, and measures how many iterations a board manages per second,
+//! which is what an iteration count for password hashing is chosen from.
+
+use hex_literal::hex;
+use pbkdf2::{pbkdf2_hmac, pbkdf2_hmac_array};
+use sha1::Sha1;
+use sha2::Sha256;
+use std::time::Instant;
+
+const BENCH_ITERATIONS: u32 = 4096;
+
+struct TestCase {
+    password: &'static [u8],
+    salt: &'static [u8],
+    rounds: u32,
+    expected: &'static [u8],
+}
+
+/// RFC 6070, section 2
+const SHA1_CASES: &[TestCase] = &[
+    TestCase {
+        password: b"password",
+        salt: b"salt",
+        rounds: 1,
+        expected: &hex!("0c60c80f961f0e71f3a9b524af6012062fe037a6"),
+    },
+    TestCase {
+        password: b"password",
+        salt: b"salt",
+        rounds: 2,
+        expected: &hex!("ea6c014dc72d6f8ccd1ed92ace1d41f0d8de8957"),
+    },
+    TestCase {
+        password: b"password",
+        salt: b"salt",
+        rounds: 4096,
+        expected: &hex!("4b007901b765489abead49d926f721d065a429c1"),
+    },
+    TestCase {
+        password: b"passwordPASSWORDpassword",
+        salt: b"saltSALTsaltSALTsaltSALTsaltSALTsalt",
+        rounds: 4096,
+        expected: &hex!("3d2eec4fe41c849b80c8d83662c0e44a8b291a964cf2f07038"),
+    },
+    TestCase {
+        password: b"pass\0word",
+        salt: b"sa\0lt",
+        rounds: 4096,
+        expected: &hex!("56fa6aa75548099dcc37d7f03425e0c3"),
+    },
+];
+
+/// RFC 7914, section 11
+const SHA256_CASE: TestCase = TestCase {
+    password: b"passwd",
+    salt: b"salt",
+    rounds: 1,
+    expected: &hex!(
+        "55ac046e56e3089fec1691c22544b605f94185216dde0465e68b9d57c20dacbc"
+        "49ca9cccf179b645991664b39d77ef317c71b845b1e30bd509112041d3a19783"
+    ),
+};
+
+fn test_rfc6070_vectors() {
+    println!("Testing RFC 6070 PBKDF2-HMAC-SHA1 test vectors");
+
+    for case in SHA1_CASES {
+        println!("  {} bytes, {} rounds", case.expected.len(), case.rounds);
+        let mut key = vec![0u8; case.expected.len()];
+        pbkdf2_hmac::<Sha1>(case.password, case.salt, case.rounds, &mut key);
+        assert_eq!(key, case.expected);
+    }
+
+    println!("RFC 6070 test vectors passed");
+}
+
+fn test_rfc7914_vector() {
+    println!("Testing RFC 7914 PBKDF2-HMAC-SHA256 test vector");
+
+    let case = &SHA256_CASE;
+    let key = pbkdf2_hmac_array::<Sha256, 64>(case.password, case.salt, case.rounds);
+    assert_eq!(key[..], *case.expected);
+
+    // A shorter key is a prefix of a longer one
+    let short = pbkdf2_hmac_array::<Sha256, 20>(case.password, case.salt, case.rounds);
+    assert_eq!(short[..], case.expected[..20]);
+
+    println!("RFC 7914 test vector passed");
+}
+
+fn report_iteration_rate() {
+    println!("Iteration rate, {} rounds:", BENCH_ITERATIONS);
+
+    let start = Instant::now();
+    std::hint::black_box(pbkdf2_hmac_array::<Sha1, 20>(
+        b"password",
+        b"salt",
+        BENCH_ITERATIONS,
+    ));
+    let elapsed = start.elapsed().as_secs_f64();
+    println!(
+        "  {:<20} {:>8.0} rounds/s",
+        "PBKDF2-HMAC-SHA1",
+        BENCH_ITERATIONS as f64 / elapsed
+    );
+
+    let start = Instant::now();
+    std::hint::black_box(pbkdf2_hmac_array::<Sha256, 32>(
+        b"password",
+        b"salt",
+        BENCH_ITERATIONS,
+    ));
+    let elapsed = start.elapsed().as_secs_f64();
+    println!(
+        "  {:<20} {:>8.0} rounds/s",
+        "PBKDF2-HMAC-SHA256",
+        BENCH_ITERATIONS as f64 / elapsed
+    );
+}
+
+#[unsafe(no_mangle)]
+pub fn rust_crate_test_pbkdf2_main() {
+    println!("Running pbkdf2 tests");
+
+    test_rfc6070_vectors();
+    test_rfc7914_vector();
+    report_iteration_rate();
+
+    println!("All pbkdf2 tests completed");
+}
@@ -0,0 +1,280 @@
+// Copyright (c) 2025 Xiaomi Corporation
+// SPDX-License-Identifier: Apache-2.0
+
+//! postcard example for NuttX
+//!
+//! Round-trips the `Person`/`Address` structs used by the serde_json crate,
+//! plus enums, maps and byte arrays, and checks the exact wire bytes. Also
+//! covers the no-alloc mode, where data is encoded into fixed buffers or
+//! `heapless` containers and borrowed back without touching the heap.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct Person {
+    name: String,
+    age: u32,
+    phones: Vec<String>,
+    address: Address,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct Address {
+    street: String,
+    city: String,
+    zip: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+enum Command {
+    Reset,
+    SetLevel(u8),
+    Move { x: i16, y: i16 },
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct Frame {
+    id: u16,
+    #[serde(with = "serde_bytes")]
+    payload: Vec<u8>,
+    checksum: [u8; 4],
+}
+
+/// Same layout as `Person`, borrowing the strings from the input buffer
+#[derive(Deserialize, Debug, PartialEq)]
+struct PersonRef<'a> {
+    name: &'a str,
+    age: u32,
+    #[serde(borrow)]
+    phones: heapless::Vec<&'a str, 2>,
+    #[serde(borrow)]
+    address: AddressRef<'a>,
+}
+
+#[derive(Deserialize, Debug, PartialEq)]
+struct AddressRef<'a> {
+    street: &'a str,
+    city: &'a str,
+    zip: &'a str,
+}
+
+/// Same layout as `Person`, using fixed capacity containers
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct HeaplessPerson {
+    name: heapless::String<16>,
+    age: u32,
+    phones: heapless::Vec<heapless::String<12>, 2>,
+    address: HeaplessAddress,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct HeaplessAddress {
+    street: heapless::String<16>,
+    city: heapless::String<16>,
+    zip: heapless::String<8>,
+}
+
+fn sample_person() -> Person {
+    Person {
+        name: "Ann".to_string(),
+        age: 30,
+        phones: vec!["555-1234".to_string()],
+        address: Address {
+            street: "Main St".to_string(),
+            city: "Oslo".to_string(),
+            zip: "0150".to_string(),
+        },
+    }
+}
+
+fn sample_map() -> BTreeMap<String, u32> {
+    BTreeMap::from([("temp".to_string(), 21), ("volt".to_string(), 3300)])
+}
+
+fn sample_frame() -> Frame {
+    Frame {
+        id: 0x0102,
+        payload: vec![0xde, 0xad, 0xbe, 0xef, 0x00],
+        checksum: [1, 2, 3, 4],
+    }
+}
+
+const PERSON_BYTES: &[u8] = &[
+    0x03, b'A', b'n', b'n', // name
+    0x1e, // age
+    0x01, 0x08, b'5', b'5', b'5', b'-', b'1', b'2', b'3', b'4', // phones
+    0x07, b'M', b'a', b'i', b'n', b' ', b'S', b't', // address.street
+    0x04, b'O', b's', b'l', b'o', // address.city
+    0x04, b'0', b'1', b'5', b'0', // address.zip
+];
+
+fn test_person_roundtrip() {
+    println!("Testing Person round-trip");
+
+    let person = sample_person();
+    let encoded = postcard::to_allocvec(&person).unwrap();
+    println!("Encoded Person: {} bytes", encoded.len());
+    assert_eq!(encoded, PERSON_BYTES);
+
+    let decoded: Person = postcard::from_bytes(&encoded).unwrap();
+    assert_eq!(decoded, person);
+
+    println!("Person round-trip tests passed");
+}
+
+fn test_enum_roundtrip() {
+    println!("Testing enum round-trip");
+
+    // Variant index as varint, signed integers are zigzag encoded
+    let cases: [(Command, &[u8]); 3] = [
+        (Command::Reset, &[0x00]),
+        (Command::SetLevel(200), &[0x01, 0xc8]),
+        (Command::Move { x: -2, y: 300 }, &[0x02, 0x03, 0xd8, 0x04]),
+    ];
+
+    for (command, expected) in cases {
+        let encoded = postcard::to_allocvec(&command).unwrap();
+        println!("{:?} -> {:02x?}", command, encoded);
+        assert_eq!(encoded, expected);
+
+        let decoded: Command = postcard::from_bytes(&encoded).unwrap();
+        assert_eq!(decoded, command);
+    }
+
+    println!("Enum round-trip tests passed");
+}
+
+fn test_map_roundtrip() {
+    println!("Testing map round-trip");
+
+    let map = sample_map();
+    let encoded = postcard::to_allocvec(&map).unwrap();
+    assert_eq!(
+        encoded,
+        [
+            0x02, 0x04, b't', b'e', b'm', b'p', 0x15, 0x04, b'v', b'o', b'l', b't', 0xe4, 0x19
+        ]
+    );
+
+    let decoded: BTreeMap<String, u32> = postcard::from_bytes(&encoded).unwrap();
+    assert_eq!(decoded, map);
+
+    println!("Map round-trip tests passed");
+}
+
+fn test_bytes_roundtrip() {
+    println!("Testing byte array round-trip");
+
+    // Byte buffers and fixed arrays both end up as raw bytes, only the
+    // buffer carries a length prefix
+    let frame = sample_frame();
+    let encoded = postcard::to_allocvec(&frame).unwrap();
+    assert_eq!(
+        encoded,
+        [
+            0x82, 0x02, 0x05, 0xde, 0xad, 0xbe, 0xef, 0x00, 0x01, 0x02, 0x03, 0x04
+        ]
+    );
+
+    let decoded: Frame = postcard::from_bytes(&encoded).unwrap();
+    assert_eq!(decoded, frame);
+
+    println!("Byte array round-trip tests passed");
+}
+
+fn test_no_alloc() {
+    println!("Testing no-alloc mode");
+
+    let person = HeaplessPerson {
+        name: "Ann".into(),
+        age: 30,
+        phones: heapless::Vec::from_slice(&["555-1234".into()]).unwrap(),
+        address: HeaplessAddress {
+            street: "Main St".into(),
+            city: "Oslo".into(),
+            zip: "0150".into(),
+        },
+    };
+
+    // Encode into a stack buffer, the wire format is the same as with alloc
+    let mut buf = [0u8; 64];
+    let used = postcard::to_slice(&person, &mut buf).unwrap();
+    assert_eq!(used, PERSON_BYTES);
+
+    // Encode into a heapless::Vec
+    let encoded: heapless::Vec<u8, 64> = postcard::to_vec(&person).unwrap();
+    assert_eq!(&encoded[..], PERSON_BYTES);
+
+    // Decode back into fixed capacity containers
+    let decoded: HeaplessPerson = postcard::from_bytes(&encoded).unwrap();
+    assert_eq!(decoded, person);
+
+    // Borrow the strings straight out of the input buffer
+    let borrowed: PersonRef = postcard::from_bytes(PERSON_BYTES).unwrap();
+    assert_eq!(borrowed.name, "Ann");
+    assert_eq!(borrowed.phones[0], "555-1234");
+    assert_eq!(borrowed.address.city, "Oslo");
+
+    // A buffer that is too small is reported instead of overflowing
+    let mut small = [0u8; 8];
+    assert_eq!(
+        postcard::to_slice(&person, &mut small),
+        Err(postcard::Error::SerializeBufferFull)
+    );
+
+    println!("No-alloc mode tests passed");
+}
+
+fn test_cobs_framing() {
+    println!("Testing COBS framing");
+
+    // COBS removes zero bytes so 0x00 can delimit frames on a UART
+    let frame = sample_frame();
+    let mut buf = [0u8; 32];
+    let encoded = postcard::to_slice_cobs(&frame, &mut buf).unwrap();
+    println!("COBS frame: {:02x?}", encoded);
+
+    let (last, body) = encoded.split_last().unwrap();
+    assert_eq!(*last, 0x00);
+    assert!(!body.contains(&0x00));
+
+    let decoded: Frame = postcard::from_bytes_cobs(encoded).unwrap();
+    assert_eq!(decoded, frame);
+
+    println!("COBS framing tests passed");
+}
+
+fn report_sizes() {
+    println!("Encoded sizes:");
+
+    let sizes = [
+        (
+            "person",
+            postcard::to_allocvec(&sample_person()).unwrap().len(),
+        ),
+        ("map", postcard::to_allocvec(&sample_map()).unwrap().len()),
+        (
+            "frame",
+            postcard::to_allocvec(&sample_frame()).unwrap().len(),
+        ),
+    ];
+    for (name, size) in sizes {
+        println!("  {:<8} {:>3} bytes", name, size);
+    }
+}
+
+#[unsafe(no_mangle)]
+pub fn rust_crate_test_postcard_main() {
+    println!("Running postcard tests");
+
+    test_person_roundtrip();
+    test_enum_roundtrip();
+    test_map_roundtrip();
+    test_bytes_roundtrip();
+    test_no_alloc();
+    test_cobs_framing();
+    report_sizes();
+
+    println!("All postcard tests completed");
+}
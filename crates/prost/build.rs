@@ -0,0 +1,20 @@
+// Copyright (c) 2025 Xiaomi Corporation
+// SPDX-License-Identifier: Apache-2.0
+
+const PROTOS: &[&str] = &["proto/addressbook.proto", "proto/telemetry.proto"];
+
+fn main() {
+    for proto in PROTOS {
+        println!("cargo:rerun-if-changed={proto}");
+    }
+
+    // Parse the .proto files with protox, a pure Rust compiler, so the
+    // build does not depend on a protoc binary on the host
+    let descriptors = protox::compile(PROTOS, ["proto"]).expect("failed to parse .proto files");
+
+    prost_build::Config::new()
+        .btree_map(["."])
+        .bytes([".nuttx.telemetry.Device.firmware_hash"])
+        .compile_fds(descriptors)
+        .expect("failed to generate Rust code");
+}
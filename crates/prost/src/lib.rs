@@ -1,29 +1,70 @@
 // Copyright (c) 2025 Xiaomi Corporation
 // SPDX-License-Identifier: Apache-2.0
 
+use bytes::Bytes;
 use prost::Message;
+use std::collections::BTreeMap;
+use std::hint::black_box;
+use std::time::Instant;
 
-#[derive(Clone, PartialEq, Message)]
-pub struct Person {
-    #[prost(string, tag = "1")]
-    pub name: String,
-    #[prost(int32, tag = "2")]
-    pub id: i32,
-    #[prost(string, optional, tag = "3")]
-    pub email: Option<String>,
-    #[prost(string, repeated, tag = "4")]
-    pub phones: Vec<String>,
+/// Generated from proto/addressbook.proto by build.rs
+pub mod addressbook {
+    include!(concat!(env!("OUT_DIR"), "/addressbook.rs"));
 }
 
-#[derive(Clone, PartialEq, Message)]
-pub struct AddressBook {
-    #[prost(message, repeated, tag = "1")]
-    pub people: Vec<Person>,
+/// Generated from proto/telemetry.proto by build.rs
+pub mod telemetry {
+    include!(concat!(env!("OUT_DIR"), "/nuttx.telemetry.rs"));
 }
 
-#[unsafe(no_mangle)]
-pub fn rust_crate_test_prost_main() {
-    println!("Prost (Protocol Buffers) Demo");
+use addressbook::{AddressBook, Person};
+use telemetry::device::{Location, Status};
+use telemetry::{Device, Reading, SensorKind};
+
+/// Reference encoding of proto/device.txtpb, fields in tag order
+const DEVICE_BYTES: &[u8] = &[
+    0x0a, 0x04, b'd', b'e', b'v', b'0', // id
+    0x12, 0x07, 0x0a, 0x03, b'l', b'a', b'b', 0x10, 0x03, // location
+    0x1a, 0x09, 0x0a, 0x02, b'f', b'w', 0x12, 0x03, b'1', b'.', b'2', // labels
+    0x22, 0x08, 0x08, 0x01, 0x10, 0x4f, 0x18, 0xc0, 0x84, 0x3d, // readings[0]
+    0x22, 0x05, 0x08, 0x02, 0x10, 0xc8, 0x33, // readings[1]
+    0x2a, 0x04, 0xde, 0xad, 0xbe, 0xef, // firmware_hash
+    0x32, 0x04, 0x01, 0x02, 0xac, 0x02, // channels, packed
+    0x38, 0x01, // online
+];
+
+/// Number of readings in the decode cost report
+const REPORT_READINGS: usize = 64;
+const REPORT_ITERATIONS: u32 = 1000;
+
+fn sample_device() -> Device {
+    Device {
+        id: "dev0".to_string(),
+        location: Some(Location {
+            site: "lab".to_string(),
+            rack: 3,
+        }),
+        labels: BTreeMap::from([("fw".to_string(), "1.2".to_string())]),
+        readings: vec![
+            Reading {
+                kind: SensorKind::Temperature.into(),
+                value: -40,
+                timestamp_us: 1_000_000,
+            },
+            Reading {
+                kind: SensorKind::Voltage.into(),
+                value: 3300,
+                timestamp_us: 0,
+            },
+        ],
+        firmware_hash: Bytes::from_static(&[0xde, 0xad, 0xbe, 0xef]),
+        channels: vec![1, 2, 300],
+        status: Some(Status::Online(true)),
+    }
+}
+
+fn test_address_book() {
+    println!("Testing AddressBook round-trip");
 
     // Create a sample person
     let person = Person {
@@ -43,33 +84,189 @@ pub fn rust_crate_test_prost_main() {
     println!("Encoded {} bytes: {:?}", encoded.len(), encoded);
 
     // Deserialize from protobuf bytes
-    match AddressBook::decode(&encoded[..]) {
-        Ok(decoded) => {
-            println!("Successfully decoded address book!");
-            if let Some(first_person) = decoded.people.first() {
-                println!("First person: {} (ID: {})", first_person.name, first_person.id);
-                if let Some(email) = &first_person.email {
-                    println!("Email: {}", email);
-                }
-                println!("Phones: {:?}", first_person.phones);
-            }
-        }
-        Err(e) => {
-            println!("Failed to decode: {}", e);
-        }
-    }
+    let decoded = AddressBook::decode(&encoded[..]).unwrap();
+    let first_person = decoded.people.first().unwrap();
+    println!(
+        "First person: {} (ID: {})",
+        first_person.name, first_person.id
+    );
+    assert_eq!(*first_person, person);
 
     // Test round-trip encoding/decoding
-    let re_encoded = match AddressBook::decode(&encoded[..]) {
-        Ok(decoded) => decoded.encode_to_vec(),
-        Err(_) => Vec::new(),
+    assert_eq!(decoded.encode_to_vec(), encoded);
+
+    // proto3 optional fields distinguish unset from empty
+    let anonymous = Person {
+        email: Some(String::new()),
+        ..Default::default()
+    };
+    assert_eq!(anonymous.encode_to_vec(), [0x1a, 0x00]);
+    assert_eq!(Person::default().encode_to_vec(), []);
+
+    println!("AddressBook round-trip tests passed");
+}
+
+fn test_wire_compatibility() {
+    println!("Testing wire compatibility with the reference encoding");
+
+    let device = sample_device();
+    let encoded = device.encode_to_vec();
+    println!("Encoded Device: {:02x?}", encoded);
+    assert_eq!(encoded, DEVICE_BYTES);
+    assert_eq!(device.encoded_len(), DEVICE_BYTES.len());
+
+    let decoded = Device::decode(DEVICE_BYTES).unwrap();
+    assert_eq!(decoded, device);
+    assert_eq!(decoded.readings[0].kind(), SensorKind::Temperature);
+    assert_eq!(decoded.location.as_ref().unwrap().site, "lab");
+
+    // Decoders must also accept unpacked repeated scalars, fields out of
+    // order and fields they do not know about
+    let reordered: &[u8] = &[
+        0x38, 0x01, // online
+        0x30, 0x01, 0x30, 0x02, 0x30, 0xac, 0x02, // channels, unpacked
+        0x78, 0x2a, // field 15, unknown
+        0x0a, 0x04, b'd', b'e', b'v', b'0', // id
+    ];
+    let decoded = Device::decode(reordered).unwrap();
+    assert_eq!(decoded.id, "dev0");
+    assert_eq!(decoded.channels, [1, 2, 300]);
+    assert_eq!(decoded.status, Some(Status::Online(true)));
+
+    println!("Wire compatibility tests passed");
+}
+
+fn test_oneof_and_enums() {
+    println!("Testing oneof and enum fields");
+
+    // Setting another oneof member replaces the previous one
+    let mut device = Device {
+        status: Some(Status::Online(true)),
+        ..Default::default()
     };
+    device.status = Some(Status::Error("overheat".to_string()));
+    assert_eq!(
+        device.encode_to_vec(),
+        [0x42, 0x08, b'o', b'v', b'e', b'r', b'h', b'e', b'a', b't']
+    );
+
+    // When a oneof appears twice on the wire, the last member wins
+    let twice: &[u8] = &[0x42, 0x01, b'x', 0x38, 0x01];
+    let decoded = Device::decode(twice).unwrap();
+    assert_eq!(decoded.status, Some(Status::Online(true)));
+
+    // Unknown enum values are kept as raw integers
+    let reading = Reading::decode(&[0x08, 0x07][..]).unwrap();
+    assert_eq!(reading.kind, 7);
+    assert!(SensorKind::try_from(reading.kind).is_err());
+    assert_eq!(reading.kind(), SensorKind::Unspecified);
+    assert_eq!(reading.encode_to_vec(), [0x08, 0x07]);
+
+    assert_eq!(SensorKind::Voltage.as_str_name(), "SENSOR_KIND_VOLTAGE");
+    assert_eq!(
+        SensorKind::from_str_name("SENSOR_KIND_TEMPERATURE"),
+        Some(SensorKind::Temperature)
+    );
 
-    if encoded == re_encoded {
-        println!("Round-trip encoding/decoding successful!");
-    } else {
-        println!("Round-trip encoding/decoding failed!");
+    println!("Oneof and enum tests passed");
+}
+
+fn test_maps_and_bytes() {
+    println!("Testing map and bytes fields");
+
+    let mut device = sample_device();
+    device.labels.insert("board".to_string(), "sim".to_string());
+    device.labels.insert("arch".to_string(), "arm".to_string());
+
+    // BTreeMap keeps the encoding of maps deterministic
+    let encoded = device.encode_to_vec();
+    assert_eq!(encoded, device.encode_to_vec());
+    let decoded = Device::decode(&encoded[..]).unwrap();
+    let keys: Vec<&str> = decoded.labels.keys().map(String::as_str).collect();
+    assert_eq!(keys, ["arch", "board", "fw"]);
+
+    // Decoding from Bytes hands out slices of the input buffer for
+    // bytes fields instead of copying them
+    let buffer = Bytes::from(encoded);
+    let decoded = Device::decode(buffer.clone()).unwrap();
+    let range = buffer.as_ptr_range();
+    assert!(range.contains(&decoded.firmware_hash.as_ptr()));
+
+    println!("Map and bytes tests passed");
+}
+
+fn test_length_delimited() {
+    println!("Testing length-delimited streams");
+
+    // Several messages in one buffer, each prefixed with its length
+    let mut stream = Vec::new();
+    for value in [-40, 25, 125] {
+        let reading = Reading {
+            kind: SensorKind::Temperature.into(),
+            value,
+            timestamp_us: 0,
+        };
+        reading.encode_length_delimited(&mut stream).unwrap();
+    }
+    assert_eq!(stream[..5], [0x04, 0x08, 0x01, 0x10, 0x4f]);
+
+    let mut buf = &stream[..];
+    let mut values = Vec::new();
+    while !buf.is_empty() {
+        values.push(Reading::decode_length_delimited(&mut buf).unwrap().value);
+    }
+    assert_eq!(values, [-40, 25, 125]);
+
+    // Truncated input is an error, not a partial message
+    assert!(Device::decode(&DEVICE_BYTES[..DEVICE_BYTES.len() - 5]).is_err());
+
+    println!("Length-delimited stream tests passed");
+}
+
+/// Same workload as report_decode_cost() in the flatbuffers and capnp crates
+fn report_decode_cost() {
+    println!("Decode cost, Device with {} readings:", REPORT_READINGS);
+
+    let mut device = sample_device();
+    device.readings = (0..REPORT_READINGS)
+        .map(|i| {
+            let kind = if i % 2 == 0 {
+                SensorKind::Temperature
+            } else {
+                SensorKind::Voltage
+            };
+            Reading {
+                kind: kind.into(),
+                value: i as i32 * 10 - 40,
+                timestamp_us: i as u64 * 1000,
+            }
+        })
+        .collect();
+    let expected: i64 = device.readings.iter().map(|r| r.value as i64).sum();
+    let buf = device.encode_to_vec();
+
+    let start = Instant::now();
+    for _ in 0..REPORT_ITERATIONS {
+        let decoded = Device::decode(black_box(&buf[..])).unwrap();
+        let sum: i64 = decoded.readings.iter().map(|r| r.value as i64).sum();
+        assert_eq!(sum, expected);
     }
+    let decode = start.elapsed() / REPORT_ITERATIONS;
+
+    println!("  encoded size      {:>6} bytes", buf.len());
+    println!("  decode            {:>6} ns", decode.as_nanos());
+}
+
+#[unsafe(no_mangle)]
+pub fn rust_crate_test_prost_main() {
+    println!("Prost (Protocol Buffers) Demo");
+
+    test_address_book();
+    test_wire_compatibility();
+    test_oneof_and_enums();
+    test_maps_and_bytes();
+    test_length_delimited();
+    report_decode_cost();
 
     println!("Prost demo completed successfully!");
 }
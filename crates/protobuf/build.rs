@@ -0,0 +1,12 @@
+// Copyright (c) 2025 Xiaomi Corporation
+// SPDX-License-Identifier: Apache-2.0
+
+fn main() {
+    // The pure Rust parser avoids depending on a protoc binary on the host
+    protobuf_codegen::Codegen::new()
+        .pure()
+        .include("proto")
+        .inputs(["proto/simple.proto", "proto/sensor.proto"])
+        .cargo_out_dir("protos")
+        .run_from_script();
+}
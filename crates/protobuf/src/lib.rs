@@ -1,144 +1,37 @@
 // Copyright (c) 2025 Xiaomi Corporation
 // SPDX-License-Identifier: Apache-2.0
 
-// Note: This example assumes you have a .proto file and generated Rust code.
-// For simplicity, we'll simulate a basic structure manually.
-// In a real scenario, you'd use `protobuf-codegen` or similar build script integration.
-
-// Simulate a simple generated message structure
-mod simple_message {
-    use protobuf::rt::CachedSize; // Correct path for CachedSize
-    use protobuf::SpecialFields;
-    use std::ptr;
-    use std::sync::atomic::{AtomicPtr, Ordering};
-    use std::sync::Once;
-
-    #[derive(PartialEq, Clone, Default, Debug)]
-    pub struct Simple {
-        pub name: ::std::string::String,
-        pub id: i32,
-        pub special_fields: SpecialFields,
-        pub cached_size: CachedSize, // Use the imported CachedSize
-    }
-
-    impl<'a> ::std::default::Default for &'a Simple {
-        fn default() -> &'a Simple {
-            <Simple as ::protobuf::Message>::default_instance()
-        }
-    }
+// Messages generated from proto/*.proto by build.rs
+include!(concat!(env!("OUT_DIR"), "/protos/mod.rs"));
 
-    impl Simple {
-        pub fn new() -> Simple {
-            ::std::default::Default::default()
-        }
-    }
+use protobuf::reflect::{ReflectValueBox, ReflectValueRef, RuntimeFieldType, RuntimeType};
+use protobuf::{
+    Enum, EnumFull, EnumOrUnknown, Message, MessageField, MessageFull, UnknownValueRef,
+};
+use sensor::Sample;
+use sensor::sample::{Location, Trigger, Unit};
+use simple::Simple;
 
-    impl ::protobuf::Message for Simple {
-        const NAME: &'static str = "Simple";
-
-        fn is_initialized(&self) -> bool {
-            true // Simplified
-        }
-
-        fn merge_from(
-            &mut self,
-            is: &mut ::protobuf::CodedInputStream<'_>,
-        ) -> ::protobuf::Result<()> {
-            while let Some(tag) = is.read_raw_tag_or_eof()? {
-                match tag {
-                    10 => {
-                        self.name = is.read_string()?;
-                    }
-                    16 => {
-                        self.id = is.read_int32()?;
-                    }
-                    tag => {
-                        ::protobuf::rt::read_unknown_or_skip_group(
-                            tag,
-                            is,
-                            self.mut_special_fields().mut_unknown_fields(),
-                        )?;
-                    }
-                };
-            }
-            Ok(())
-        }
-
-        fn compute_size(&self) -> u64 {
-            let mut my_size = 0;
-            if !self.name.is_empty() {
-                my_size += ::protobuf::rt::string_size(1, &self.name);
-            }
-            if self.id != 0 {
-                my_size += ::protobuf::rt::int32_size(2, self.id);
-            }
-            my_size += ::protobuf::rt::unknown_fields_size(self.special_fields.unknown_fields());
-            self.cached_size.set(my_size as u32);
-            my_size
-        }
-
-        fn write_to_with_cached_sizes(
-            &self,
-            os: &mut ::protobuf::CodedOutputStream<'_>,
-        ) -> ::protobuf::Result<()> {
-            if !self.name.is_empty() {
-                os.write_string(1, &self.name)?;
-            }
-            if self.id != 0 {
-                os.write_int32(2, self.id)?;
-            }
-            os.write_unknown_fields(self.special_fields().unknown_fields())?;
-            ::std::result::Result::Ok(())
-        }
-
-        fn special_fields(&self) -> &::protobuf::SpecialFields {
-            &self.special_fields
-        }
-
-        fn mut_special_fields(&mut self) -> &mut ::protobuf::SpecialFields {
-            &mut self.special_fields
-        }
-
-        fn new() -> Simple {
-            Simple::new()
-        }
-
-        fn clear(&mut self) {
-            self.name.clear();
-            self.id = 0;
-            self.special_fields.clear();
-        }
-
-        fn default_instance() -> &'static Simple {
-            // Use AtomicPtr and Box::leak for safe static initialization
-            static INSTANCE_PTR: AtomicPtr<Simple> = AtomicPtr::new(ptr::null_mut());
-            static ONCE: Once = Once::new();
-
-            ONCE.call_once(|| {
-                let instance = Simple::new();
-                // Allocate on the heap and leak to get a 'static reference
-                let leaked_ptr = Box::into_raw(Box::new(instance));
-                INSTANCE_PTR.store(leaked_ptr, Ordering::SeqCst);
-            });
-
-            let ptr = INSTANCE_PTR.load(Ordering::SeqCst);
-            // Safety:
-            // - `ptr` is initialized exactly once by `call_once`.
-            // - `ptr` points to a valid `Simple` allocated via `Box::new`.
-            // - The allocation was leaked using `Box::into_raw`, so the pointer
-            //   remains valid for the static lifetime.
-            // - The data pointed to is immutable after initialization.
-            unsafe { &*ptr }
-        }
-    }
+fn sample_message() -> Sample {
+    let mut sample = Sample::new();
+    sample.sensor_name = "temp0".to_string();
+    sample.id = 7;
+    sample.unit = EnumOrUnknown::new(Unit::UNIT_CELSIUS);
+    sample.values = vec![21.5, 22.0, -3.25];
+    sample.timestamp_us = 1_000_000;
+    sample.location = MessageField::some(Location {
+        site: "lab".to_string(),
+        rack: 3,
+        ..Default::default()
+    });
+    sample.tags.insert("board".to_string(), "sim".to_string());
+    sample.raw = vec![0xde, 0xad, 0xbe, 0xef];
+    sample.set_period_ms(500);
+    sample
 }
 
-use protobuf::Message;
-use simple_message::Simple;
-
-#[unsafe(no_mangle)]
-pub extern "C" fn rust_crate_test_protobuf_main() -> i32 {
-    println!("Rust Protobuf Demo Started!");
+fn test_simple_message() -> bool {
+    println!("Testing generated Simple message");
 
     let mut msg = Simple::new();
     msg.name = "Test".to_string();
@@ -153,7 +46,7 @@ pub extern "C" fn rust_crate_test_protobuf_main() -> i32 {
         }
         Err(e) => {
             println!("Serialization failed: {}", e);
-            return 1;
+            return false;
         }
     };
 
@@ -162,15 +55,223 @@ pub extern "C" fn rust_crate_test_protobuf_main() -> i32 {
             println!("Deserialized message: {:?}", parsed_msg);
             if parsed_msg == msg {
                 println!("Serialization/Deserialization successful!");
-                0
+                true
             } else {
                 println!("Deserialized message does not match original!");
-                1
+                false
             }
         }
         Err(e) => {
             println!("Deserialization failed: {}", e);
-            1
+            false
         }
     }
 }
+
+fn test_generated_accessors() {
+    println!("Testing generated accessors");
+
+    let mut sample = sample_message();
+    assert_eq!(sample.unit.enum_value(), Ok(Unit::UNIT_CELSIUS));
+    assert_eq!(sample.location.site, "lab");
+    assert!(sample.has_period_ms());
+    assert_eq!(sample.period_ms(), 500);
+
+    // Setting another oneof member replaces the previous one
+    sample.set_event("threshold".to_string());
+    assert!(!sample.has_period_ms());
+    assert_eq!(
+        sample.trigger,
+        Some(Trigger::Event("threshold".to_string()))
+    );
+
+    let bytes = sample.write_to_bytes().unwrap();
+    let parsed = Sample::parse_from_bytes(&bytes).unwrap();
+    assert_eq!(parsed, sample);
+    assert_eq!(parsed.compute_size() as usize, bytes.len());
+
+    println!("Generated accessor tests passed");
+}
+
+fn test_reflection() {
+    println!("Testing reflection");
+
+    let sample = sample_message();
+    let descriptor = Sample::descriptor();
+    assert_eq!(descriptor.full_name(), "sensor.Sample");
+    assert_eq!(descriptor.file_descriptor().proto().package(), "sensor");
+
+    // Walk every field the way a generic dumper would
+    for field in descriptor.fields() {
+        let value = match field.runtime_field_type() {
+            RuntimeFieldType::Singular(_) => match field.get_singular(&sample) {
+                Some(value) => format!("{}", value),
+                None => "<unset>".to_string(),
+            },
+            RuntimeFieldType::Repeated(_) => {
+                format!("{} items", field.get_repeated(&sample).len())
+            }
+            RuntimeFieldType::Map(_, _) => format!("{} entries", field.get_map(&sample).len()),
+        };
+        println!("  {:>2} {:<12} {}", field.number(), field.name(), value);
+    }
+    assert_eq!(descriptor.fields().count(), 10);
+
+    let oneof = descriptor.oneofs().next().unwrap();
+    assert_eq!(oneof.name(), "trigger");
+    assert_eq!(oneof.fields().count(), 2);
+
+    // Read and write fields by name without the generated accessors
+    let id = descriptor.field_by_name("id").unwrap();
+    assert_eq!(id.get_singular(&sample), Some(ReflectValueRef::I32(7)));
+    assert!(matches!(
+        id.runtime_field_type(),
+        RuntimeFieldType::Singular(RuntimeType::I32)
+    ));
+
+    let mut dynamic = descriptor.new_instance();
+    id.set_singular_field(&mut *dynamic, ReflectValueBox::I32(42));
+    descriptor
+        .field_by_name("sensor_name")
+        .unwrap()
+        .set_singular_field(&mut *dynamic, ReflectValueBox::String("volt1".to_string()));
+    let typed = dynamic.downcast_box::<Sample>().unwrap();
+    assert_eq!(typed.id, 42);
+    assert_eq!(typed.sensor_name, "volt1");
+
+    // Enum descriptors list the values declared in the .proto file
+    let names: Vec<String> = Unit::enum_descriptor()
+        .values()
+        .map(|value| value.name().to_string())
+        .collect();
+    assert_eq!(
+        names,
+        ["UNIT_UNSPECIFIED", "UNIT_CELSIUS", "UNIT_MILLIVOLT"]
+    );
+    assert_eq!(Unit::from_str("UNIT_MILLIVOLT"), Some(Unit::UNIT_MILLIVOLT));
+    assert_eq!(Unit::UNIT_MILLIVOLT.value(), 2);
+
+    println!("Reflection tests passed");
+}
+
+fn test_text_format() {
+    println!("Testing text format");
+
+    let mut sample = Sample::new();
+    sample.sensor_name = "temp0".to_string();
+    sample.id = 7;
+    sample.unit = EnumOrUnknown::new(Unit::UNIT_CELSIUS);
+    sample.values = vec![21.5, -3.25];
+
+    let text = protobuf::text_format::print_to_string(&sample);
+    println!("Text format: {}", text);
+    assert_eq!(
+        text,
+        "sensor_name: \"temp0\" id: 7 unit: UNIT_CELSIUS values: 21.5 values: -3.25"
+    );
+
+    // Parse a hand-written config, including comments and nested messages
+    let input = r#"
+        # sensor configuration
+        sensor_name: "volt1"
+        unit: UNIT_MILLIVOLT
+        location { site: "lab" rack: 3 }
+        tags { key: "board" value: "sim" }
+        event: "boot"
+    "#;
+    let parsed: Sample = protobuf::text_format::parse_from_str(input).unwrap();
+    assert_eq!(parsed.sensor_name, "volt1");
+    assert_eq!(parsed.unit.enum_value(), Ok(Unit::UNIT_MILLIVOLT));
+    assert_eq!(parsed.location.rack, 3);
+    assert_eq!(parsed.tags["board"], "sim");
+    assert_eq!(parsed.event(), "boot");
+
+    let err = protobuf::text_format::parse_from_str::<Sample>("no_such_field: 1").unwrap_err();
+    println!("Error (expected): {}", err);
+
+    println!("Text format tests passed");
+}
+
+fn test_json_mapping() {
+    println!("Testing JSON mapping");
+
+    let sample = sample_message();
+    let json = protobuf_json_mapping::print_to_string(&sample).unwrap();
+    println!("JSON: {}", json);
+
+    // Field names are lowerCamelCase, enums use their names, 64-bit
+    // integers are strings and bytes are base64
+    assert!(json.contains("\"sensorName\""));
+    assert!(json.contains("\"UNIT_CELSIUS\""));
+    assert!(json.contains("\"1000000\""));
+    assert!(json.contains("\"3q2+7w==\""));
+
+    let parsed: Sample = protobuf_json_mapping::parse_from_str(&json).unwrap();
+    assert_eq!(parsed, sample);
+
+    // Both the JSON name and the original field name are accepted
+    let parsed: Sample =
+        protobuf_json_mapping::parse_from_str(r#"{"sensor_name": "a", "periodMs": 10}"#).unwrap();
+    assert_eq!(parsed.sensor_name, "a");
+    assert_eq!(parsed.period_ms(), 10);
+
+    assert!(protobuf_json_mapping::parse_from_str::<Sample>(r#"{"unknown": 1}"#).is_err());
+
+    println!("JSON mapping tests passed");
+}
+
+fn test_unknown_fields() {
+    println!("Testing unknown field preservation");
+
+    // A newer peer sends Simple with two extra fields, 3 (varint) and
+    // 15 (length delimited)
+    let bytes: &[u8] = &[
+        0x0a, 0x04, b'T', b'e', b's', b't', // name
+        0x10, 0x7b, // id
+        0x18, 0x96, 0x01, // field 3: 150
+        0x7a, 0x02, b'h', b'i', // field 15: "hi"
+    ];
+    let msg = Simple::parse_from_bytes(bytes).unwrap();
+    assert_eq!(msg.name, "Test");
+    assert_eq!(msg.id, 123);
+
+    let unknown = msg.special_fields.unknown_fields();
+    assert_eq!(unknown.iter().count(), 2);
+    assert_eq!(unknown.get(3), Some(UnknownValueRef::Varint(150)));
+    assert_eq!(
+        unknown.get(15),
+        Some(UnknownValueRef::LengthDelimited(&b"hi"[..]))
+    );
+
+    // Unknown fields are written back after the known ones, in no
+    // particular order among themselves
+    let written = msg.write_to_bytes().unwrap();
+    assert_eq!(written.len(), bytes.len());
+    assert_eq!(written[..8], bytes[..8]);
+    assert_eq!(Simple::parse_from_bytes(&written).unwrap(), msg);
+
+    // Clearing them gives the plain message again
+    let mut stripped = msg.clone();
+    stripped.special_fields.mut_unknown_fields().clear();
+    assert_eq!(stripped.write_to_bytes().unwrap(), &bytes[..8]);
+
+    println!("Unknown field preservation tests passed");
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn rust_crate_test_protobuf_main() -> i32 {
+    println!("Rust Protobuf Demo Started!");
+
+    if !test_simple_message() {
+        return 1;
+    }
+
+    test_generated_accessors();
+    test_reflection();
+    test_text_format();
+    test_json_mapping();
+    test_unknown_fields();
+
+    println!("Rust Protobuf Demo Completed!");
+    0
+}
@@ -0,0 +1,354 @@
+// Copyright (c) 2025 Xiaomi Corporation
+// SPDX-License-Identifier: Apache-2.0
+
+//! quick-xml example for NuttX
+//!
+//! Parses a partner gateway configuration with the pull reader, from memory
+//! and from a file under /tmp, deserializes it with serde and writes XML
+//! back out.
+
+use quick_xml::events::{BytesText, Event};
+use quick_xml::name::QName;
+use quick_xml::{Reader, Writer};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File};
+use std::io::{BufReader, Cursor};
+
+const CONFIG_PATH: &str = "/tmp/partner_gateway.xml";
+
+const GATEWAY_XML: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<!-- partner gateway configuration -->
+<gateway id="gw-01" version="2">
+  <network dhcp="false">
+    <address>192.168.1.10</address>
+    <netmask>255.255.255.0</netmask>
+  </network>
+  <sensor name="temp0" kind="temperature" interval_ms="500"/>
+  <sensor name="volt1" kind="voltage" interval_ms="1000">
+    <threshold min="3000" max="3600"/>
+  </sensor>
+  <description><![CDATA[Rack <3> in the lab]]></description>
+  <owner>Fish &amp; Chips Ltd</owner>
+</gateway>
+"#;
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[serde(rename = "gateway")]
+struct Gateway {
+    #[serde(rename = "@id")]
+    id: String,
+    #[serde(rename = "@version")]
+    version: u32,
+    network: Network,
+    #[serde(rename = "sensor", default)]
+    sensors: Vec<Sensor>,
+    description: String,
+    owner: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct Network {
+    #[serde(rename = "@dhcp")]
+    dhcp: bool,
+    address: String,
+    netmask: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct Sensor {
+    #[serde(rename = "@name")]
+    name: String,
+    #[serde(rename = "@kind")]
+    kind: SensorKind,
+    #[serde(rename = "@interval_ms")]
+    interval_ms: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    threshold: Option<Threshold>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[serde(rename_all = "lowercase")]
+enum SensorKind {
+    Temperature,
+    Voltage,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct Threshold {
+    #[serde(rename = "@min")]
+    min: i32,
+    #[serde(rename = "@max")]
+    max: i32,
+}
+
+fn test_pull_reader() {
+    println!("Testing pull reader events");
+
+    let mut reader = Reader::from_str(GATEWAY_XML);
+    reader.config_mut().trim_text(true);
+
+    let mut depth = 0;
+    let mut max_depth = 0;
+    let mut elements = Vec::new();
+    let mut sensors = Vec::new();
+    let mut texts = Vec::new();
+    let mut comments = 0;
+
+    loop {
+        match reader.read_event().unwrap() {
+            Event::Decl(decl) => {
+                assert_eq!(decl.version().unwrap().as_ref(), b"1.0");
+            }
+            Event::Comment(_) => comments += 1,
+            Event::Start(start) => {
+                depth += 1;
+                max_depth = max_depth.max(depth);
+                elements.push(String::from_utf8(start.name().as_ref().to_vec()).unwrap());
+            }
+            Event::End(_) => depth -= 1,
+            // Self-closing elements have no matching End event
+            Event::Empty(empty) if empty.name() == QName(b"sensor") => {
+                let name = empty.try_get_attribute("name").unwrap().unwrap();
+                sensors.push(name.unescape_value().unwrap().into_owned());
+            }
+            Event::Text(text) => texts.push(text.unescape().unwrap().into_owned()),
+            Event::CData(cdata) => texts.push(String::from_utf8(cdata.to_vec()).unwrap()),
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+
+    println!("Elements: {:?}", elements);
+    assert_eq!(depth, 0);
+    assert_eq!(max_depth, 3);
+    assert_eq!(comments, 1);
+    assert_eq!(
+        elements,
+        [
+            "gateway",
+            "network",
+            "address",
+            "netmask",
+            "sensor",
+            "description",
+            "owner"
+        ]
+    );
+    assert_eq!(sensors, ["temp0"]);
+    assert_eq!(
+        texts,
+        [
+            "192.168.1.10",
+            "255.255.255.0",
+            "Rack <3> in the lab",
+            "Fish & Chips Ltd"
+        ]
+    );
+
+    println!("Pull reader tests passed");
+}
+
+fn test_attributes() {
+    println!("Testing attribute access");
+
+    let mut reader = Reader::from_str(r#"<sensor name="a&lt;b" kind='voltage' interval_ms="10"/>"#);
+    let Event::Empty(sensor) = reader.read_event().unwrap() else {
+        panic!("expected an empty element");
+    };
+
+    let attributes: Vec<(String, String)> = sensor
+        .attributes()
+        .map(|attr| {
+            let attr = attr.unwrap();
+            (
+                String::from_utf8(attr.key.as_ref().to_vec()).unwrap(),
+                attr.unescape_value().unwrap().into_owned(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        attributes,
+        [
+            ("name".to_string(), "a<b".to_string()),
+            ("kind".to_string(), "voltage".to_string()),
+            ("interval_ms".to_string(), "10".to_string()),
+        ]
+    );
+
+    // Duplicated attributes are reported when iterating
+    let mut reader = Reader::from_str(r#"<sensor name="a" name="b"/>"#);
+    let Event::Empty(sensor) = reader.read_event().unwrap() else {
+        panic!("expected an empty element");
+    };
+    assert!(sensor.attributes().any(|attr| attr.is_err()));
+
+    println!("Attribute access tests passed");
+}
+
+fn test_serde() {
+    println!("Testing serde deserialization");
+
+    let gateway: Gateway = quick_xml::de::from_str(GATEWAY_XML).unwrap();
+    println!("Gateway: {:?}", gateway);
+    assert_eq!(gateway.id, "gw-01");
+    assert_eq!(gateway.version, 2);
+    assert!(!gateway.network.dhcp);
+    assert_eq!(gateway.network.address, "192.168.1.10");
+    assert_eq!(gateway.sensors.len(), 2);
+    assert_eq!(gateway.sensors[0].kind, SensorKind::Temperature);
+    assert_eq!(gateway.sensors[0].threshold, None);
+    assert_eq!(
+        gateway.sensors[1].threshold,
+        Some(Threshold {
+            min: 3000,
+            max: 3600
+        })
+    );
+    assert_eq!(gateway.description, "Rack <3> in the lab");
+    assert_eq!(gateway.owner, "Fish & Chips Ltd");
+
+    // Serializing escapes the text again and round-trips
+    let xml = quick_xml::se::to_string(&gateway).unwrap();
+    println!("Serialized: {}", xml);
+    assert!(xml.starts_with(r#"<gateway id="gw-01" version="2"><network dhcp="false">"#));
+    assert!(xml.contains("<owner>Fish &amp; Chips Ltd</owner>"));
+    let decoded: Gateway = quick_xml::de::from_str(&xml).unwrap();
+    assert_eq!(decoded, gateway);
+
+    // Unknown enum values and missing fields are errors
+    let err =
+        quick_xml::de::from_str::<Sensor>(r#"<sensor name="x" kind="pressure" interval_ms="1"/>"#)
+            .unwrap_err();
+    println!("Error (expected): {}", err);
+    let err =
+        quick_xml::de::from_str::<Sensor>(r#"<sensor name="x" kind="voltage"/>"#).unwrap_err();
+    println!("Error (expected): {}", err);
+    assert!(err.to_string().contains("interval_ms"));
+
+    println!("Serde deserialization tests passed");
+}
+
+fn test_read_from_file() {
+    println!("Testing reading {}", CONFIG_PATH);
+
+    fs::write(CONFIG_PATH, GATEWAY_XML).unwrap();
+
+    // The buffered reader reuses one buffer for all events
+    let mut reader = Reader::from_file(CONFIG_PATH).unwrap();
+    reader.config_mut().trim_text(true);
+    let mut buf = Vec::new();
+    let mut sensors = 0;
+    let mut address = None;
+    loop {
+        match reader.read_event_into(&mut buf).unwrap() {
+            Event::Start(e) | Event::Empty(e) if e.name() == QName(b"sensor") => sensors += 1,
+            Event::Start(e) if e.name() == QName(b"address") => {
+                let mut text = Vec::new();
+                let Event::Text(t) = reader.read_event_into(&mut text).unwrap() else {
+                    panic!("expected the address text");
+                };
+                address = Some(t.unescape().unwrap().into_owned());
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+        buf.clear();
+    }
+    assert_eq!(sensors, 2);
+    assert_eq!(address.as_deref(), Some("192.168.1.10"));
+    assert_eq!(reader.buffer_position() as usize, GATEWAY_XML.len());
+
+    // serde can read from the file directly as well
+    let file = BufReader::new(File::open(CONFIG_PATH).unwrap());
+    let gateway: Gateway = quick_xml::de::from_reader(file).unwrap();
+    assert_eq!(gateway.sensors[1].name, "volt1");
+
+    fs::remove_file(CONFIG_PATH).unwrap();
+
+    println!("Reading from file tests passed");
+}
+
+fn test_errors() {
+    println!("Testing malformed input");
+
+    let input = "<gateway>\n  <network></gateway>";
+    let mut reader = Reader::from_str(input);
+    let err = loop {
+        match reader.read_event() {
+            Ok(Event::Eof) => panic!("malformed input accepted"),
+            Ok(_) => {}
+            Err(err) => break err,
+        }
+    };
+    println!(
+        "Error (expected) at byte {}: {}",
+        reader.error_position(),
+        err
+    );
+    assert!(matches!(
+        err,
+        quick_xml::Error::IllFormed(quick_xml::errors::IllFormedError::MismatchedEndTag { .. })
+    ));
+    assert_eq!(reader.error_position(), 21);
+
+    // Reading the rest of an element skips over everything it contains
+    let mut reader = Reader::from_str(GATEWAY_XML);
+    reader.config_mut().trim_text(true);
+    loop {
+        if let Event::Start(e) = reader.read_event().unwrap()
+            && e.name() == QName(b"network")
+        {
+            reader.read_to_end(e.name()).unwrap();
+            break;
+        }
+    }
+    let Event::Empty(next) = reader.read_event().unwrap() else {
+        panic!("expected the first sensor");
+    };
+    assert_eq!(next.name(), QName(b"sensor"));
+
+    println!("Malformed input tests passed");
+}
+
+fn test_writer() {
+    println!("Testing writer");
+
+    let mut writer = Writer::new_with_indent(Cursor::new(Vec::new()), b' ', 2);
+    writer
+        .create_element("sensor")
+        .with_attribute(("name", "a<b"))
+        .write_inner_content(|writer| {
+            writer
+                .create_element("threshold")
+                .with_attributes([("min", "1"), ("max", "2")])
+                .write_empty()?;
+            writer
+                .create_element("note")
+                .write_text_content(BytesText::new("x & y"))?;
+            Ok(())
+        })
+        .unwrap();
+
+    let xml = String::from_utf8(writer.into_inner().into_inner()).unwrap();
+    println!("Written:\n{}", xml);
+    assert_eq!(
+        xml,
+        "<sensor name=\"a&lt;b\">\n  <threshold min=\"1\" max=\"2\"/>\n  <note>x &amp; y</note>\n</sensor>"
+    );
+
+    println!("Writer tests passed");
+}
+
+#[unsafe(no_mangle)]
+pub fn rust_crate_test_quick_xml_main() {
+    println!("Running quick-xml tests");
+
+    test_pull_reader();
+    test_attributes();
+    test_serde();
+    test_read_from_file();
+    test_errors();
+    test_writer();
+
+    println!("All quick-xml tests completed");
+}
@@ -6,9 +6,66 @@
 //! This module demonstrates the usage of regular expressions in Rust
 //! to parse structured text data. It provides several examples of common regex
 //! patterns and use cases including parsing structured data, name matching,
-//! date validation, and regex sets.
+//! date validation, and regex sets. It also covers named captures, Unicode
+//! classes and case folding, byte-oriented matching on non-UTF-8 data, size
+//! limits suited to boards with little RAM, and times a log-parsing workload
+//! that the regex_lite crate runs as well.
 
-use regex::Regex;
+use regex::bytes::Regex as BytesRegex;
+use regex::{Regex, RegexBuilder, RegexSet};
+use std::time::Instant;
+
+/// Upper bound on the compiled program of one pattern, instead of the
+/// default 10 MiB
+const SIZE_LIMIT: usize = 256 * 1024;
+
+/// Upper bound on the lazy DFA cache of one regex, instead of the default
+/// 2 MiB; past it the cache is cleared or a slower engine takes over
+const DFA_SIZE_LIMIT: usize = 64 * 1024;
+
+const LOG_LINES: usize = 200;
+const TIMING_ITERATIONS: u32 = 4;
+
+/// One line per record, as written by the sensor service. The regex_lite
+/// crate parses the same log with the same pattern.
+const LOG_PATTERN: &str = r"(?m)^(?P<ts>[0-9]{4}-[0-9]{2}-[0-9]{2}T[0-9]{2}:[0-9]{2}:[0-9]{2}\.[0-9]{3}Z) \[(?P<level>[A-Z]+) *\] (?P<module>[a-z0-9_]+): (?P<msg>[^\n]*)$";
+
+/// Compiles `pattern` within the limits above
+fn build(pattern: &str) -> Result<Regex, regex::Error> {
+    RegexBuilder::new(pattern)
+        .size_limit(SIZE_LIMIT)
+        .dfa_size_limit(DFA_SIZE_LIMIT)
+        .build()
+}
+
+/// A log of `lines` records from a few modules, with a warning every
+/// seventh line and an error every thirtieth
+fn sensor_log(lines: usize) -> String {
+    (0..lines)
+        .map(|i| {
+            let (level, module, msg) = if i % 30 == 29 {
+                ("ERROR", "i2c_bus", format!("bus {} timeout", i / 30 % 3))
+            } else if i % 7 == 6 {
+                ("WARN", "sensor", format!("ch{} adc=1023 over range", i % 4))
+            } else {
+                (
+                    "INFO",
+                    "sensor",
+                    format!("ch{} adc={}", i % 4, (i * 37) % 1024),
+                )
+            };
+            format!(
+                "2025-01-01T00:{:02}:{:02}.{:03}Z [{:<5}] {}: {}\n",
+                i / 240,
+                i / 4 % 60,
+                i % 4 * 250,
+                level,
+                module,
+                msg
+            )
+        })
+        .collect()
+}
 
 /// Demonstrates basic regular expression functionality.
 ///
@@ -109,6 +166,247 @@ fn test_url_validation() {
     }
 }
 
+/// Extracts fields from log records with named capture groups.
+///
+/// # Example Output
+/// ```text
+/// 2025-01-01T00:00:07.250Z ERROR i2c_bus: bus 0 timeout
+/// ```
+///
+/// # Implementation Details
+/// - Looks groups up by name rather than by position
+/// - Iterates over every record of a multi-line log with `captures_iter`
+/// - Leaves optional groups that did not take part as `None`
+fn test_named_captures() {
+    println!("\nTesting named captures:");
+
+    let re = build(LOG_PATTERN).unwrap();
+    let names: Vec<_> = re.capture_names().flatten().collect();
+    assert_eq!(names, ["ts", "level", "module", "msg"]);
+
+    let log = sensor_log(LOG_LINES);
+    let mut records = 0;
+    let mut errors = Vec::new();
+    for caps in re.captures_iter(&log) {
+        records += 1;
+        if &caps["level"] == "ERROR" {
+            errors.push(format!(
+                "{} {} {}: {}",
+                &caps["ts"], &caps["level"], &caps["module"], &caps["msg"]
+            ));
+        }
+    }
+    assert_eq!(records, LOG_LINES);
+    assert_eq!(errors.len(), LOG_LINES / 30);
+    assert_eq!(
+        errors[0],
+        "2025-01-01T00:00:07.250Z ERROR i2c_bus: bus 0 timeout"
+    );
+    for error in &errors {
+        println!("{}", error);
+    }
+
+    // An optional group that did not match is None, not an empty string
+    let re = build(r"ch(?P<channel>\d)(?: adc=(?P<adc>\d+))?").unwrap();
+    let caps = re.captures("ch3").unwrap();
+    assert_eq!(caps.name("channel").unwrap().as_str(), "3");
+    assert!(caps.name("adc").is_none());
+}
+
+/// Sorts log records into categories with a single pass per record.
+///
+/// # Example Output
+/// ```text
+/// [ERROR] i2c_bus: bus 0 timeout -> [1, 2]
+/// ```
+///
+/// # Implementation Details
+/// - Matches all patterns of a `RegexSet` at once
+/// - Reports every pattern that matched, not just the first
+fn test_regex_set() {
+    println!("\nTesting RegexSet:");
+
+    let set = RegexSet::new([r"\[WARN *\]", r"\[ERROR *\]", r"\bi2c_\w+:", r"adc=1023\b"]).unwrap();
+    assert_eq!(set.len(), 4);
+
+    let log = sensor_log(LOG_LINES);
+    let mut counts = [0; 4];
+    for line in log.lines() {
+        for i in set.matches(line).iter() {
+            counts[i] += 1;
+        }
+    }
+    println!("Matches per pattern: {:?}", counts);
+    assert_eq!(counts[1], counts[2]);
+    assert!(counts[3] >= counts[0]);
+
+    let line = log.lines().nth(29).unwrap();
+    let matched: Vec<_> = set.matches(line).into_iter().collect();
+    println!("{} -> {:?}", &line[25..], matched);
+    assert_eq!(matched, [1, 2]);
+    assert!(!set.is_match("2025-01-01T00:00:00.000Z [INFO ] sensor: ch0 adc=0"));
+}
+
+/// Searches binary data that is not valid UTF-8.
+///
+/// # Example Output
+/// ```text
+/// Frame at 4: id=42 len=3
+/// ```
+///
+/// # Implementation Details
+/// - Uses `regex::bytes::Regex`, which works on `&[u8]`
+/// - Turns off Unicode with `(?-u)` to match arbitrary bytes
+fn test_bytes_regex() {
+    println!("\nTesting bytes::Regex:");
+
+    // Frames start with 0xa5 0x5a, followed by an ASCII id and a length
+    // byte, between raw samples that are not UTF-8
+    let data: &[u8] = b"\xff\xfe\x00\xc0\xa5\x5aid=42\x03\x01\x02\x03\xa5\x5aid=7\x00\xff";
+
+    let re = BytesRegex::new(r"(?-u)\xa5\x5aid=(?P<id>[0-9]+)(?P<len>.)").unwrap();
+    let frames: Vec<_> = re
+        .captures_iter(data)
+        .map(|caps| {
+            let start = caps.get(0).unwrap().start();
+            let id = std::str::from_utf8(&caps["id"]).unwrap().to_string();
+            let len = caps["len"][0];
+            println!("Frame at {}: id={} len={}", start, id, len);
+            (start, id, len)
+        })
+        .collect();
+    assert_eq!(frames, [(4, "42".to_string(), 3), (15, "7".to_string(), 0)]);
+
+    // With Unicode on, `.` only matches whole UTF-8 characters, so it
+    // doesn't match the 0xfe after the leading 0xff. Without it, `.` matches
+    // any byte but `\n`.
+    let re = BytesRegex::new(r"(?-u:\xff).").unwrap();
+    assert!(!re.is_match(data));
+    let re = BytesRegex::new(r"(?-u:\xff.)").unwrap();
+    assert_eq!(re.find(data).unwrap().as_bytes(), b"\xff\xfe");
+}
+
+/// Matches Unicode classes and case-insensitive text.
+///
+/// # Example Output
+/// ```text
+/// Greek runs in 'Δ-sensor: ωmega, αλφα, 温度 beta': ["Δ", "ω", "αλφα"]
+/// ```
+///
+/// # Implementation Details
+/// - Uses script classes such as `\p{Greek}` and `\p{Han}`
+/// - Shows that `\w` and `\d` are Unicode aware unless `(?-u)` is set
+/// - Applies simple case folding, which maps one character to one character
+fn test_unicode() {
+    println!("\nTesting Unicode classes and case folding:");
+
+    let text = "Δ-sensor: ωmega, αλφα, 温度 beta";
+    let greek = build(r"\p{Greek}+").unwrap();
+    let words: Vec<_> = greek.find_iter(text).map(|m| m.as_str()).collect();
+    println!("Greek runs in '{}': {:?}", text, words);
+    assert_eq!(words, ["Δ", "ω", "αλφα"]);
+    assert_eq!(
+        build(r"\p{Han}+").unwrap().find(text).unwrap().as_str(),
+        "温度"
+    );
+
+    // \w and \d cover every script unless Unicode is turned off
+    assert!(build(r"^\w+$").unwrap().is_match("température"));
+    assert!(!build(r"^(?-u:\w)+$").unwrap().is_match("température"));
+    assert!(build(r"^\d$").unwrap().is_match("٣"));
+    assert!(!build(r"^[0-9]$").unwrap().is_match("٣"));
+
+    // Folding covers all cases of a letter, final sigma included
+    let sigma = build(r"(?i)σ").unwrap();
+    let forms: Vec<_> = sigma.find_iter("Σ σ ς").map(|m| m.as_str()).collect();
+    assert_eq!(forms, ["Σ", "σ", "ς"]);
+    assert!(build(r"(?i)^ΔΙΑΓΝΩΣΗ$").unwrap().is_match("διαγνωση"));
+
+    // but not full folding: ß does not match "SS"
+    assert!(build(r"(?i)^straße$").unwrap().is_match("STRAẞE"));
+    assert!(!build(r"(?i)^straße$").unwrap().is_match("STRASSE"));
+}
+
+/// Keeps compiled patterns within a RAM budget.
+///
+/// # Example Output
+/// ```text
+/// \w{50}: Compiled regex exceeds size limit of 262144 bytes.
+/// ```
+///
+/// # Implementation Details
+/// - Rejects patterns whose program exceeds `SIZE_LIMIT`
+/// - Shows that the same pattern fits once Unicode is turned off
+/// - Still matches correctly with a tiny lazy DFA cache
+fn test_size_limits() {
+    println!("\nTesting size limits:");
+
+    // A Unicode \w repeated 50 times compiles to more than 1 MiB
+    let err = build(r"\w{50}").unwrap_err();
+    println!("\\w{{50}}: {}", err);
+    assert!(matches!(err, regex::Error::CompiledTooBig(SIZE_LIMIT)));
+    assert!(build(r"(?-u:\w){50}").is_ok());
+
+    // A cache too small for the lazy DFA slows matching down, but does not
+    // change the result
+    let log = sensor_log(LOG_LINES);
+    let small = RegexBuilder::new(LOG_PATTERN)
+        .size_limit(SIZE_LIMIT)
+        .dfa_size_limit(1024)
+        .build()
+        .unwrap();
+    assert_eq!(small.find_iter(&log).count(), LOG_LINES);
+}
+
+/// Times a log-parsing workload, compare with the regex_lite crate.
+///
+/// # Example Output
+/// ```text
+/// Parsing 200 log records, 4 iterations:
+///   regex            5080 us compile     4565 us parse
+/// ```
+///
+/// # Implementation Details
+/// - Compiles `LOG_PATTERN` and collects the fields of every record
+fn report_log_parsing() {
+    println!(
+        "\nParsing {} log records, {} iterations:",
+        LOG_LINES, TIMING_ITERATIONS
+    );
+
+    let log = sensor_log(LOG_LINES);
+
+    let start = Instant::now();
+    for _ in 0..TIMING_ITERATIONS {
+        std::hint::black_box(build(LOG_PATTERN).unwrap());
+    }
+    let regex_compile = start.elapsed() / TIMING_ITERATIONS;
+
+    let re = build(LOG_PATTERN).unwrap();
+    let parse = || -> Vec<(&str, &str, &str)> {
+        re.captures_iter(&log)
+            .map(|caps| {
+                let field = |name| caps.name(name).unwrap().as_str();
+                (field("ts"), field("level"), field("module"))
+            })
+            .collect()
+    };
+    let start = Instant::now();
+    for _ in 0..TIMING_ITERATIONS {
+        std::hint::black_box(parse());
+    }
+    let regex_parse = start.elapsed() / TIMING_ITERATIONS;
+
+    assert_eq!(parse().len(), LOG_LINES);
+
+    println!(
+        "  {:<12} {:>8} us compile {:>8} us parse",
+        "regex",
+        regex_compile.as_micros(),
+        regex_parse.as_micros()
+    );
+}
+
 /// Main entry point for the regex example
 ///
 /// This function is marked as no_mangle to ensure it's callable from C code,
@@ -120,6 +418,12 @@ pub fn rust_crate_test_regex_main() {
     test_basic_patterns();
     test_text_replacement();
     test_url_validation();
+    test_named_captures();
+    test_regex_set();
+    test_bytes_regex();
+    test_unicode();
+    test_size_limits();
+    report_log_parsing();
 
     println!("\nAll regex tests completed successfully!");
 }
@@ -0,0 +1,88 @@
+// Copyright (c) 2025 Xiaomi Corporation
+// SPDX-License-Identifier: Apache-2.0
+
+use regex_automata::MatchKind;
+use regex_automata::dfa::{StartKind, dense};
+use regex_automata::nfa::thompson::{self, WhichCaptures};
+use regex_syntax::ParserBuilder;
+use std::env;
+use std::fs;
+use std::path::Path;
+
+#[path = "src/patterns.rs"]
+mod patterns;
+
+const PATTERNS: &[(&str, &str)] = &[
+    ("timestamp", patterns::TIMESTAMP),
+    ("reading", patterns::READING),
+    ("ipv4", patterns::IPV4),
+];
+
+/// Builds the forward and reverse DFAs of `pattern` as dfa::regex::Builder
+/// does, and writes both dense and sparse, little endian, to `out_dir`
+fn write_dfas(out_dir: &Path, name: &str, pattern: &str) {
+    let hir = ParserBuilder::new()
+        .unicode(false)
+        .build()
+        .parse(pattern)
+        .unwrap_or_else(|err| panic!("failed to parse {name}: {err}"));
+
+    // A DFA only reports where matches are, so the NFA needs no capture
+    // states
+    let forward_nfa = thompson::Compiler::new()
+        .configure(thompson::Config::new().which_captures(WhichCaptures::None))
+        .build_from_hir(&hir)
+        .unwrap();
+
+    // Minimizing takes time, but only on the host, and shrinks the tables
+    // that end up in flash
+    let forward = dense::Builder::new()
+        .configure(dense::Config::new().minimize(true))
+        .build_from_nfa(&forward_nfa)
+        .unwrap();
+
+    // The reverse DFA finds where a match starts, scanning back from its end
+    let reverse_nfa = thompson::Compiler::new()
+        .configure(
+            thompson::Config::new()
+                .which_captures(WhichCaptures::None)
+                .reverse(true),
+        )
+        .build_from_hir(&hir)
+        .unwrap();
+    let reverse = dense::Builder::new()
+        .configure(
+            dense::Config::new()
+                .minimize(true)
+                .specialize_start_states(false)
+                .start_kind(StartKind::Anchored)
+                .match_kind(MatchKind::All),
+        )
+        .build_from_nfa(&reverse_nfa)
+        .unwrap();
+
+    for (direction, dfa) in [("fwd", forward), ("rev", reverse)] {
+        let (bytes, pad) = dfa.to_bytes_little_endian();
+        fs::write(
+            out_dir.join(format!("{name}.{direction}.dense")),
+            &bytes[pad..],
+        )
+        .unwrap();
+
+        let sparse = dfa.to_sparse().unwrap();
+        fs::write(
+            out_dir.join(format!("{name}.{direction}.sparse")),
+            sparse.to_bytes_little_endian(),
+        )
+        .unwrap();
+    }
+}
+
+fn main() {
+    println!("cargo:rerun-if-changed=src/patterns.rs");
+
+    let out_dir = env::var("OUT_DIR").unwrap();
+    for (name, pattern) in PATTERNS {
+        write_dfas(Path::new(&out_dir), name, pattern);
+    }
+}
@@ -0,0 +1,370 @@
+// Copyright (c) 2025 Xiaomi Corporation
+// SPDX-License-Identifier: Apache-2.0
+
+//! regex-automata example for NuttX
+//!
+//! Loads DFAs that build.rs compiled on the host with regex-syntax and
+//! regex-automata and serialized into the image, so the board never runs
+//! the regex compiler. `from_bytes` validates the tables where they are in
+//! flash instead of copying them, and neither loading a dense DFA nor any
+//! search allocates. Every match is checked against the regex crate
+//! compiling the same patterns at run time.
+
+use regex_automata::dfa::regex::Regex as DfaRegex;
+use regex_automata::dfa::{Automaton, dense, sparse};
+use regex_automata::util::wire::AlignAs;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+mod patterns;
+
+#[cfg(target_endian = "big")]
+compile_error!("build.rs serializes the DFAs little endian");
+
+const LOG_LINES: usize = 200;
+const TIMING_ITERATIONS: u32 = 4;
+
+/// Includes a DFA written by build.rs, aligned to 4 bytes as the dense
+/// format requires
+macro_rules! include_dfa {
+    ($file:literal) => {{
+        static ALIGNED: &AlignAs<[u8], u32> = &AlignAs {
+            _align: [],
+            bytes: *include_bytes!(concat!(env!("OUT_DIR"), "/", $file)),
+        };
+        &ALIGNED.bytes
+    }};
+}
+
+struct Precompiled {
+    name: &'static str,
+    pattern: &'static str,
+    dense: [&'static [u8]; 2],
+    sparse: [&'static [u8]; 2],
+}
+
+/// Forward and reverse DFA of each pattern
+static PRECOMPILED: &[Precompiled] = &[
+    Precompiled {
+        name: "timestamp",
+        pattern: patterns::TIMESTAMP,
+        dense: [
+            include_dfa!("timestamp.fwd.dense"),
+            include_dfa!("timestamp.rev.dense"),
+        ],
+        sparse: [
+            include_dfa!("timestamp.fwd.sparse"),
+            include_dfa!("timestamp.rev.sparse"),
+        ],
+    },
+    Precompiled {
+        name: "reading",
+        pattern: patterns::READING,
+        dense: [
+            include_dfa!("reading.fwd.dense"),
+            include_dfa!("reading.rev.dense"),
+        ],
+        sparse: [
+            include_dfa!("reading.fwd.sparse"),
+            include_dfa!("reading.rev.sparse"),
+        ],
+    },
+    Precompiled {
+        name: "ipv4",
+        pattern: patterns::IPV4,
+        dense: [
+            include_dfa!("ipv4.fwd.dense"),
+            include_dfa!("ipv4.rev.dense"),
+        ],
+        sparse: [
+            include_dfa!("ipv4.fwd.sparse"),
+            include_dfa!("ipv4.rev.sparse"),
+        ],
+    },
+];
+
+static HEAP_IN_USE: AtomicUsize = AtomicUsize::new(0);
+static HEAP_PEAK: AtomicUsize = AtomicUsize::new(0);
+
+/// The system allocator, keeping count of the bytes in use and their peak
+struct CountingAllocator;
+
+fn record_alloc(size: usize) {
+    let in_use = HEAP_IN_USE.fetch_add(size, Ordering::Relaxed) + size;
+    HEAP_PEAK.fetch_max(in_use, Ordering::Relaxed);
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc(layout) };
+        if !ptr.is_null() {
+            record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc_zeroed(layout) };
+        if !ptr.is_null() {
+            record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) };
+        HEAP_IN_USE.fetch_sub(layout.size(), Ordering::Relaxed);
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = unsafe { System.realloc(ptr, layout, new_size) };
+        if !new_ptr.is_null() {
+            HEAP_IN_USE.fetch_sub(layout.size(), Ordering::Relaxed);
+            record_alloc(new_size);
+        }
+        new_ptr
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// Restarts the peak from the bytes in use now, and returns them
+fn reset_heap_peak() -> usize {
+    let in_use = HEAP_IN_USE.load(Ordering::Relaxed);
+    HEAP_PEAK.store(in_use, Ordering::Relaxed);
+    in_use
+}
+
+/// Log lines mixing timestamps, readings and addresses, some of them
+/// almost but not quite matching
+fn sensor_log(lines: usize) -> String {
+    (0..lines)
+        .map(|i| {
+            format!(
+                "2025-01-01T00:{:02}:{:02}.{:03}Z node {}.168.{}.{} temp={}.{} humidity={} vbat{}=3.3\n",
+                i / 240,
+                i / 4 % 60,
+                i % 4 * 250,
+                [10, 192, 256][i % 3],
+                i % 7,
+                i * 37 % 300,
+                20 + i % 5,
+                i % 10,
+                40 + i % 20,
+                i % 2
+            )
+        })
+        .collect()
+}
+
+fn load_dense(dfas: [&'static [u8]; 2]) -> DfaRegex<dense::DFA<&'static [u32]>> {
+    let (forward, _) = dense::DFA::from_bytes(dfas[0]).unwrap();
+    let (reverse, _) = dense::DFA::from_bytes(dfas[1]).unwrap();
+    DfaRegex::builder().build_from_dfas(forward, reverse)
+}
+
+fn load_sparse(dfas: [&'static [u8]; 2]) -> DfaRegex<sparse::DFA<&'static [u8]>> {
+    let (forward, _) = sparse::DFA::from_bytes(dfas[0]).unwrap();
+    let (reverse, _) = sparse::DFA::from_bytes(dfas[1]).unwrap();
+    DfaRegex::builder().build_from_dfas(forward, reverse)
+}
+
+/// The same pattern compiled by the regex crate, Unicode off as in build.rs
+fn runtime_regex(pattern: &str) -> regex::Regex {
+    regex::RegexBuilder::new(pattern)
+        .unicode(false)
+        .build()
+        .unwrap()
+}
+
+fn dfa_matches<A: Automaton>(re: &DfaRegex<A>, haystack: &str) -> Vec<(usize, usize)> {
+    re.find_iter(haystack)
+        .map(|m| (m.start(), m.end()))
+        .collect()
+}
+
+fn regex_matches(re: &regex::Regex, haystack: &str) -> Vec<(usize, usize)> {
+    re.find_iter(haystack)
+        .map(|m| (m.start(), m.end()))
+        .collect()
+}
+
+fn test_deserialize() {
+    println!("Testing DFA deserialization");
+
+    for dfa in PRECOMPILED {
+        let re = load_dense(dfa.dense);
+        let forward = re.forward();
+        println!(
+            "  {:<10} {:>3} byte classes, stride {}",
+            dfa.name,
+            forward.alphabet_len(),
+            forward.stride()
+        );
+        assert_eq!(forward.pattern_len(), 1);
+        assert!(!forward.has_empty());
+        load_sparse(dfa.sparse);
+    }
+
+    // from_bytes checks the tables rather than trusting them, so damaged or
+    // misplaced data is an error instead of undefined behavior
+    let bytes = PRECOMPILED[0].dense[0];
+    let err = dense::DFA::from_bytes(&bytes[..bytes.len() - 4]).unwrap_err();
+    println!("  {:<20} {}", "truncated", err);
+
+    let err = dense::DFA::from_bytes(&bytes[1..]).unwrap_err();
+    println!("  {:<20} {}", "misaligned", err);
+
+    let err = dense::DFA::from_bytes(PRECOMPILED[0].sparse[0]).unwrap_err();
+    println!("  {:<20} {}", "sparse as dense", err);
+
+    println!("DFA deserialization tests passed");
+}
+
+fn test_same_matches() {
+    println!("Testing matches against the regex crate");
+
+    let log = sensor_log(LOG_LINES);
+    for dfa in PRECOMPILED {
+        let expected = regex_matches(&runtime_regex(dfa.pattern), &log);
+        assert_eq!(dfa_matches(&load_dense(dfa.dense), &log), expected);
+        assert_eq!(dfa_matches(&load_sparse(dfa.sparse), &log), expected);
+        println!("  {:<10} {:>4} matches", dfa.name, expected.len());
+        assert!(!expected.is_empty());
+    }
+
+    // Near misses from the log, matched or rejected the same way
+    let ipv4 = load_dense(PRECOMPILED[2].dense);
+    let regex = runtime_regex(patterns::IPV4);
+    for text in ["10.0.0.1", "256.168.1.2", "1.2.3", "01.2.3.4", "a10.0.0.1b"] {
+        let found = ipv4.find(text).map(|m| &text[m.range()]);
+        assert_eq!(found, regex.find(text).map(|m| m.as_str()));
+        println!("  ipv4 in {:<12} {:?}", text, found);
+    }
+
+    println!("Matches against the regex crate passed");
+}
+
+fn test_no_allocation() {
+    println!("Testing allocation-free loading and search");
+
+    let log = sensor_log(LOG_LINES);
+    for dfa in PRECOMPILED {
+        let heap_base = reset_heap_peak();
+        let dense = load_dense(dfa.dense);
+        let count = dense.find_iter(&log).count();
+        assert_eq!(HEAP_PEAK.load(Ordering::Relaxed), heap_base);
+
+        // With the alloc feature on, which the regex crate turns on, sparse
+        // validation tracks the states it has seen in a set it frees again
+        let heap_base = reset_heap_peak();
+        let sparse = load_sparse(dfa.sparse);
+        let validation = HEAP_PEAK.load(Ordering::Relaxed) - heap_base;
+        assert_eq!(HEAP_IN_USE.load(Ordering::Relaxed), heap_base);
+
+        let heap_base = reset_heap_peak();
+        assert_eq!(sparse.find_iter(&log).count(), count);
+        assert_eq!(HEAP_PEAK.load(Ordering::Relaxed), heap_base);
+
+        println!(
+            "  {:<10} {:>4} matches, sparse validation {} bytes heap",
+            dfa.name, count, validation
+        );
+    }
+
+    println!("Allocation-free loading and search passed");
+}
+
+fn average(iterations: u32, mut f: impl FnMut()) -> Duration {
+    let start = Instant::now();
+    for _ in 0..iterations {
+        f();
+    }
+    start.elapsed() / iterations
+}
+
+fn report_footprint() {
+    println!("Flash and heap per pattern, forward + reverse:");
+    println!(
+        "  {:<10} {:>8} {:>8} {:>12}",
+        "pattern", "dense", "sparse", "regex heap"
+    );
+
+    for dfa in PRECOMPILED {
+        let heap_base = reset_heap_peak();
+        let re = runtime_regex(dfa.pattern);
+        re.is_match("");
+        let heap = HEAP_PEAK.load(Ordering::Relaxed) - heap_base;
+        println!(
+            "  {:<10} {:>8} {:>8} {:>12}",
+            dfa.name,
+            dfa.dense[0].len() + dfa.dense[1].len(),
+            dfa.sparse[0].len() + dfa.sparse[1].len(),
+            heap
+        );
+    }
+}
+
+fn report_timing() {
+    println!(
+        "Timing over {} log lines, {} iterations, in us:",
+        LOG_LINES, TIMING_ITERATIONS
+    );
+    println!(
+        "  {:<10} {:>9} {:>9} {:>9} {:>9} {:>9} {:>9}",
+        "pattern", "dense ld", "dense", "sparse ld", "sparse", "compile", "regex"
+    );
+
+    let log = sensor_log(LOG_LINES);
+    for dfa in PRECOMPILED {
+        let load_dense_time = average(TIMING_ITERATIONS, || {
+            std::hint::black_box(load_dense(dfa.dense));
+        });
+        let dense = load_dense(dfa.dense);
+        let dense_time = average(TIMING_ITERATIONS, || {
+            std::hint::black_box(dense.find_iter(&log).count());
+        });
+
+        let load_sparse_time = average(TIMING_ITERATIONS, || {
+            std::hint::black_box(load_sparse(dfa.sparse));
+        });
+        let sparse = load_sparse(dfa.sparse);
+        let sparse_time = average(TIMING_ITERATIONS, || {
+            std::hint::black_box(sparse.find_iter(&log).count());
+        });
+
+        let compile_time = average(TIMING_ITERATIONS, || {
+            std::hint::black_box(runtime_regex(dfa.pattern));
+        });
+        let regex = runtime_regex(dfa.pattern);
+        let regex_time = average(TIMING_ITERATIONS, || {
+            std::hint::black_box(regex.find_iter(&log).count());
+        });
+
+        println!(
+            "  {:<10} {:>9} {:>9} {:>9} {:>9} {:>9} {:>9}",
+            dfa.name,
+            load_dense_time.as_micros(),
+            dense_time.as_micros(),
+            load_sparse_time.as_micros(),
+            sparse_time.as_micros(),
+            compile_time.as_micros(),
+            regex_time.as_micros()
+        );
+    }
+}
+
+#[unsafe(no_mangle)]
+pub fn rust_crate_test_regex_automata_main() {
+    println!("Running regex-automata tests");
+
+    test_deserialize();
+    test_same_matches();
+    test_no_allocation();
+    report_footprint();
+    report_timing();
+
+    println!("All regex-automata tests completed");
+}
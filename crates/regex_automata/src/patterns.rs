@@ -0,0 +1,15 @@
+// Copyright (c) 2025 Xiaomi Corporation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Patterns compiled to DFAs by build.rs, and at run time by the regex crate
+//! for comparison. Both compile them with Unicode off: Unicode classes make
+//! DFAs far larger, and DFAs only support ASCII word boundaries.
+
+/// An ISO 8601 UTC timestamp with milliseconds
+pub const TIMESTAMP: &str = r"[0-9]{4}-[0-9]{2}-[0-9]{2}T[0-9]{2}:[0-9]{2}:[0-9]{2}\.[0-9]{3}Z";
+
+/// A sensor reading like `temp=21.5`
+pub const READING: &str = r"\b(?:temp|humidity|pressure|voltage)=-?[0-9]+(?:\.[0-9]+)?\b";
+
+/// A dotted IPv4 address, each octet at most 255
+pub const IPV4: &str = r"\b(?:(?:25[0-5]|2[0-4][0-9]|1[0-9][0-9]|[1-9]?[0-9])\.){3}(?:25[0-5]|2[0-4][0-9]|1[0-9][0-9]|[1-9]?[0-9])\b";
@@ -5,11 +5,49 @@
 //!
 //! This module demonstrates the usage of lightweight regular expressions in Rust
 //! for common pattern matching tasks. It provides examples of email validation,
-//! phone number formatting, capture groups, and text replacement.
+//! phone number formatting, capture groups, and text replacement. It also
+//! times the log-parsing workload of the regex crate, for a side by side
+//! comparison.
 
 extern crate regex_lite;
 
 use regex_lite::Regex;
+use std::time::Instant;
+
+const LOG_LINES: usize = 200;
+const TIMING_ITERATIONS: u32 = 4;
+
+/// Same pattern as `LOG_PATTERN` in the regex crate
+const LOG_PATTERN: &str = r"(?m)^(?P<ts>[0-9]{4}-[0-9]{2}-[0-9]{2}T[0-9]{2}:[0-9]{2}:[0-9]{2}\.[0-9]{3}Z) \[(?P<level>[A-Z]+) *\] (?P<module>[a-z0-9_]+): (?P<msg>[^\n]*)$";
+
+/// Builds the same log as `sensor_log()` in the regex crate, so that both
+/// crates time identical input
+fn sensor_log(lines: usize) -> String {
+    (0..lines)
+        .map(|i| {
+            let (level, module, msg) = if i % 30 == 29 {
+                ("ERROR", "i2c_bus", format!("bus {} timeout", i / 30 % 3))
+            } else if i % 7 == 6 {
+                ("WARN", "sensor", format!("ch{} adc=1023 over range", i % 4))
+            } else {
+                (
+                    "INFO",
+                    "sensor",
+                    format!("ch{} adc={}", i % 4, (i * 37) % 1024),
+                )
+            };
+            format!(
+                "2025-01-01T00:{:02}:{:02}.{:03}Z [{:<5}] {}: {}\n",
+                i / 240,
+                i / 4 % 60,
+                i % 4 * 250,
+                level,
+                module,
+                msg
+            )
+        })
+        .collect()
+}
 
 /// Demonstrates basic regular expression functionality.
 ///
@@ -110,6 +148,81 @@ fn test_url_validation() {
     }
 }
 
+/// Times the log-parsing workload of the regex crate.
+///
+/// # Example Output
+/// ```text
+/// Parsing 200 log records, 4 iterations:
+///   regex-lite        121 us compile    11961 us parse
+/// ```
+///
+/// # Implementation Details
+/// - Compiles the same pattern on the same log as the regex crate
+/// - Prints in the same format, so the two reports line up
+fn report_log_parsing() {
+    println!(
+        "\nParsing {} log records, {} iterations:",
+        LOG_LINES, TIMING_ITERATIONS
+    );
+
+    let log = sensor_log(LOG_LINES);
+
+    let start = Instant::now();
+    for _ in 0..TIMING_ITERATIONS {
+        std::hint::black_box(Regex::new(LOG_PATTERN).unwrap());
+    }
+    let compile = start.elapsed() / TIMING_ITERATIONS;
+
+    let re = Regex::new(LOG_PATTERN).unwrap();
+    let parse = || -> Vec<(&str, &str, &str)> {
+        re.captures_iter(&log)
+            .map(|caps| {
+                let field = |name| caps.name(name).unwrap().as_str();
+                (field("ts"), field("level"), field("module"))
+            })
+            .collect()
+    };
+    let start = Instant::now();
+    for _ in 0..TIMING_ITERATIONS {
+        std::hint::black_box(parse());
+    }
+    let parse_time = start.elapsed() / TIMING_ITERATIONS;
+
+    let records = parse();
+    assert_eq!(records.len(), LOG_LINES);
+    assert_eq!(
+        records[29],
+        ("2025-01-01T00:00:07.250Z", "ERROR", "i2c_bus")
+    );
+
+    println!(
+        "  {:<12} {:>8} us compile {:>8} us parse",
+        "regex-lite",
+        compile.as_micros(),
+        parse_time.as_micros()
+    );
+}
+
+/// Shows what regex-lite leaves out compared to the regex crate.
+///
+/// # Example Output
+/// ```text
+/// Error (expected): Unicode character classes are not supported
+/// ```
+///
+/// # Implementation Details
+/// - Rejects Unicode classes such as `\p{Greek}`
+/// - Keeps `\w` and case folding to ASCII
+fn test_ascii_only() {
+    println!("\nTesting ASCII-only matching:");
+
+    let err = Regex::new(r"\p{Greek}").unwrap_err();
+    println!("Error (expected): {}", err);
+    assert!(!Regex::new(r"(?i)σ").unwrap().is_match("Σ"));
+    assert!(!Regex::new(r"^\w+$").unwrap().is_match("température"));
+    assert!(Regex::new(r"^\w+$").unwrap().is_match("temperature"));
+}
+
 /// Main entry point for the regex-lite example
 ///
 /// This function is marked as no_mangle to ensure it's callable from C code,
@@ -121,6 +234,8 @@ pub fn rust_crate_test_regex_lite_main() {
     test_basic_patterns();
     test_text_replacement();
     test_url_validation();
+    test_ascii_only();
+    report_log_parsing();
 
     println!("\nAll regex-lite tests completed successfully!");
 }
@@ -0,0 +1,234 @@
+// Copyright (c) 2025 Xiaomi Corporation
+// SPDX-License-Identifier: Apache-2.0
+
+//! rmp-serde example for NuttX
+//!
+//! Round-trips the `Person`/`Address` structs used by the serde_json crate,
+//! plus enums, maps and byte arrays, as MessagePack and checks the exact wire
+//! bytes. Structs are encoded both as compact arrays and as named maps.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct Person {
+    name: String,
+    age: u32,
+    phones: Vec<String>,
+    address: Address,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct Address {
+    street: String,
+    city: String,
+    zip: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+enum Command {
+    Reset,
+    SetLevel(u8),
+    Move { x: i16, y: i16 },
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct Frame {
+    id: u16,
+    #[serde(with = "serde_bytes")]
+    payload: Vec<u8>,
+    checksum: [u8; 4],
+}
+
+fn sample_person() -> Person {
+    Person {
+        name: "Ann".to_string(),
+        age: 30,
+        phones: vec!["555-1234".to_string()],
+        address: Address {
+            street: "Main St".to_string(),
+            city: "Oslo".to_string(),
+            zip: "0150".to_string(),
+        },
+    }
+}
+
+fn sample_map() -> BTreeMap<String, u32> {
+    BTreeMap::from([("temp".to_string(), 21), ("volt".to_string(), 3300)])
+}
+
+fn sample_frame() -> Frame {
+    Frame {
+        id: 0x0102,
+        payload: vec![0xde, 0xad, 0xbe, 0xef, 0x00],
+        checksum: [1, 2, 3, 4],
+    }
+}
+
+fn test_person_roundtrip() {
+    println!("Testing Person round-trip");
+
+    let person = sample_person();
+
+    // The default encoding writes structs as arrays, in field order
+    let compact = rmp_serde::to_vec(&person).unwrap();
+    println!("Compact Person: {} bytes", compact.len());
+    let expected: &[u8] = &[
+        0x94, // array(4)
+        0xa3, b'A', b'n', b'n', // name
+        0x1e, // age
+        0x91, 0xa8, b'5', b'5', b'5', b'-', b'1', b'2', b'3', b'4', // phones
+        0x93, // address
+        0xa7, b'M', b'a', b'i', b'n', b' ', b'S', b't', // address.street
+        0xa4, b'O', b's', b'l', b'o', // address.city
+        0xa4, b'0', b'1', b'5', b'0', // address.zip
+    ];
+    assert_eq!(compact, expected);
+
+    let decoded: Person = rmp_serde::from_slice(&compact).unwrap();
+    assert_eq!(decoded, person);
+
+    // The named encoding writes structs as maps keyed by field name
+    let named = rmp_serde::to_vec_named(&person).unwrap();
+    println!("Named Person: {} bytes", named.len());
+    let expected: &[u8] = &[
+        0x84, // map(4)
+        0xa4, b'n', b'a', b'm', b'e', 0xa3, b'A', b'n', b'n', // name
+        0xa3, b'a', b'g', b'e', 0x1e, // age
+        0xa6, b'p', b'h', b'o', b'n', b'e', b's', 0x91, 0xa8, b'5', b'5', b'5', b'-', b'1', b'2',
+        b'3', b'4', // phones
+        0xa7, b'a', b'd', b'd', b'r', b'e', b's', b's', 0x83, // address
+        0xa6, b's', b't', b'r', b'e', b'e', b't', 0xa7, b'M', b'a', b'i', b'n', b' ', b'S', b't',
+        0xa4, b'c', b'i', b't', b'y', 0xa4, b'O', b's', b'l', b'o', // address.city
+        0xa3, b'z', b'i', b'p', 0xa4, b'0', b'1', b'5', b'0', // address.zip
+    ];
+    assert_eq!(named, expected);
+
+    // Both forms are accepted when decoding
+    let decoded: Person = rmp_serde::from_slice(&named).unwrap();
+    assert_eq!(decoded, person);
+
+    println!("Person round-trip tests passed");
+}
+
+fn test_enum_roundtrip() {
+    println!("Testing enum round-trip");
+
+    // Unit variants are plain strings, the others a single entry map
+    let cases: [(Command, &[u8]); 3] = [
+        (Command::Reset, &[0xa5, b'R', b'e', b's', b'e', b't']),
+        (
+            Command::SetLevel(200),
+            &[
+                0x81, 0xa8, b'S', b'e', b't', b'L', b'e', b'v', b'e', b'l', 0xcc, 0xc8,
+            ],
+        ),
+        (
+            Command::Move { x: -2, y: 300 },
+            &[
+                0x81, 0xa4, b'M', b'o', b'v', b'e', 0x92, 0xfe, 0xcd, 0x01, 0x2c,
+            ],
+        ),
+    ];
+
+    for (command, expected) in cases {
+        let encoded = rmp_serde::to_vec(&command).unwrap();
+        println!("{:?} -> {:02x?}", command, encoded);
+        assert_eq!(encoded, expected);
+
+        let decoded: Command = rmp_serde::from_slice(&encoded).unwrap();
+        assert_eq!(decoded, command);
+    }
+
+    let named = rmp_serde::to_vec_named(&Command::Move { x: -2, y: 300 }).unwrap();
+    assert_eq!(
+        named,
+        [
+            0x81, 0xa4, b'M', b'o', b'v', b'e', 0x82, 0xa1, b'x', 0xfe, 0xa1, b'y', 0xcd, 0x01,
+            0x2c
+        ]
+    );
+
+    println!("Enum round-trip tests passed");
+}
+
+fn test_map_roundtrip() {
+    println!("Testing map round-trip");
+
+    let map = sample_map();
+    let encoded = rmp_serde::to_vec(&map).unwrap();
+    assert_eq!(
+        encoded,
+        [
+            0x82, 0xa4, b't', b'e', b'm', b'p', 0x15, 0xa4, b'v', b'o', b'l', b't', 0xcd, 0x0c,
+            0xe4
+        ]
+    );
+
+    let decoded: BTreeMap<String, u32> = rmp_serde::from_slice(&encoded).unwrap();
+    assert_eq!(decoded, map);
+
+    println!("Map round-trip tests passed");
+}
+
+fn test_bytes_roundtrip() {
+    println!("Testing byte array round-trip");
+
+    // serde_bytes produces a bin 8 (0xc4), a fixed array stays an array of
+    // integers (0x94)
+    let frame = sample_frame();
+    let encoded = rmp_serde::to_vec(&frame).unwrap();
+    assert_eq!(
+        encoded,
+        [
+            0x93, 0xcd, 0x01, 0x02, 0xc4, 0x05, 0xde, 0xad, 0xbe, 0xef, 0x00, 0x94, 0x01, 0x02,
+            0x03, 0x04
+        ]
+    );
+
+    let decoded: Frame = rmp_serde::from_slice(&encoded).unwrap();
+    assert_eq!(decoded, frame);
+
+    // Decoding from a reader consumes exactly one value
+    let mut stream = encoded.clone();
+    stream.extend_from_slice(&rmp_serde::to_vec(&Command::Reset).unwrap());
+    let mut reader = stream.as_slice();
+    let first: Frame = rmp_serde::from_read(&mut reader).unwrap();
+    let second: Command = rmp_serde::from_read(&mut reader).unwrap();
+    assert_eq!(first, frame);
+    assert_eq!(second, Command::Reset);
+    assert!(reader.is_empty());
+
+    println!("Byte array round-trip tests passed");
+}
+
+fn report_sizes() {
+    println!("Encoded sizes:");
+
+    let sizes = [
+        ("person", rmp_serde::to_vec(&sample_person()).unwrap().len()),
+        (
+            "person*",
+            rmp_serde::to_vec_named(&sample_person()).unwrap().len(),
+        ),
+        ("map", rmp_serde::to_vec(&sample_map()).unwrap().len()),
+        ("frame", rmp_serde::to_vec(&sample_frame()).unwrap().len()),
+    ];
+    for (name, size) in sizes {
+        println!("  {:<8} {:>3} bytes", name, size);
+    }
+    println!("  (* named fields)");
+}
+
+#[unsafe(no_mangle)]
+pub fn rust_crate_test_rmp_serde_main() {
+    println!("Running rmp-serde tests");
+
+    test_person_roundtrip();
+    test_enum_roundtrip();
+    test_map_roundtrip();
+    test_bytes_roundtrip();
+    report_sizes();
+
+    println!("All rmp-serde tests completed");
+}
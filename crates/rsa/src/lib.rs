@@ -0,0 +1,261 @@
+// Copyright (c) 2025 Xiaomi Corporation
+// SPDX-License-Identifier: Apache-2.0
+
+//! rsa example for NuttX
+//!
+//! Verifies RSA-2048 PKCS#1 v1.5 and PSS signatures over SHA-256, made with
+//! OpenSSL, plus a set of Wycheproof-style invalid signatures, and measures
+//! verification time. Signing is left to the build server, so the device
+//! only needs the public key.
+
+use hex_literal::hex;
+use rsa::pkcs8::DecodePublicKey;
+use rsa::signature::Verifier;
+use rsa::traits::PublicKeyParts;
+use rsa::{BigUint, RsaPublicKey, pkcs1v15, pss};
+use sha2::Sha256;
+use std::time::Instant;
+
+const TIMING_ITERATIONS: u32 = 4;
+
+/// `openssl genpkey -algorithm RSA -pkeyopt rsa_keygen_bits:2048`
+const PUBLIC_KEY_PEM: &str = "-----BEGIN PUBLIC KEY-----
+MIIBIjANBgkqhkiG9w0BAQEFAAOCAQ8AMIIBCgKCAQEAy5f9T4HaO59rR2dHhNyi
+hR88dyinsW34gJqtdP/uXzN77xNKgQuRFnP5IuqoXx4pYb9SjXpfxGOZwOxkZmyC
+CdHtVVu+4DjmGZgAhFNRe3QFsZIKgJ9UFmV2IrLat50RYq374vQvxKXNYr4wi+8E
+2l8RJtofanlk35fH4nE9njYYnxZIx+QPkxgIj6dOBaHNlPc02GVnIB9K0224gs2T
+nJGabWFt1opzkeInoJAlAgWxSL8kKRG60qI9xbdRMsH2F87WgA2d8yVi2BOPyVsP
+GdwKJg30n4ewUy/FmINQKJzVrdbpeYMWFEaSVPoKswFjtbrjsF7GM+PsgHi+lDiD
+bwIDAQAB
+-----END PUBLIC KEY-----
+";
+
+const MESSAGE: &[u8] = b"nuttx firmware 1.4.2";
+
+/// `openssl dgst -sha256 -sign key.pem`
+const PKCS1V15_SIGNATURE: [u8; 256] = hex!(
+    "24aaa1111c09eb4aff66abca378588610c870217647d3e5ad063d3402401bbc9"
+    "6e9c06ea830dca0ffd956bb730184123eb5894c1f926c3851851b73473ed9c8b"
+    "585d1f50fccc528fc4a1e857bb8ab1b385145aa4805d4016b42ef0d8dcd4d61d"
+    "de3ea9592b62a211d6cb1d5878f3e9463424cb1e5e493794f697e0330986db06"
+    "2eab5df611dd7255f7cf720c347139e083a6c773caed88317a5a5b7e85fb26e7"
+    "18ceaf6b8381f4d433cea37f3620871bd1fa97d1a2591a3afb26da8b5fcb643a"
+    "4a84bb85e419e67cf82e951b0b428f588f8a645401adeb23624ac44482b4af3a"
+    "77579388eaf73b64d99109df72ad5272720fd6f3c73d5b9121236637b75f7832"
+);
+
+/// `openssl dgst -sha256 -sign key.pem -sigopt rsa_padding_mode:pss
+/// -sigopt rsa_pss_saltlen:32`
+const PSS_SIGNATURE: [u8; 256] = hex!(
+    "98b6c546f7521cff5f9ebd793cc91b777dfd03b7cb40f8b24f2d900216250ff1"
+    "296bc3157bc830a53dd3091f0e0b5e94427b3e21aa59f392637b32ca2f7c7b3f"
+    "8efddc30b3cc5f7879beb4ed6b058863cab6b0a8a64ba7f81cd9c40df5eafda7"
+    "b1cba0ee5ed7bc66d68800d217d999f9ffb4dee18f2af45cdcc50c5fbb05d016"
+    "12e77268644830377fb7f97b418c19a87fda66d132f2df6fa5fbe0b2caf6f5a3"
+    "7bc3a96733b779668833ef71cc8a9d7e43b65200c124dde8630f0b34f37d0a11"
+    "faef54e9c3d7a6235111a3da3879ec4f9b0ebc2619ae19fa9060d57aa6c26641"
+    "07ef2a784e964126bfbe64360a8a8281fd2949c3a5727d61227423983baa92f5"
+);
+
+#[derive(Clone, Copy)]
+enum Padding {
+    Pkcs1v15,
+    Pss,
+}
+
+struct TestCase {
+    tc_id: u32,
+    comment: &'static str,
+    padding: Padding,
+    msg: &'static [u8],
+    sig: fn() -> Vec<u8>,
+    valid: bool,
+}
+
+fn flip_last_bit(sig: [u8; 256]) -> Vec<u8> {
+    let mut sig = sig.to_vec();
+    sig[255] ^= 1;
+    sig
+}
+
+/// Laid out like the Wycheproof rsa_signature_2048_sha256_test.json and
+/// rsa_pss_2048_sha256_mgf1_32_test.json groups
+const TEST_CASES: &[TestCase] = &[
+    TestCase {
+        tc_id: 1,
+        comment: "PKCS#1 v1.5",
+        padding: Padding::Pkcs1v15,
+        msg: MESSAGE,
+        sig: || PKCS1V15_SIGNATURE.to_vec(),
+        valid: true,
+    },
+    TestCase {
+        tc_id: 2,
+        comment: "PSS, 32 byte salt",
+        padding: Padding::Pss,
+        msg: MESSAGE,
+        sig: || PSS_SIGNATURE.to_vec(),
+        valid: true,
+    },
+    TestCase {
+        tc_id: 3,
+        comment: "PKCS#1 v1.5, modified message",
+        padding: Padding::Pkcs1v15,
+        msg: b"nuttx firmware 1.4.3",
+        sig: || PKCS1V15_SIGNATURE.to_vec(),
+        valid: false,
+    },
+    TestCase {
+        tc_id: 4,
+        comment: "PSS, modified message",
+        padding: Padding::Pss,
+        msg: b"nuttx firmware 1.4.3",
+        sig: || PSS_SIGNATURE.to_vec(),
+        valid: false,
+    },
+    TestCase {
+        tc_id: 5,
+        comment: "PKCS#1 v1.5, modified signature",
+        padding: Padding::Pkcs1v15,
+        msg: MESSAGE,
+        sig: || flip_last_bit(PKCS1V15_SIGNATURE),
+        valid: false,
+    },
+    TestCase {
+        tc_id: 6,
+        comment: "PSS, modified signature",
+        padding: Padding::Pss,
+        msg: MESSAGE,
+        sig: || flip_last_bit(PSS_SIGNATURE),
+        valid: false,
+    },
+    TestCase {
+        tc_id: 7,
+        comment: "PSS signature as PKCS#1 v1.5",
+        padding: Padding::Pkcs1v15,
+        msg: MESSAGE,
+        sig: || PSS_SIGNATURE.to_vec(),
+        valid: false,
+    },
+    TestCase {
+        tc_id: 8,
+        comment: "PKCS#1 v1.5 signature as PSS",
+        padding: Padding::Pss,
+        msg: MESSAGE,
+        sig: || PKCS1V15_SIGNATURE.to_vec(),
+        valid: false,
+    },
+    TestCase {
+        tc_id: 9,
+        comment: "signature one byte short",
+        padding: Padding::Pkcs1v15,
+        msg: MESSAGE,
+        sig: || PKCS1V15_SIGNATURE[1..].to_vec(),
+        valid: false,
+    },
+    TestCase {
+        tc_id: 10,
+        comment: "signature larger than modulus",
+        padding: Padding::Pkcs1v15,
+        msg: MESSAGE,
+        sig: || vec![0xff; 256],
+        valid: false,
+    },
+    TestCase {
+        tc_id: 11,
+        comment: "signature = 0",
+        padding: Padding::Pss,
+        msg: MESSAGE,
+        sig: || vec![0; 256],
+        valid: false,
+    },
+];
+
+fn verify(key: &RsaPublicKey, padding: Padding, msg: &[u8], sig: &[u8]) -> bool {
+    match padding {
+        Padding::Pkcs1v15 => {
+            let key = pkcs1v15::VerifyingKey::<Sha256>::new(key.clone());
+            pkcs1v15::Signature::try_from(sig)
+                .and_then(|sig| key.verify(msg, &sig))
+                .is_ok()
+        }
+        Padding::Pss => {
+            let key = pss::VerifyingKey::<Sha256>::new(key.clone());
+            pss::Signature::try_from(sig)
+                .and_then(|sig| key.verify(msg, &sig))
+                .is_ok()
+        }
+    }
+}
+
+fn test_public_key() {
+    println!("Testing public key parsing");
+
+    let key = RsaPublicKey::from_public_key_pem(PUBLIC_KEY_PEM).unwrap();
+    assert_eq!(key.size(), 256);
+    assert_eq!(key.n().bits(), 2048);
+    assert_eq!(*key.e(), BigUint::from(65537u32));
+
+    // An exponent of 1 would make every message its own signature
+    assert!(RsaPublicKey::new(key.n().clone(), BigUint::from(1u32)).is_err());
+
+    println!("Public key parsing tests passed");
+}
+
+fn test_vectors() {
+    println!("Testing Wycheproof-style verification vectors");
+
+    let key = RsaPublicKey::from_public_key_pem(PUBLIC_KEY_PEM).unwrap();
+    for case in TEST_CASES {
+        let valid = verify(&key, case.padding, case.msg, &(case.sig)());
+        println!(
+            "  tcId {:>2} {:<32} {}",
+            case.tc_id,
+            case.comment,
+            if valid { "valid" } else { "invalid" }
+        );
+        assert_eq!(valid, case.valid);
+    }
+
+    println!("Wycheproof-style vectors passed");
+}
+
+fn report_timing() {
+    println!("Timing, {} iterations:", TIMING_ITERATIONS);
+
+    let key = RsaPublicKey::from_public_key_pem(PUBLIC_KEY_PEM).unwrap();
+
+    let pkcs1v15_key = pkcs1v15::VerifyingKey::<Sha256>::new(key.clone());
+    let sig = pkcs1v15::Signature::try_from(&PKCS1V15_SIGNATURE[..]).unwrap();
+    let start = Instant::now();
+    for _ in 0..TIMING_ITERATIONS {
+        pkcs1v15_key.verify(MESSAGE, &sig).unwrap();
+    }
+    let pkcs1v15 = start.elapsed() / TIMING_ITERATIONS;
+
+    let pss_key = pss::VerifyingKey::<Sha256>::new(key);
+    let sig = pss::Signature::try_from(&PSS_SIGNATURE[..]).unwrap();
+    let start = Instant::now();
+    for _ in 0..TIMING_ITERATIONS {
+        pss_key.verify(MESSAGE, &sig).unwrap();
+    }
+    let pss = start.elapsed() / TIMING_ITERATIONS;
+
+    println!(
+        "  {:<20} {:>8} us",
+        "verify PKCS#1 v1.5",
+        pkcs1v15.as_micros()
+    );
+    println!("  {:<20} {:>8} us", "verify PSS", pss.as_micros());
+}
+
+#[unsafe(no_mangle)]
+pub fn rust_crate_test_rsa_main() {
+    println!("Running rsa tests");
+
+    test_public_key();
+    test_vectors();
+    report_timing();
+
+    println!("All rsa tests completed");
+}
@@ -0,0 +1,305 @@
+// Copyright (c) 2025 Xiaomi Corporation
+// SPDX-License-Identifier: Apache-2.0
+
+//! ruzstd example for NuttX
+//!
+//! Decodes a Zstandard frame made by the reference zstd tool, verifies its
+//! content checksum, bounds the window a frame may ask for, skips metadata
+//! frames, checks that corrupted frames are rejected, and reports ratio,
+//! decode speed and peak heap. The deflate figures for the same log come
+//! from crates/miniz_oxide.
+
+use hex_literal::hex;
+use ruzstd::decoding::errors::{FrameDecoderError, ReadFrameHeaderError};
+use ruzstd::decoding::{FrameDecoder, StreamingDecoder};
+use ruzstd::encoding::{CompressionLevel, compress_to_vec};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::io::{self, Read};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Instant;
+
+const LOG_LINES: usize = 256;
+const DECODE_ITERATIONS: u32 = 16;
+
+/// Largest window accepted, where ruzstd would allow 128 MiB; the decoder
+/// buffers this much output, so it bounds the heap a frame can demand.
+/// Frames from the ruzstd encoder declare 128 KiB
+const MAX_WINDOW: u64 = 128 * 1024;
+
+/// `zstd -19 --check` over `sensor_log(256)`, a single segment frame with
+/// an XXH64 content checksum
+const ZSTD_FRAME: [u8; 860] = hex!(
+    "28b52ffd64bc12751a0006f46212b057725414ad241060a75d1191ba10470601"
+    "640059005a00bca2ab822a239c10868cc73e975966c925d468084938d00c3768"
+    "d49d2e1f389295935188738a4f279999c578f33fe457179150318344c42c36ae"
+    "edcb7adb5242839064cc36de365faf4290245a2a0b16380010ca133800080204"
+    "050301c1802020081c049bcaee559d2d032530c8c8c568c6dfe4bf973ea3986e"
+    "44fee0530f9118ca8238e20b6b5c5be4f0966d844e70243dbba7add7a40a1225"
+    "a805c5673ad384597e5ee10d199984093a42f111af76f0a322368661c6189f3b"
+    "8759e4fff4d3c522a72848234216614c3b648f74ad431facc8cec40b1b794537"
+    "4193995b903fef489aca850fe1a10c89c93ce6182b2e56dde04b34b01c862923"
+    "ee73f7b090a80c61ae0aa24c703af3c5bdff42592a1189d018434478e600a3ed"
+    "72c70751526a41fd4c3a414379f09a918578f2470839aa1071a887c1119d75c3"
+    "dbbca8af9d4858109111e36d42d0646ccee78a77af5c82859290e03016c2e1cf"
+    "d3b4aea8e114cb089cd8b8608feed1d48f34486642ccb98981ffa87129c32d7b"
+    "0701da44a1ad013128551811f81ce68ca63f028b4a4c4c135a2651a903d902cb"
+    "4d1a59b093a6e1192d8a4acb325a4042f18c169050d453b32d0d833210124e5d"
+    "510acaa4f4f0945e13a894806c81e5268d2cd849d3f08c1690503ca3052414cf"
+    "680109453d35dbd2a8259a7ecca039c466161100a5e446ec152921a178460b48"
+    "289ed102128a67b48084e2192d20a1a8a7e66e302264872e7524fa0c132429ef"
+    "4066a30891099a65b4282a2dcb680109c5335a4042f18c1690c884c052463708"
+    "90b7135dd453967e30ebff8e9a620ab5c20b5829acd102128a67b48084e2192d"
+    "20a178468ba262130549c94e0b164c21d0cf1a4d6b80777b1f96d102128a67b4"
+    "8084e2192d20a178460b48289ed102129910a651473e3882a923f199136a4d40"
+    "a5a7d6ca9029402f3519930891099a65b48084e219f4e4e320cd88cd7702f619"
+    "a1f94e087111a2a529245dc3e49549a974266053c400ae1b2dcb680109c5335a"
+    "08531a92c2d87d563d1de85bb42d6ca335c2535b8ec56e7cdb04a0fa0c31b341"
+    "0b709254942e122ca208b53749052a982625149a82695242a129982625149a82"
+    "69ca531a8dc25cbc32b190542f9320181977b4fe9e19f82a48f9139f"
+);
+
+static HEAP_IN_USE: AtomicUsize = AtomicUsize::new(0);
+static HEAP_PEAK: AtomicUsize = AtomicUsize::new(0);
+
+/// The system allocator, keeping count of the bytes in use and their peak
+struct CountingAllocator;
+
+fn record_alloc(size: usize) {
+    let in_use = HEAP_IN_USE.fetch_add(size, Ordering::Relaxed) + size;
+    HEAP_PEAK.fetch_max(in_use, Ordering::Relaxed);
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc(layout) };
+        if !ptr.is_null() {
+            record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc_zeroed(layout) };
+        if !ptr.is_null() {
+            record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) };
+        HEAP_IN_USE.fetch_sub(layout.size(), Ordering::Relaxed);
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = unsafe { System.realloc(ptr, layout, new_size) };
+        if !new_ptr.is_null() {
+            HEAP_IN_USE.fetch_sub(layout.size(), Ordering::Relaxed);
+            record_alloc(new_size);
+        }
+        new_ptr
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// Restarts the peak from the bytes in use now, and returns them
+fn reset_heap_peak() -> usize {
+    let in_use = HEAP_IN_USE.load(Ordering::Relaxed);
+    HEAP_PEAK.store(in_use, Ordering::Relaxed);
+    in_use
+}
+
+/// The input of ZSTD_FRAME, 256 lines such as `t=500 ch2 adc=74`
+fn sensor_log(lines: usize) -> Vec<u8> {
+    (0..lines)
+        .flat_map(|i| format!("t={} ch{} adc={}\n", i * 250, i % 4, (i * 37) % 1024).into_bytes())
+        .collect()
+}
+
+/// Decodes one frame, returning it with the checksum stored in the frame and
+/// the one computed over the output; ruzstd reads the stored checksum but
+/// leaves comparing them to the caller
+fn decode_frame(frame: &[u8]) -> io::Result<(Vec<u8>, Option<u32>, Option<u32>)> {
+    let mut decoder = StreamingDecoder::new_with_max_window_size(frame, MAX_WINDOW)
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+    let mut output = Vec::new();
+    decoder.read_to_end(&mut output)?;
+    Ok((
+        output,
+        decoder.decoder.get_checksum_from_data(),
+        decoder.decoder.get_calculated_checksum(),
+    ))
+}
+
+/// decode_frame(), failing on a checksum mismatch
+fn decode_checked(frame: &[u8]) -> io::Result<Vec<u8>> {
+    let (output, stored, computed) = decode_frame(frame)?;
+    if stored != computed {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "content checksum mismatch",
+        ));
+    }
+    Ok(output)
+}
+
+fn test_vector() {
+    println!("Testing zstd tool vector");
+
+    let log = sensor_log(LOG_LINES);
+    assert_eq!(ZSTD_FRAME[..4], 0xfd2fb528u32.to_le_bytes());
+    let (output, stored, computed) = decode_frame(&ZSTD_FRAME).unwrap();
+    assert_eq!(output, log);
+    // The low 32 bits of the XXH64 of the content, from `zstd -lv`
+    assert_eq!(stored, Some(0x9f13f948));
+    assert_eq!(computed, stored);
+
+    println!("zstd tool vector passed");
+}
+
+fn test_window_limit() {
+    println!("Testing window size limit");
+
+    // A frame whose window does not fit the limit is refused before any
+    // buffer is allocated for it. A single segment frame's window is its
+    // content size
+    let content_size = sensor_log(LOG_LINES).len() as u64;
+    let result = StreamingDecoder::new_with_max_window_size(&ZSTD_FRAME[..], 4096);
+    let err = result.err().unwrap();
+    println!("  {}", err);
+    assert!(matches!(
+        err,
+        FrameDecoderError::WindowSizeTooBig { requested, max: 4096 }
+            if requested == content_size
+    ));
+
+    println!("Window size limit tests passed");
+}
+
+fn test_multiple_frames() {
+    println!("Testing skippable and concatenated frames");
+
+    // Metadata such as a version tag can travel in a skippable frame,
+    // which decoders pass over
+    let mut payload = Vec::new();
+    payload.extend_from_slice(&0x184d2a50u32.to_le_bytes());
+    payload.extend_from_slice(&12u32.to_le_bytes());
+    payload.extend_from_slice(b"fw 1.4.2 OTA");
+    payload.extend_from_slice(&ZSTD_FRAME);
+    payload.extend_from_slice(&ZSTD_FRAME);
+
+    let log = sensor_log(LOG_LINES);
+    let mut output = Vec::with_capacity(2 * log.len());
+    FrameDecoder::new()
+        .decode_all_to_vec(&payload, &mut output)
+        .unwrap();
+    assert_eq!(output.len(), 2 * log.len());
+    assert!(output.chunks(log.len()).all(|chunk| chunk == log));
+
+    // The output must be reserved up front, it is never grown
+    let mut small = Vec::with_capacity(log.len());
+    let err = FrameDecoder::new()
+        .decode_all_to_vec(&payload, &mut small)
+        .unwrap_err();
+    assert!(matches!(err, FrameDecoderError::TargetTooSmall));
+
+    println!("Skippable and concatenated frame tests passed");
+}
+
+fn test_roundtrip() {
+    println!("Testing ruzstd compression");
+
+    // Only the uncompressed and fastest levels are implemented by ruzstd
+    let log = sensor_log(4 * LOG_LINES);
+    for level in [CompressionLevel::Uncompressed, CompressionLevel::Fastest] {
+        let frame = compress_to_vec(&log[..], level);
+        println!(
+            "  {:<14} {} -> {} bytes",
+            format!("{:?}", level),
+            log.len(),
+            frame.len()
+        );
+        assert_eq!(decode_checked(&frame).unwrap(), log);
+    }
+
+    println!("ruzstd compression tests passed");
+}
+
+fn test_corrupted_input() {
+    println!("Testing corrupted input");
+
+    let mut frame = ZSTD_FRAME;
+    frame[0] ^= 1;
+    let err = StreamingDecoder::new(&frame[..]).err().unwrap();
+    println!("  {:<20} {}", "bad magic", err);
+    assert!(matches!(
+        err,
+        FrameDecoderError::ReadFrameHeaderError(ReadFrameHeaderError::BadMagicNumber(_))
+    ));
+
+    let err = decode_checked(&ZSTD_FRAME[..400]).unwrap_err();
+    println!("  {:<20} {}", "truncated", err);
+
+    let mut frame = ZSTD_FRAME;
+    let len = frame.len();
+    frame[len - 1] ^= 1;
+    let err = decode_checked(&frame).unwrap_err();
+    println!("  {:<20} {}", "checksum", err);
+
+    // Damage in the compressed data surfaces as a decoding error or, if it
+    // still decodes, as a checksum mismatch
+    for pos in [40, 200, 600] {
+        let mut frame = ZSTD_FRAME;
+        frame[pos] ^= 0x10;
+        let err = decode_checked(&frame).unwrap_err();
+        println!("  {:<20} {}", format!("byte {} flipped", pos), err);
+    }
+
+    println!("Corrupted input tests passed");
+}
+
+/// Prints ratio, speed and peak heap for one frame
+fn report_frame(name: &str, frame: &[u8], log: &[u8]) {
+    let heap_base = reset_heap_peak();
+    assert_eq!(decode_checked(frame).unwrap(), log);
+    let heap = HEAP_PEAK.load(Ordering::Relaxed) - heap_base;
+
+    let start = Instant::now();
+    for _ in 0..DECODE_ITERATIONS {
+        std::hint::black_box(decode_checked(std::hint::black_box(frame)).unwrap());
+    }
+    let decoded = log.len() as f64 * DECODE_ITERATIONS as f64;
+
+    println!(
+        "  {:<14} {:>6.1}% {:>8.2} MB/s {:>8} bytes heap",
+        name,
+        frame.len() as f64 * 100.0 / log.len() as f64,
+        decoded / 1_000_000.0 / start.elapsed().as_secs_f64(),
+        heap
+    );
+}
+
+fn report_decompression() {
+    let log = sensor_log(LOG_LINES);
+    println!("Decompression of a {} byte sensor log:", log.len());
+
+    report_frame("zstd -19", &ZSTD_FRAME, &log);
+    let fastest = compress_to_vec(&log[..], CompressionLevel::Fastest);
+    report_frame("ruzstd fastest", &fastest, &log);
+}
+
+#[unsafe(no_mangle)]
+pub fn rust_crate_test_ruzstd_main() {
+    println!("Running ruzstd tests");
+
+    test_vector();
+    test_window_limit();
+    test_multiple_frames();
+    test_roundtrip();
+    test_corrupted_input();
+    report_decompression();
+
+    println!("All ruzstd tests completed");
+}
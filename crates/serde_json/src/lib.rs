@@ -2,7 +2,26 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use serde::{Deserialize, Serialize};
+#[cfg(feature = "raw_value")]
+use serde_json::value::RawValue;
+use serde_json::{Deserializer, Map, Number, Value};
 use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::{BufReader, BufWriter, Write};
+use std::thread;
+
+/// NDJSON file used by the streaming test, /tmp is a tmpfs mount
+const STREAM_PATH: &str = "/tmp/serde_json_stream.ndjson";
+
+/// Stack for the nesting tests. Every nesting level recurses through the
+/// deserializer, and the 127 levels below take about 40 KiB in a release
+/// build on x86_64 but 176 KiB in an unoptimized one, far more than the
+/// default task has.
+const NESTING_STACK_SIZE: usize = if cfg!(debug_assertions) {
+    192 * 1024
+} else {
+    64 * 1024
+};
 
 // Define test data structures
 #[derive(Serialize, Deserialize, Debug, PartialEq)]
@@ -32,6 +51,17 @@ pub fn rust_crate_test_serde_json_main() {
     test_json_to_string_pretty();
     test_json_value_manipulation();
     test_json_arbitrary_types();
+    test_stream_from_file();
+    #[cfg(feature = "raw_value")]
+    test_raw_value();
+    #[cfg(feature = "preserve_order")]
+    test_preserve_order();
+    #[cfg(feature = "arbitrary_precision")]
+    test_arbitrary_precision();
+    #[cfg(feature = "float_roundtrip")]
+    test_float_roundtrip();
+    test_enum_representations();
+    test_recursion_limit();
 
     println!("All serde_json tests completed");
 }
@@ -55,6 +85,7 @@ fn test_basic_serialization() {
     println!("Basic serialization tests passed");
 }
 
+#[allow(clippy::bool_assert_comparison)]
 fn test_basic_deserialization() {
     println!("Testing basic deserialization");
 
@@ -204,3 +235,386 @@ fn test_json_arbitrary_types() {
 
     println!("JSON with arbitrary types tests passed");
 }
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct Reading {
+    seq: u32,
+    sensor: String,
+    value: f64,
+}
+
+fn test_stream_from_file() {
+    println!("Testing streaming deserialization from a file");
+
+    const RECORDS: u32 = 50;
+
+    // Write newline delimited JSON, one record per line
+    let file = File::create(STREAM_PATH).unwrap();
+    let mut writer = BufWriter::new(file);
+    for seq in 0..RECORDS {
+        let reading = Reading {
+            seq,
+            sensor: format!("temp{}", seq % 4),
+            value: f64::from(seq) * 0.5,
+        };
+        serde_json::to_writer(&mut writer, &reading).unwrap();
+        writer.write_all(b"\n").unwrap();
+    }
+    writer.flush().unwrap();
+    drop(writer);
+
+    // Decode the records one at a time instead of loading the whole file
+    let file = File::open(STREAM_PATH).unwrap();
+    let stream = Deserializer::from_reader(BufReader::new(file)).into_iter::<Reading>();
+    let mut count = 0;
+    let mut total = 0.0;
+    for (seq, reading) in (0..).zip(stream) {
+        let reading = reading.unwrap();
+        assert_eq!(reading.seq, seq);
+        count += 1;
+        total += reading.value;
+    }
+    println!("Streamed {} records, total value {}", count, total);
+    assert_eq!(count, RECORDS);
+    assert_eq!(total, 612.5);
+
+    // A truncated record ends the stream with an EOF error after the
+    // complete ones
+    fs::write(
+        STREAM_PATH,
+        "{\"seq\":0,\"sensor\":\"a\",\"value\":1}\n{\"seq\":1,",
+    )
+    .unwrap();
+    let file = File::open(STREAM_PATH).unwrap();
+    let mut stream = Deserializer::from_reader(BufReader::new(file)).into_iter::<Reading>();
+    assert_eq!(stream.next().unwrap().unwrap().seq, 0);
+    let err = stream.next().unwrap().unwrap_err();
+    println!("Error (expected): {}", err);
+    assert!(err.is_eof());
+
+    fs::remove_file(STREAM_PATH).unwrap();
+
+    println!("Streaming deserialization tests passed");
+}
+
+/// Message whose payload is parsed later, once its kind is known
+#[cfg(feature = "raw_value")]
+#[derive(Deserialize)]
+struct Envelope<'a> {
+    kind: &'a str,
+    #[serde(borrow)]
+    payload: &'a RawValue,
+}
+
+#[cfg(feature = "raw_value")]
+#[derive(Serialize)]
+struct Outgoing {
+    kind: &'static str,
+    payload: Box<RawValue>,
+}
+
+#[cfg(feature = "raw_value")]
+fn test_raw_value() {
+    println!("Testing RawValue");
+
+    let input = r#"[
+        {"kind": "reading", "payload": {"seq": 1, "sensor": "temp0", "value": 21.5}},
+        {"kind": "log", "payload": [ "kept",  "verbatim" ]}
+    ]"#;
+
+    // The payloads are borrowed as unparsed slices of the input
+    let envelopes: Vec<Envelope> = serde_json::from_str(input).unwrap();
+    assert_eq!(envelopes.len(), 2);
+    assert_eq!(envelopes[1].payload.get(), r#"[ "kept",  "verbatim" ]"#);
+
+    for envelope in &envelopes {
+        match envelope.kind {
+            "reading" => {
+                let reading: Reading = serde_json::from_str(envelope.payload.get()).unwrap();
+                assert_eq!(reading.sensor, "temp0");
+                assert_eq!(reading.value, 21.5);
+            }
+            "log" => {
+                let lines: Vec<String> = serde_json::from_str(envelope.payload.get()).unwrap();
+                assert_eq!(lines, ["kept", "verbatim"]);
+            }
+            kind => panic!("unexpected kind {}", kind),
+        }
+    }
+
+    // Pre-serialized JSON is embedded as is, without a parse/serialize cycle
+    let outgoing = Outgoing {
+        kind: "log",
+        payload: RawValue::from_string(r#"[ "kept",  "verbatim" ]"#.to_string()).unwrap(),
+    };
+    assert_eq!(
+        serde_json::to_string(&outgoing).unwrap(),
+        r#"{"kind":"log","payload":[ "kept",  "verbatim" ]}"#
+    );
+
+    let raw = serde_json::value::to_raw_value(&Reading {
+        seq: 2,
+        sensor: "volt".to_string(),
+        value: 3.3,
+    })
+    .unwrap();
+    assert_eq!(raw.get(), r#"{"seq":2,"sensor":"volt","value":3.3}"#);
+
+    // Invalid JSON is rejected when the RawValue is created
+    assert!(RawValue::from_string("{\"open\": ".to_string()).is_err());
+
+    println!("RawValue tests passed");
+}
+
+#[cfg(feature = "preserve_order")]
+fn test_preserve_order() {
+    println!("Testing preserve_order");
+
+    // Objects keep their insertion order instead of being sorted by key
+    let input = r#"{"zeta":1,"alpha":2,"mid":3}"#;
+    let value: Value = serde_json::from_str(input).unwrap();
+    let keys: Vec<&str> = value
+        .as_object()
+        .unwrap()
+        .keys()
+        .map(String::as_str)
+        .collect();
+    assert_eq!(keys, ["zeta", "alpha", "mid"]);
+    assert_eq!(serde_json::to_string(&value).unwrap(), input);
+
+    let mut map = Map::new();
+    map.insert("version".to_string(), Value::from(2));
+    map.insert("board".to_string(), Value::from("sim"));
+    map.insert("arch".to_string(), Value::from("arm"));
+    assert_eq!(
+        serde_json::to_string(&map).unwrap(),
+        r#"{"version":2,"board":"sim","arch":"arm"}"#
+    );
+
+    // Removing keeps the order of the remaining entries
+    map.shift_remove("version");
+    map.insert("version".to_string(), Value::from(3));
+    assert_eq!(
+        serde_json::to_string(&map).unwrap(),
+        r#"{"board":"sim","arch":"arm","version":3}"#
+    );
+
+    println!("preserve_order tests passed");
+}
+
+#[cfg(feature = "arbitrary_precision")]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct Invoice {
+    id: u32,
+    amount: Number,
+}
+
+#[cfg(feature = "arbitrary_precision")]
+fn test_arbitrary_precision() {
+    println!("Testing arbitrary_precision");
+
+    // Numbers keep their original digits instead of going through u64/f64
+    for text in [
+        "123456789012345678901234567890",
+        "-0.000000000000000000000000000001",
+        "3.141592653589793238462643383279",
+        "1e+400",
+    ] {
+        let value: Value = serde_json::from_str(text).unwrap();
+        assert_eq!(serde_json::to_string(&value).unwrap(), text);
+    }
+
+    let big: Number = serde_json::from_str("123456789012345678901234567890").unwrap();
+    assert_eq!(big.as_u64(), None);
+    assert_eq!(big.as_u128(), Some(123456789012345678901234567890));
+    assert_eq!(big.as_str(), "123456789012345678901234567890");
+
+    // Decimal amounts survive a round trip without binary rounding
+    let input = r#"{"id":7,"amount":19.999999999999999999}"#;
+    let invoice: Invoice = serde_json::from_str(input).unwrap();
+    assert_eq!(invoice.amount.as_str(), "19.999999999999999999");
+    assert_eq!(invoice.amount.as_f64(), Some(20.0));
+    assert_eq!(serde_json::to_string(&invoice).unwrap(), input);
+
+    println!("arbitrary_precision tests passed");
+}
+
+#[cfg(feature = "float_roundtrip")]
+fn test_float_roundtrip() {
+    println!("Testing float_roundtrip");
+
+    // The exact parser gets the last bit right where the fast one may not
+    let tricky: f64 = serde_json::from_str("2.638344616030823e-256").unwrap();
+    assert_eq!(tricky.to_bits(), 2.638344616030823e-256f64.to_bits());
+
+    for value in [
+        0.1f64,
+        1.0 / 3.0,
+        2.2250738585072014e-308,
+        5e-324,
+        1.7976931348623157e308,
+        -123456.789e-10,
+        9007199254740993.0,
+    ] {
+        let text = serde_json::to_string(&value).unwrap();
+        let parsed: f64 = serde_json::from_str(&text).unwrap();
+        assert_eq!(
+            parsed.to_bits(),
+            value.to_bits(),
+            "{} did not round-trip",
+            text
+        );
+    }
+
+    println!("float_roundtrip tests passed");
+}
+
+/// Internally tagged, the variant name is stored in a "type" field
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum Event {
+    Connect { addr: String },
+    Data { len: u32 },
+    Disconnect,
+}
+
+/// Adjacently tagged, the variant data sits next to the tag
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[serde(tag = "op", content = "args")]
+enum Request {
+    Read(String),
+    Write(String, String),
+}
+
+/// Untagged, the first variant that matches wins
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[serde(untagged)]
+enum Setting {
+    Flag(bool),
+    Number(Number),
+    Text(String),
+    List(Vec<Setting>),
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct Device {
+    id: String,
+    #[serde(flatten)]
+    location: Location,
+    /// Fields not claimed by the other members end up here
+    #[serde(flatten)]
+    extra: Map<String, Value>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct Location {
+    site: String,
+    rack: String,
+}
+
+fn test_enum_representations() {
+    println!("Testing tagged, untagged and flattened representations");
+
+    let events = [
+        (
+            Event::Connect {
+                addr: "10.0.0.1".to_string(),
+            },
+            r#"{"type":"connect","addr":"10.0.0.1"}"#,
+        ),
+        (Event::Data { len: 512 }, r#"{"type":"data","len":512}"#),
+        (Event::Disconnect, r#"{"type":"disconnect"}"#),
+    ];
+    for (event, expected) in events {
+        assert_eq!(serde_json::to_string(&event).unwrap(), expected);
+        let decoded: Event = serde_json::from_str(expected).unwrap();
+        assert_eq!(decoded, event);
+    }
+
+    // The tag does not have to come first
+    let event: Event = serde_json::from_str(r#"{"len":64,"type":"data"}"#).unwrap();
+    assert_eq!(event, Event::Data { len: 64 });
+
+    let request = Request::Write("/dev/null".to_string(), "x".to_string());
+    let json = serde_json::to_string(&request).unwrap();
+    assert_eq!(json, r#"{"op":"Write","args":["/dev/null","x"]}"#);
+    assert_eq!(serde_json::from_str::<Request>(&json).unwrap(), request);
+
+    let setting: Setting = serde_json::from_str(r#"[true, 42, 0.5, "auto", []]"#).unwrap();
+    assert_eq!(
+        setting,
+        Setting::List(vec![
+            Setting::Flag(true),
+            Setting::Number(Number::from(42)),
+            Setting::Number(Number::from_f64(0.5).unwrap()),
+            Setting::Text("auto".to_string()),
+            Setting::List(vec![]),
+        ])
+    );
+    assert_eq!(
+        serde_json::to_string(&setting).unwrap(),
+        r#"[true,42,0.5,"auto",[]]"#
+    );
+    assert!(serde_json::from_str::<Setting>("null").is_err());
+
+    let input = r#"{"id":"dev0","site":"lab","rack":"r1","firmware":"1.2.0","slots":4}"#;
+    let device: Device = serde_json::from_str(input).unwrap();
+    assert_eq!(device.location.rack, "r1");
+    assert_eq!(device.extra.len(), 2);
+    assert_eq!(device.extra["slots"], 4);
+    assert_eq!(serde_json::to_string(&device).unwrap(), input);
+
+    println!("Tagged, untagged and flattened representation tests passed");
+}
+
+/// Arrays nested `depth` levels deep, e.g. [[[]]] for 3
+fn nested_arrays(depth: usize) -> String {
+    format!("{}{}", "[".repeat(depth), "]".repeat(depth))
+}
+
+/// Recursive type with a much smaller frame per level than `Value`
+#[derive(Deserialize)]
+struct Nested(Vec<Nested>);
+
+impl Nested {
+    fn depth(&self) -> usize {
+        1 + self.0.iter().map(Nested::depth).max().unwrap_or(0)
+    }
+}
+
+fn test_recursion_limit() {
+    println!("Testing recursion limit");
+
+    let handle = thread::Builder::new()
+        .stack_size(NESTING_STACK_SIZE)
+        .spawn(|| {
+            // Moderate nesting into Value is fine
+            let value: Value = serde_json::from_str(&nested_arrays(16)).unwrap();
+            let mut depth = 0;
+            let mut current = &value;
+            while let Some(inner) = current.as_array() {
+                depth += 1;
+                match inner.first() {
+                    Some(next) => current = next,
+                    None => break,
+                }
+            }
+            assert_eq!(depth, 16);
+
+            // The deserializer stops at 128 levels instead of exhausting the
+            // stack on hostile input
+            let deepest: Nested = serde_json::from_str(&nested_arrays(127)).unwrap();
+            assert_eq!(deepest.depth(), 127);
+
+            let err = serde_json::from_str::<Nested>(&nested_arrays(128))
+                .err()
+                .unwrap();
+            println!("Error (expected): {}", err);
+            assert!(err.to_string().starts_with("recursion limit exceeded"));
+            assert_eq!(err.classify(), serde_json::error::Category::Syntax);
+        })
+        .unwrap();
+    handle.join().unwrap();
+
+    println!("Recursion limit tests passed");
+}
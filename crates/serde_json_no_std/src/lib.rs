@@ -0,0 +1,247 @@
+// Copyright (c) 2025 Xiaomi Corporation
+// SPDX-License-Identifier: Apache-2.0
+
+//! serde_json example for NuttX without std
+//!
+//! Uses `core` and `alloc` only, with a global allocator on top of the NuttX
+//! heap. Runs the same kind of workload as the std based `serde_json` crate,
+//! so the size of the two applications can be compared.
+
+#![no_std]
+#![feature(alloc_error_handler)]
+
+extern crate alloc;
+
+use alloc::boxed::Box;
+use alloc::collections::BTreeMap;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::alloc::{GlobalAlloc, Layout};
+use core::ffi::{c_int, c_void};
+use core::fmt::Write;
+use core::panic::PanicInfo;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+
+unsafe extern "C" {
+    fn malloc(size: usize) -> *mut c_void;
+    fn realloc(ptr: *mut c_void, size: usize) -> *mut c_void;
+    fn memalign(alignment: usize, size: usize) -> *mut c_void;
+    fn free(ptr: *mut c_void);
+    fn write(fd: c_int, buf: *const c_void, count: usize) -> isize;
+    fn abort() -> !;
+}
+
+const STDOUT_FILENO: c_int = 1;
+
+/// Alignment guaranteed by the NuttX heap for every block
+const MIN_ALIGN: usize = 2 * core::mem::size_of::<usize>();
+
+/// Global allocator forwarding to the NuttX heap
+struct NuttxAllocator;
+
+unsafe impl GlobalAlloc for NuttxAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        if layout.align() <= MIN_ALIGN {
+            unsafe { malloc(layout.size()) as *mut u8 }
+        } else {
+            unsafe { memalign(layout.align(), layout.size()) as *mut u8 }
+        }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, _layout: Layout) {
+        unsafe { free(ptr as *mut c_void) }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        if layout.align() <= MIN_ALIGN {
+            unsafe { realloc(ptr as *mut c_void, new_size) as *mut u8 }
+        } else {
+            // realloc() may move the block to a less aligned address
+            let new_layout = unsafe { Layout::from_size_align_unchecked(new_size, layout.align()) };
+            let new_ptr = unsafe { self.alloc(new_layout) };
+            if !new_ptr.is_null() {
+                unsafe {
+                    core::ptr::copy_nonoverlapping(ptr, new_ptr, layout.size().min(new_size));
+                    self.dealloc(ptr, layout);
+                }
+            }
+            new_ptr
+        }
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: NuttxAllocator = NuttxAllocator;
+
+#[alloc_error_handler]
+fn alloc_error(layout: Layout) -> ! {
+    let _ = writeln!(
+        StdoutWriter,
+        "Failed to allocate {} bytes aligned to {}",
+        layout.size(),
+        layout.align()
+    );
+    unsafe { abort() }
+}
+
+#[panic_handler]
+fn panic(info: &PanicInfo) -> ! {
+    let _ = writeln!(StdoutWriter, "{}", info);
+    unsafe { abort() }
+}
+
+/// Writer printing straight to the stdout file descriptor
+struct StdoutWriter;
+
+impl Write for StdoutWriter {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        let mut bytes = s.as_bytes();
+        while !bytes.is_empty() {
+            let written =
+                unsafe { write(STDOUT_FILENO, bytes.as_ptr() as *const c_void, bytes.len()) };
+            if written <= 0 {
+                return Err(core::fmt::Error);
+            }
+            bytes = &bytes[written as usize..];
+        }
+        Ok(())
+    }
+}
+
+macro_rules! println {
+    ($($arg:tt)*) => {{
+        let _ = writeln!(StdoutWriter, $($arg)*);
+    }};
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct Person {
+    name: String,
+    age: u32,
+    phones: Vec<String>,
+    address: Address,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct Address {
+    street: String,
+    city: String,
+    zip: String,
+}
+
+fn test_allocator() {
+    println!("Testing global allocator");
+
+    let mut numbers = Vec::new();
+    for i in 0..1000u32 {
+        numbers.push(i);
+    }
+    assert_eq!(numbers.iter().sum::<u32>(), 499500);
+
+    // Over-aligned types take the memalign() path
+    #[repr(align(64))]
+    struct Aligned([u8; 64]);
+
+    let aligned = Box::new(Aligned([0xa5; 64]));
+    assert!((&*aligned as *const Aligned).is_aligned());
+    assert!(aligned.0.iter().all(|b| *b == 0xa5));
+
+    let mut blocks: Vec<Box<Aligned>> = Vec::new();
+    for _ in 0..8 {
+        blocks.push(Box::new(Aligned([0; 64])));
+    }
+    assert!(blocks.iter().all(|b| (&**b as *const Aligned).is_aligned()));
+
+    println!("Global allocator tests passed");
+}
+
+fn test_complex_structures() {
+    println!("Testing complex structures");
+
+    let person = Person {
+        name: "John Doe".to_string(),
+        age: 30,
+        phones: vec!["123-456-7890".to_string(), "987-654-3210".to_string()],
+        address: Address {
+            street: "123 Main St".to_string(),
+            city: "Anytown".to_string(),
+            zip: "12345".to_string(),
+        },
+    };
+
+    let serialized = serde_json::to_string(&person).unwrap();
+    println!("Serialized: {}", serialized);
+
+    let deserialized: Person = serde_json::from_str(&serialized).unwrap();
+    assert_eq!(deserialized, person);
+
+    // Byte slices work the same way as strings
+    let bytes = serde_json::to_vec(&person).unwrap();
+    let deserialized: Person = serde_json::from_slice(&bytes).unwrap();
+    assert_eq!(deserialized, person);
+
+    println!("Complex structures tests passed");
+}
+
+fn test_json_error_handling() {
+    println!("Testing JSON error handling");
+
+    let invalid_json = "{\"name\": \"John\", age: 30}";
+    let err = serde_json::from_str::<Person>(invalid_json).unwrap_err();
+    println!("Error (expected): {}", err);
+    assert_eq!((err.line(), err.column()), (1, 18));
+
+    let mismatched_json = "{\"name\": 42}";
+    let err = serde_json::from_str::<Person>(mismatched_json).unwrap_err();
+    println!("Error (expected): {}", err);
+    assert!(err.is_data());
+
+    println!("JSON error handling tests passed");
+}
+
+fn test_json_value() {
+    println!("Testing JSON Value");
+
+    let mut data = json!({
+        "name": "John",
+        "age": 30,
+        "tags": ["developer", "rust"]
+    });
+
+    data["age"] = json!(31);
+    data["tags"].as_array_mut().unwrap().push(json!("no_std"));
+    data["new_field"] = json!(true);
+
+    assert_eq!(data["age"], 31);
+    assert_eq!(data["tags"][2], "no_std");
+    assert_eq!(data["new_field"], true);
+
+    let pretty = serde_json::to_string_pretty(&data).unwrap();
+    println!("Pretty JSON:\n{}", pretty);
+
+    let parsed: Value = serde_json::from_str(&pretty).unwrap();
+    assert_eq!(parsed, data);
+
+    // Without preserve_order objects are BTreeMaps sorted by key
+    let keys: Vec<&String> = parsed.as_object().unwrap().keys().collect();
+    assert_eq!(keys, ["age", "name", "new_field", "tags"]);
+
+    let map: BTreeMap<String, u32> = serde_json::from_value(json!({"b": 2, "a": 1})).unwrap();
+    assert_eq!(serde_json::to_string(&map).unwrap(), r#"{"a":1,"b":2}"#);
+
+    println!("JSON Value tests passed");
+}
+
+#[unsafe(no_mangle)]
+pub fn rust_crate_test_serde_json_no_std_main() {
+    println!("Running serde_json no_std tests");
+
+    test_allocator();
+    test_complex_structures();
+    test_json_error_handling();
+    test_json_value();
+
+    println!("All serde_json no_std tests completed");
+}
@@ -0,0 +1,224 @@
+// Copyright (c) 2025 Xiaomi Corporation
+// SPDX-License-Identifier: Apache-2.0
+
+//! serde_norway example for NuttX
+//!
+//! serde_norway is the maintained fork of serde_yaml, with the same API.
+//! Deserializes partner sensor configurations using anchors, merge keys,
+//! multiple documents and tagged enums, from memory and from files under
+//! /tmp.
+
+use serde::{Deserialize, Serialize};
+use serde_norway::Value;
+use serde_norway::value::TaggedValue;
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use std::io::BufReader;
+
+const CONFIG_PATH: &str = "/tmp/partner_sensors.yaml";
+
+const SENSORS_YAML: &str = "\
+# partner sensor configuration
+gateway: gw-01
+defaults: &defaults
+  interval_ms: 1000
+  enabled: true
+sensors:
+  - name: temp0
+    kind: temperature
+    <<: *defaults
+    interval_ms: 500
+  - name: volt1
+    kind: voltage
+    <<: *defaults
+    labels: { rack: '3', site: lab }
+";
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct SensorConfig {
+    gateway: String,
+    sensors: Vec<Sensor>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct Sensor {
+    name: String,
+    kind: SensorKind,
+    interval_ms: u32,
+    #[serde(default)]
+    enabled: bool,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    labels: BTreeMap<String, String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[serde(rename_all = "lowercase")]
+enum SensorKind {
+    Temperature,
+    Voltage,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+enum Command {
+    Reset,
+    SetLevel(u8),
+    Move { x: i16, y: i16 },
+}
+
+fn parse_config(yaml: &str) -> SensorConfig {
+    // Merge keys are resolved on the Value before it is deserialized
+    let mut value: Value = serde_norway::from_str(yaml).unwrap();
+    value.apply_merge().unwrap();
+    serde_norway::from_value(value).unwrap()
+}
+
+fn test_anchors_and_merge_keys() {
+    println!("Testing anchors, aliases and merge keys");
+
+    let config = parse_config(SENSORS_YAML);
+    println!("Config: {:?}", config);
+    assert_eq!(config.gateway, "gw-01");
+    assert_eq!(config.sensors.len(), 2);
+
+    // Keys of the sensor itself override the merged defaults
+    assert_eq!(config.sensors[0].interval_ms, 500);
+    assert!(config.sensors[0].enabled);
+    assert_eq!(config.sensors[1].interval_ms, 1000);
+    assert_eq!(config.sensors[1].kind, SensorKind::Voltage);
+    assert_eq!(config.sensors[1].labels["rack"], "3");
+
+    // A plain alias is a copy of the anchored node
+    let value: Value = serde_norway::from_str("base: &b [1, 2]\ncopy: *b\n").unwrap();
+    assert_eq!(value["copy"], value["base"]);
+    assert_eq!(value["copy"][1].as_u64(), Some(2));
+
+    println!("Anchors, aliases and merge keys tests passed");
+}
+
+fn test_multi_document() {
+    println!("Testing multiple documents");
+
+    let yaml = "\
+---
+name: temp0
+kind: temperature
+interval_ms: 500
+---
+name: volt1
+kind: voltage
+interval_ms: 1000
+enabled: true
+...
+";
+    let mut sensors = Vec::new();
+    for document in serde_norway::Deserializer::from_str(yaml) {
+        sensors.push(Sensor::deserialize(document).unwrap());
+    }
+    assert_eq!(sensors.len(), 2);
+    assert_eq!(sensors[0].name, "temp0");
+    assert!(!sensors[0].enabled);
+    assert!(sensors[1].enabled);
+
+    // The single document helpers refuse a stream with several documents
+    let err = serde_norway::from_str::<Sensor>(yaml).unwrap_err();
+    println!("Error (expected): {}", err);
+
+    println!("Multiple documents tests passed");
+}
+
+fn test_tagged_enums() {
+    println!("Testing tagged enums");
+
+    // Enum variants are written as YAML tags
+    let cases = [
+        (Command::Reset, "Reset\n"),
+        (Command::SetLevel(200), "!SetLevel 200\n"),
+        (Command::Move { x: -2, y: 300 }, "!Move\nx: -2\ny: 300\n"),
+    ];
+    for (command, expected) in cases {
+        let yaml = serde_norway::to_string(&command).unwrap();
+        println!("{:?} -> {:?}", command, yaml);
+        assert_eq!(yaml, expected);
+        assert_eq!(serde_norway::from_str::<Command>(&yaml).unwrap(), command);
+    }
+
+    let commands: Vec<Command> =
+        serde_norway::from_str("- Reset\n- !SetLevel 7\n- !Move { x: 1, y: 2 }\n").unwrap();
+    assert_eq!(
+        commands,
+        [
+            Command::Reset,
+            Command::SetLevel(7),
+            Command::Move { x: 1, y: 2 }
+        ]
+    );
+
+    // Untyped parsing keeps the tag next to the value
+    let value: Value = serde_norway::from_str("!SetLevel 7").unwrap();
+    let Value::Tagged(tagged) = value else {
+        panic!("expected a tagged value");
+    };
+    let TaggedValue { tag, value } = *tagged;
+    assert_eq!(tag, "SetLevel");
+    assert_eq!(value.as_u64(), Some(7));
+
+    assert!(serde_norway::from_str::<Command>("!Jump 1").is_err());
+
+    println!("Tagged enums tests passed");
+}
+
+fn test_read_from_file() {
+    println!("Testing reading {}", CONFIG_PATH);
+
+    fs::write(CONFIG_PATH, SENSORS_YAML).unwrap();
+
+    let file = BufReader::new(File::open(CONFIG_PATH).unwrap());
+    let mut value: Value = serde_norway::from_reader(file).unwrap();
+    value.apply_merge().unwrap();
+    let config: SensorConfig = serde_norway::from_value(value).unwrap();
+    assert_eq!(config, parse_config(SENSORS_YAML));
+
+    // Write the resolved configuration back and read it again
+    let resolved = serde_norway::to_string(&config).unwrap();
+    println!("Resolved:\n{}", resolved);
+    fs::write(CONFIG_PATH, &resolved).unwrap();
+    let file = File::open(CONFIG_PATH).unwrap();
+    let reread: SensorConfig = serde_norway::from_reader(file).unwrap();
+    assert_eq!(reread, config);
+
+    fs::remove_file(CONFIG_PATH).unwrap();
+
+    println!("Reading from file tests passed");
+}
+
+fn test_errors() {
+    println!("Testing error locations");
+
+    let yaml = "gateway: gw-01\nsensors:\n  - name: temp0\n    kind: pressure\n";
+    let err = serde_norway::from_str::<SensorConfig>(yaml).unwrap_err();
+    println!("Error (expected): {}", err);
+    let location = err.location().unwrap();
+    assert_eq!((location.line(), location.column()), (4, 11));
+
+    let err = serde_norway::from_str::<Value>("key: [1, 2\n").unwrap_err();
+    println!("Error (expected): {}", err);
+    assert!(err.location().is_some());
+
+    // An alias to an anchor that was never defined
+    assert!(serde_norway::from_str::<Value>("a: *missing\n").is_err());
+
+    println!("Error location tests passed");
+}
+
+#[unsafe(no_mangle)]
+pub fn rust_crate_test_serde_norway_main() {
+    println!("Running serde_norway tests");
+
+    test_anchors_and_merge_keys();
+    test_multi_document();
+    test_tagged_enums();
+    test_read_from_file();
+    test_errors();
+
+    println!("All serde_norway tests completed");
+}
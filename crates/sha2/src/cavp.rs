@@ -0,0 +1,316 @@
+// Copyright (c) 2025 Xiaomi Corporation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Entries of the NIST CAVP SHAVS response files (CAVS 11.0), byte oriented.
+//! From each ShortMsg file: one byte, the longest message that still fits
+//! one block with its padding, the shortest that needs two, and one whole
+//! block. From each LongMsg file: its first two messages. For SHA-512/256
+//! only the first two ShortMsg entries are included.
+
+use super::{Message, Vector};
+use hex_literal::hex;
+
+pub const SHA224: &[Vector] = &[
+    // SHA224ShortMsg.rsp, Len = 8
+    Vector {
+        message: Message::Bytes(&hex!("84")),
+        digest: &hex!("3cd36921df5d6963e73739cf4d20211e2d8877c19cff087ade9d0e3a"),
+    },
+    // SHA224ShortMsg.rsp, Len = 440
+    Vector {
+        message: Message::Bytes(&hex!(
+            "445e8698eeb8accbaac4ffa7d934fffd16014a430ef70f3a9174c6cfe96d1e3f"
+            "6ab1377f4a7212dbb30146dd17d9f470c4dffc45b8e871"
+        )),
+        digest: &hex!("4c7ae028c0fe61f2a9cada61fae30685b77f04c6442576e912af9fa6"),
+    },
+    // SHA224ShortMsg.rsp, Len = 448
+    Vector {
+        message: Message::Bytes(&hex!(
+            "52839f2f0853a30df14ec897a1914c685c1ac21470d00654c8c37663bfb65fa7"
+            "32dbb694d9dd09ced723b48d8f545846ba168988b61cc724"
+        )),
+        digest: &hex!("2f755a57674b49d5c25cb37348f35b6fd2de2552c749f2645ba63d20"),
+    },
+    // SHA224ShortMsg.rsp, Len = 512
+    Vector {
+        message: Message::Bytes(&hex!(
+            "a3310ba064be2e14ad32276e18cd0310c933a6e650c3c754d0243c6c61207865"
+            "b4b65248f66a08edf6e0832689a9dc3a2e5d2095eeea50bd862bac88c8bd318d"
+        )),
+        digest: &hex!("b2a5586d9cbf0baa999157b4af06d88ae08d7c9faab4bc1a96829d65"),
+    },
+    // SHA224LongMsg.rsp, Len = 1304
+    Vector {
+        message: Message::Bytes(&hex!(
+            "f149e41d848f59276cfddd743bafa9a90e1ee4a263a118142b33e3702176ef0a"
+            "59f8237a1cb51b42f3ded6b202d9af0997898fdd03cf60bda951c514547a0850"
+            "cec25444ae2f24cb711bfbafcc3956c941d3de69f155e3f8b10f06db5f37359b"
+            "772ddd43e1035a0a0d3db33242d5843033833b0dd43b870c6bf60e8deab55f31"
+            "7cc3273f5e3ba747f0cb65050cb7228796210d9254873643008d45f29cfd6c5b"
+            "060c9a"
+        )),
+        digest: &hex!("9db6dc3a23abd7b6c3d72c38f4843c7de48a71d0ba91a86b18393e5f"),
+    },
+    // SHA224LongMsg.rsp, Len = 2096
+    Vector {
+        message: Message::Bytes(&hex!(
+            "c39147fad02c6ed5876eb3257837c3f456008694fac94932aa521bae9c8e77ab"
+            "b65524e298595abbdc5b261e9c9f0f86359dfa584bf94b740eb54d09bba6d4ad"
+            "652421adf50605a170ce4a4478204e831679f1d4b61db1c9735114e595cb47ae"
+            "58670403f01bb8b0c92de64013a3c6137efc33b0421dc45b638e38eb33e617d6"
+            "1884968e8f80bb1071e1c3b97611c15cf78e8793f8e1c95265d480c29ce08d4c"
+            "2ce59ff3dc1e56e8f053a958a75085890897b625de520bd6028bb512d89ff139"
+            "1cf0e7dddfdbd160ccea5fc5b04a5ac03a7f890fff56d10dc01b9f85f00d8bc4"
+            "710f35f29fa073a28f0dafa798e58a2913aec5f960d662222aed06c1eb11a216"
+            "b2a952da2456"
+        )),
+        digest: &hex!("2b05b170d4976409f23ce885a0a2c0a943226688d4f5bbaf35fabb46"),
+    },
+];
+
+pub const SHA256: &[Vector] = &[
+    // SHA256ShortMsg.rsp, Len = 8
+    Vector {
+        message: Message::Bytes(&hex!("d3")),
+        digest: &hex!("28969cdfa74a12c82f3bad960b0b000aca2ac329deea5c2328ebc6f2ba9802c1"),
+    },
+    // SHA256ShortMsg.rsp, Len = 440
+    Vector {
+        message: Message::Bytes(&hex!(
+            "3ebfb06db8c38d5ba037f1363e118550aad94606e26835a01af05078533cc25f"
+            "2f39573c04b632f62f68c294ab31f2a3e2a1a0d8c2be51"
+        )),
+        digest: &hex!("6595a2ef537a69ba8583dfbf7f5bec0ab1f93ce4c8ee1916eff44a93af5749c4"),
+    },
+    // SHA256ShortMsg.rsp, Len = 448
+    Vector {
+        message: Message::Bytes(&hex!(
+            "2d52447d1244d2ebc28650e7b05654bad35b3a68eedc7f8515306b496d75f3e7"
+            "3385dd1b002625024b81a02f2fd6dffb6e6d561cb7d0bd7a"
+        )),
+        digest: &hex!("cfb88d6faf2de3a69d36195acec2e255e2af2b7d933997f348e09f6ce5758360"),
+    },
+    // SHA256ShortMsg.rsp, Len = 512
+    Vector {
+        message: Message::Bytes(&hex!(
+            "5a86b737eaea8ee976a0a24da63e7ed7eefad18a101c1211e2b3650c5187c2a8"
+            "a650547208251f6d4237e661c7bf4c77f335390394c37fa1a9f9be836ac28509"
+        )),
+        digest: &hex!("42e61e174fbb3897d6dd6cef3dd2802fe67b331953b06114a65c772859dfc1aa"),
+    },
+    // SHA256LongMsg.rsp, Len = 1304
+    Vector {
+        message: Message::Bytes(&hex!(
+            "451101250ec6f26652249d59dc974b7361d571a8101cdfd36aba3b5854d3ae08"
+            "6b5fdd4597721b66e3c0dc5d8c606d9657d0e323283a5217d1f53f2f284f57b8"
+            "5c8a61ac8924711f895c5ed90ef17745ed2d728abd22a5f7a13479a462d71b56"
+            "c19a74a40b655c58edfe0a188ad2cf46cbf30524f65d423c837dd1ff2bf462ac"
+            "4198007345bb44dbb7b1c861298cdf61982a833afc728fae1eda2f87aa2c9480"
+            "858bec"
+        )),
+        digest: &hex!("3c593aa539fdcdae516cdf2f15000f6634185c88f505b39775fb9ab137a10aa2"),
+    },
+    // SHA256LongMsg.rsp, Len = 2096
+    Vector {
+        message: Message::Bytes(&hex!(
+            "6b918fb1a5ad1f9c5e5dbdf10a93a9c8f6bca89f37e79c9fe12a57227941b173"
+            "ac79d8d440cde8c64c4ebc84a4c803d198a296f3de060900cc427f58ca6ec373"
+            "084f95dd6c7c427ecfbf781f68be572a88dbcbb188581ab200bfb99a3a816407"
+            "e7dd6dd21003554d4f7a99c93ebfce5c302ff0e11f26f83fe669acefb0c1bbb8"
+            "b1e909bd14aa48ba3445c88b0e1190eef765ad898ab8ca2fe507015f1578f10d"
+            "ce3c11a55fb9434ee6e9ad6cc0fdc4684447a9b3b156b908646360f24fec2d8f"
+            "a69e2c93db78708fcd2eef743dcb9353819b8d667c48ed54cd436fb1476598c4"
+            "a1d7028e6f2ff50751db36ab6bc32435152a00abd3d58d9a8770d9a3e52d5a36"
+            "28ae3c9e0325"
+        )),
+        digest: &hex!("46500b6ae1ab40bde097ef168b0f3199049b55545a1588792d39d594f493dca7"),
+    },
+];
+
+pub const SHA384: &[Vector] = &[
+    // SHA384ShortMsg.rsp, Len = 8
+    Vector {
+        message: Message::Bytes(&hex!("c5")),
+        digest: &hex!(
+            "b52b72da75d0666379e20f9b4a79c33a329a01f06a2fb7865c9062a28c1de860"
+            "ba432edfd86b4cb1cb8a75b46076e3b1"
+        ),
+    },
+    // SHA384ShortMsg.rsp, Len = 888
+    Vector {
+        message: Message::Bytes(&hex!(
+            "a04f390a9cc2effad05db80d9076a8d4b6cc8bba97b27b423670b290b8e69c2b"
+            "187230011c1481ac88d090f39154659494db5e410851c6e8b2b8a93717cae760"
+            "37e0881978124fe7e1a0929d8891491f4e99646cc94062dc82411fa66130eda4"
+            "6560e75b98048236439465125e737b"
+        )),
+        digest: &hex!(
+            "e7089d72945cef851e689b4409cfb63d135f0b5cdfb0dac6c3a292dd70371ab4"
+            "b79da1997d7992906ac7213502662920"
+        ),
+    },
+    // SHA384ShortMsg.rsp, Len = 896
+    Vector {
+        message: Message::Bytes(&hex!(
+            "f419494c3c6d0727b3395a483a2167182a7252f4fd099c2d4b71b053f94bb8b3"
+            "adf3b51e8460cfec084ce9415c95798fbae4975c208c544645b54c44d2b97f2e"
+            "cfce5c805be61f5ba1d35dcc07afdd51a87baa990506668cf710e18be9b0ebf9"
+            "43f366fa29c69f7a6616de72a3353b66"
+        )),
+        digest: &hex!(
+            "aead8688c58c6ba4e9cadb4756b465dce0fb06f1cfaa478197f2ea89414e47e9"
+            "572034adfed160703c79b82b3fd7ab78"
+        ),
+    },
+    // SHA384ShortMsg.rsp, Len = 1024
+    Vector {
+        message: Message::Bytes(&hex!(
+            "3bf52cc5ee86b9a0190f390a5c0366a560b557000dbe5115fd9ee11630a62769"
+            "011575f15881198f227876e8fe685a6939bc8b89fd48a34ec5e71e131462b288"
+            "6794dffa68ccc6d564733e67ffef25e627c6f4b5460796e3bce67bf58ca6e8e5"
+            "55bc916a8531697ac948b90dc8616f25101db90b50c3d3dbc9e21e42ff387187"
+        )),
+        digest: &hex!(
+            "12b6cb35eda92ee37356ddee77781a17b3d90e563824a984faffc6fdd1693bd7"
+            "626039635563cfc3b9a2b00f9c65eefd"
+        ),
+    },
+    // SHA384LongMsg.rsp, Len = 1816
+    Vector {
+        message: Message::Bytes(&hex!(
+            "62c6a169b9be02b3d7b471a964fc0bcc72b480d26aecb2ed460b7f50016ddaf0"
+            "4c51218783f3aadfdff5a04ded030d7b3fb7376b61ba30b90e2da921a4470740"
+            "d63fb99fa16cc8ed81abaf8ce4016e50df81da832070372c24a80890aa3a26fa"
+            "675710b8fb718266249d496f313c55d0bada101f8f56eeccee4345a8f98f60a3"
+            "6662cfda794900d12f9414fcbdfdeb85388a814996b47e24d5c8086e7a8edcc5"
+            "3d299d0d033e6bb60c58b83d6e8b57f6c258d6081dd10eb942fdf8ec157ec3e7"
+            "5371235a8196eb9d22b1de3a2d30c2abbe0db7650cf6c7159bacbe29b3a93c92"
+            "100508"
+        )),
+        digest: &hex!(
+            "0730e184e7795575569f87030260bb8e54498e0e5d096b18285e988d245b6f34"
+            "86d1f2447d5f85bcbe59d5689fc49425"
+        ),
+    },
+    // SHA384LongMsg.rsp, Len = 2608
+    Vector {
+        message: Message::Bytes(&hex!(
+            "ffd67890ff77f344ad4f067df2f4ff1db8f541c7a2bd9ae9fabad0faebbf7d00"
+            "f0a71d568c3c66ac3c57d84faa4894ab2377710e4b4c4dae0f4da1eedc8658dd"
+            "0e2ee2ffac8784515206f2876eb41f98afd4547cbbc68034212bcf0c8e4a7d1d"
+            "43b3ed15c621f53bd8a57cada80148ec4652119b5af3da84169d81dc69d394c8"
+            "767d662044d36272b77ca04abff7b6b0cf3bd1f3919a04a5d8ebdfe7d6e844e7"
+            "8fd576a68d6373ffd5d384e51b5e12ec32d5bb0ac685a59f4d5d12b43b533580"
+            "750345310999cfe91cf2500624fe03a65769f86a627a667b5f3b42cb01da109e"
+            "124ffa48203f1f3873202d35429f32e8263eaf9bce42ef40f5cc96b591467d46"
+            "4d00bd743a1b0af4c1a743fbdd0846b9879e092371a5e7f6f65937f9515e2382"
+            "0e60b83bbff73926f0cdb9df5d02e82262cf2e8cb26af6a64c2a4d1fabecab59"
+            "3db510281799"
+        )),
+        digest: &hex!(
+            "1396fea95ce0c1c1c224b50a07dd7197f1d62b993c7fe9e1cc1a56101920d4b0"
+            "fecef587fbcd56b854c8c9da95132f02"
+        ),
+    },
+];
+
+pub const SHA512: &[Vector] = &[
+    // SHA512ShortMsg.rsp, Len = 8
+    Vector {
+        message: Message::Bytes(&hex!("21")),
+        digest: &hex!(
+            "3831a6a6155e509dee59a7f451eb35324d8f8f2df6e3708894740f98fdee2388"
+            "9f4de5adb0c5010dfb555cda77c8ab5dc902094c52de3278f35a75ebc25f093a"
+        ),
+    },
+    // SHA512ShortMsg.rsp, Len = 888
+    Vector {
+        message: Message::Bytes(&hex!(
+            "324533e685f1852e358eea8ea8b81c288b3f3beb1f2bc2b8d3fdbac318382e3d"
+            "7120de30c9c237aa0a34831deb1e5e060a7969cd3a9742ec1e64b354f7eb290c"
+            "ba1c681c66cc7ea994fdf5614f604d1a2718aab581c1c94931b1387e4b7dc736"
+            "35bf3a7301174075fa70a9227d85d3"
+        )),
+        digest: &hex!(
+            "3b26c5170729d0814153becb95f1b65cd42f9a6d0649d914e4f69d938b5e9dc0"
+            "41cd0f5c8da0b484d7c7bc7b1bdefb08fe8b1bfedc81109345bc9e9a399feedf"
+        ),
+    },
+    // SHA512ShortMsg.rsp, Len = 896
+    Vector {
+        message: Message::Bytes(&hex!(
+            "518985977ee21d2bf622a20567124fcbf11c72df805365835ab3c041f4a9cd8a"
+            "0ad63c9dee1018aa21a9fa3720f47dc48006f1aa3dba544950f87e627f369bc2"
+            "793ede21223274492cceb77be7eea50e5a509059929a16d33a9f54796cde5770"
+            "c74bd3ecc25318503f1a41976407aff2"
+        )),
+        digest: &hex!(
+            "c00926a374cde55b8fbd77f50da1363da19744d3f464e07ce31794c5a61b6f9c"
+            "85689fa1cfe136553527fd876be91673c2cac2dd157b2defea360851b6d92cf4"
+        ),
+    },
+    // SHA512ShortMsg.rsp, Len = 1024
+    Vector {
+        message: Message::Bytes(&hex!(
+            "fd2203e467574e834ab07c9097ae164532f24be1eb5d88f1af7748ceff0d2c67"
+            "a21f4e4097f9d3bb4e9fbf97186e0db6db0100230a52b453d421f8ab9c9a6043"
+            "aa3295ea20d2f06a2f37470d8a99075f1b8a8336f6228cf08b5942fc1fb4299c"
+            "7d2480e8e82bce175540bdfad7752bc95b577f229515394f3ae5cec870a4b2f8"
+        )),
+        digest: &hex!(
+            "a21b1077d52b27ac545af63b32746c6e3c51cb0cb9f281eb9f3580a6d4996d5c"
+            "9917d2a6e484627a9d5a06fa1b25327a9d710e027387fc3e07d7c4d14c6086cc"
+        ),
+    },
+    // SHA512LongMsg.rsp, Len = 1816
+    Vector {
+        message: Message::Bytes(&hex!(
+            "4f05600950664d5190a2ebc29c9edb89c20079a4d3e6bc3b27d75e34e2fa3d02"
+            "768502bd69790078598d5fcf3d6779bfed1284bbe5ad72fb456015181d9587d6"
+            "e864c940564eaafb4f2fead4346ea09b6877d9340f6b82eb1515880872213da3"
+            "ad88feba9f4f13817a71d6f90a1a17c43a15c038d988b5b29edffe2d6a062813"
+            "cedbe852cde302b3e33b696846d2a8e36bd680efcc6cd3f9e9a4c1ae8cac10cc"
+            "5244d131677140399176ed46700019a004a163806f7fa467fc4e17b4617bbd76"
+            "41aaff7ff56396ba8c08a8be100b33a20b5daf134a2aefa5e1c3496770dcf6ba"
+            "a4f7bb"
+        )),
+        digest: &hex!(
+            "a9db490c708cc72548d78635aa7da79bb253f945d710e5cb677a474efc7c65a2"
+            "aab45bc7ca1113c8ce0f3c32e1399de9c459535e8816521ab714b2a6cd200525"
+        ),
+    },
+    // SHA512LongMsg.rsp, Len = 2608
+    Vector {
+        message: Message::Bytes(&hex!(
+            "d5e378ae9fc2648f4a13bbec4b0935afb4f822f5fe0d5063053d2fbd547b33b4"
+            "a32e7a009ee2afafe83d2ebd603568e4a38189b5d24d59e8953260f15f654ed4"
+            "f42f9a39299d68c3eb78b09e83779d5718b433f1765d35350eac46493d194e84"
+            "d1ce1f81c95b59725cab8ab73d369ab01e7967cf73a3acf1789227ee75fdfb6e"
+            "40f353ff0484486542be053115db2896bab86c774f8985c4dbcc4c078f7b1c3a"
+            "4c867cdc6580fe44a598673494cc0fb1f6598b1295768a584041fdbd14fa7b90"
+            "fa6fe33f71b743b68e23f8e7407217aad9440cc8cad28152aedb82388be2de16"
+            "5496d051b292de6303460273a43508296b6237c07804335d2e81229f7c9a0e77"
+            "61e38a3aaf7799f40fe9cb00457ea9d5b59953232676681fc71b261a6f8cd359"
+            "293f5b21f0cf3a11b7f49cb5adb3c357bed2aa185d8fe8408192d6d3ed1ff465"
+            "b590892efe03"
+        )),
+        digest: &hex!(
+            "a70c75b9b1f0ac2ed2c2797763ac9a6601d95f46889b00fc3ddae4d0ac692375"
+            "0a108d79eb764e77ac07b7cb5c01cb4b3747dcf69ba3b35c51fb995da2632e70"
+        ),
+    },
+];
+
+pub const SHA512_256: &[Vector] = &[
+    // SHA512_256ShortMsg.rsp, Len = 0
+    Vector {
+        message: Message::Bytes(b""),
+        digest: &hex!("c672b8d1ef56ed28ab87c3622c5114069bdd3ad7b8f9737498d0c01ecef0967a"),
+    },
+    // SHA512_256ShortMsg.rsp, Len = 8
+    Vector {
+        message: Message::Bytes(&hex!("fa")),
+        digest: &hex!("c4ef36923c64e51e875720e550298a5ab8a3f2f875b1e1a4c9b95babf7344fef"),
+    },
+];
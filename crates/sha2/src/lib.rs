@@ -1,31 +1,505 @@
 // Copyright (c) 2025 Xiaomi Corporation
 // SPDX-License-Identifier: Apache-2.0
 
-use sha2::{Digest, Sha256};
+//! Hash function conformance suite for NuttX
+//!
+//! Checks the SHA-2 family against the NIST FIPS 180-4 examples and CAVP
+//! short and long messages, SHA-3, BLAKE2 and BLAKE3 against their reference
+//! vectors, and MD5 and SHA-1 for legacy protocols. Every hash is also fed
+//! in random chunks, and its throughput is reported to choose a hash for
+//! flash integrity checks.
 
-#[unsafe(no_mangle)]
-pub unsafe fn rust_crate_test_sha2_main() {
-    // Create a new SHA-256 hasher
-    let mut hasher = Sha256::new();
+use blake2::{Blake2b512, Blake2s256};
+use hex_literal::hex;
+use md5::Md5;
+use sha1::Sha1;
+use sha2::digest::{DynDigest, InvalidBufferSize};
+use sha2::{Sha224, Sha256, Sha384, Sha512, Sha512_256};
+use sha3::{Sha3_256, Sha3_512};
+use std::time::Instant;
+
+mod cavp;
+
+const THROUGHPUT_BUFFER: usize = 16 * 1024;
+const THROUGHPUT_ITERATIONS: u32 = 4;
+
+/// Seed of the random chunkings, unless SHA2_CHUNK_SEED gives another one
+/// in hexadecimal
+const CHUNK_SEED: u64 = 0x9e37_79b9_7f4a_7c15;
+
+const ABC: &[u8] = b"abc";
+const TWO_BLOCK_448: &[u8] = b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq";
+const TWO_BLOCK_896: &[u8] = b"abcdefghbcdefghicdefghijdefghijkefghijklfghijklmghijklmn\
+hijklmnoijklmnopjklmnopqklmnopqrlmnopqrsmnopqrstnopqrstu";
+
+enum Message {
+    Bytes(&'static [u8]),
+    /// One million repetitions of 'a', the long message of FIPS 180
+    MillionA,
+}
+
+struct Vector {
+    message: Message,
+    digest: &'static [u8],
+}
+
+struct Algorithm {
+    name: &'static str,
+    new: fn() -> Box<dyn DynDigest>,
+    vectors: &'static [Vector],
+}
+
+fn boxed<D: DynDigest + Default + 'static>() -> Box<dyn DynDigest> {
+    Box::new(D::default())
+}
+
+/// blake3 only implements the digest traits behind its unstable
+/// "traits-preview" feature, so it gets a small adapter instead
+#[derive(Clone, Default)]
+struct Blake3(blake3::Hasher);
+
+impl DynDigest for Blake3 {
+    fn update(&mut self, data: &[u8]) {
+        self.0.update(data);
+    }
 
-    // Update with binary data
-    let data = b"Hello world!";
-    hasher.update(data);
+    fn finalize_into(mut self, buf: &mut [u8]) -> Result<(), InvalidBufferSize> {
+        self.finalize_into_reset(buf)
+    }
+
+    fn finalize_into_reset(&mut self, out: &mut [u8]) -> Result<(), InvalidBufferSize> {
+        if out.len() != blake3::OUT_LEN {
+            return Err(InvalidBufferSize);
+        }
+        out.copy_from_slice(self.0.finalize().as_bytes());
+        self.0.reset();
+        Ok(())
+    }
+
+    fn reset(&mut self) {
+        self.0.reset();
+    }
+
+    fn output_size(&self) -> usize {
+        blake3::OUT_LEN
+    }
+
+    fn box_clone(&self) -> Box<dyn DynDigest> {
+        Box::new(self.clone())
+    }
+}
 
-    // Update with string data
-    // `update` can be called repeatedly and is generic over `AsRef<[u8]>`
-    hasher.update("String data");
+const ALGORITHMS: &[Algorithm] = &[
+    Algorithm {
+        name: "SHA-224",
+        new: boxed::<Sha224>,
+        vectors: &[
+            Vector {
+                message: Message::Bytes(b""),
+                digest: &hex!("d14a028c2a3a2bc9476102bb288234c415a2b01f828ea62ac5b3e42f"),
+            },
+            Vector {
+                message: Message::Bytes(ABC),
+                digest: &hex!("23097d223405d8228642a477bda255b32aadbce4bda0b3f7e36c9da7"),
+            },
+            Vector {
+                message: Message::Bytes(TWO_BLOCK_448),
+                digest: &hex!("75388b16512776cc5dba5da1fd890150b0c6455cb4f58b1952522525"),
+            },
+        ],
+    },
+    Algorithm {
+        name: "SHA-256",
+        new: boxed::<Sha256>,
+        vectors: &[
+            Vector {
+                message: Message::Bytes(b""),
+                digest: &hex!("e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"),
+            },
+            Vector {
+                message: Message::Bytes(ABC),
+                digest: &hex!("ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"),
+            },
+            Vector {
+                message: Message::Bytes(TWO_BLOCK_448),
+                digest: &hex!("248d6a61d20638b8e5c026930c3e6039a33ce45964ff2167f6ecedd419db06c1"),
+            },
+            Vector {
+                message: Message::MillionA,
+                digest: &hex!("cdc76e5c9914fb9281a1c7e284d73e67f1809a48a497200e046d39ccc7112cd0"),
+            },
+        ],
+    },
+    Algorithm {
+        name: "SHA-384",
+        new: boxed::<Sha384>,
+        vectors: &[
+            Vector {
+                message: Message::Bytes(ABC),
+                digest: &hex!(
+                    "cb00753f45a35e8bb5a03d699ac65007272c32ab0eded1631a8b605a43ff5bed"
+                    "8086072ba1e7cc2358baeca134c825a7"
+                ),
+            },
+            Vector {
+                message: Message::Bytes(TWO_BLOCK_896),
+                digest: &hex!(
+                    "09330c33f71147e83d192fc782cd1b4753111b173b3b05d22fa08086e3b0f712"
+                    "fcc7c71a557e2db966c3e9fa91746039"
+                ),
+            },
+        ],
+    },
+    Algorithm {
+        name: "SHA-512",
+        new: boxed::<Sha512>,
+        vectors: &[
+            Vector {
+                message: Message::Bytes(b""),
+                digest: &hex!(
+                    "cf83e1357eefb8bdf1542850d66d8007d620e4050b5715dc83f4a921d36ce9ce"
+                    "47d0d13c5d85f2b0ff8318d2877eec2f63b931bd47417a81a538327af927da3e"
+                ),
+            },
+            Vector {
+                message: Message::Bytes(ABC),
+                digest: &hex!(
+                    "ddaf35a193617abacc417349ae20413112e6fa4e89a97ea20a9eeee64b55d39a"
+                    "2192992a274fc1a836ba3c23a3feebbd454d4423643ce80e2a9ac94fa54ca49f"
+                ),
+            },
+            Vector {
+                message: Message::Bytes(TWO_BLOCK_896),
+                digest: &hex!(
+                    "8e959b75dae313da8cf4f72814fc143f8f7779c6eb9f7fa17299aeadb6889018"
+                    "501d289e4900f7e4331b99dec4b5433ac7d329eeb6dd26545e96e55b874be909"
+                ),
+            },
+            Vector {
+                message: Message::MillionA,
+                digest: &hex!(
+                    "e718483d0ce769644e2e42c7bc15b4638e1f98b13b2044285632a803afa973eb"
+                    "de0ff244877ea60a4cb0432ce577c31beb009c5c2c49aa2e4eadb217ad8cc09b"
+                ),
+            },
+        ],
+    },
+    Algorithm {
+        name: "SHA-512/256",
+        new: boxed::<Sha512_256>,
+        vectors: &[
+            Vector {
+                message: Message::Bytes(ABC),
+                digest: &hex!("53048e2681941ef99b2e29b76b4c7dabe4c2d0c634fc6d46e0e2f13107e7af23"),
+            },
+            Vector {
+                message: Message::Bytes(TWO_BLOCK_896),
+                digest: &hex!("3928e184fb8690f840da3988121d31be65cb9d3ef83ee6146feac861e19b563a"),
+            },
+        ],
+    },
+    Algorithm {
+        name: "SHA3-256",
+        new: boxed::<Sha3_256>,
+        vectors: &[
+            Vector {
+                message: Message::Bytes(b""),
+                digest: &hex!("a7ffc6f8bf1ed76651c14756a061d662f580ff4de43b49fa82d80a4b80f8434a"),
+            },
+            Vector {
+                message: Message::Bytes(ABC),
+                digest: &hex!("3a985da74fe225b2045c172d6bd390bd855f086e3e9d525b46bfe24511431532"),
+            },
+            Vector {
+                message: Message::Bytes(TWO_BLOCK_448),
+                digest: &hex!("41c0dba2a9d6240849100376a8235e2c82e1b9998a999e21db32dd97496d3376"),
+            },
+        ],
+    },
+    Algorithm {
+        name: "SHA3-512",
+        new: boxed::<Sha3_512>,
+        vectors: &[Vector {
+            message: Message::Bytes(ABC),
+            digest: &hex!(
+                "b751850b1a57168a5693cd924b6b096e08f621827444f70d884f5d0240d2712e"
+                "10e116e9192af3c91a7ec57647e3934057340b4cf408d5a56592f8274eec53f0"
+            ),
+        }],
+    },
+    // RFC 7693, appendices A and B
+    Algorithm {
+        name: "BLAKE2b-512",
+        new: boxed::<Blake2b512>,
+        vectors: &[Vector {
+            message: Message::Bytes(ABC),
+            digest: &hex!(
+                "ba80a53f981c4d0d6a2797b69f12f6e94c212f14685ac4b74b12bb6fdbffa2d1"
+                "7d87c5392aab792dc252d5de4533cc9518d38aa8dbf1925ab92386edd4009923"
+            ),
+        }],
+    },
+    Algorithm {
+        name: "BLAKE2s-256",
+        new: boxed::<Blake2s256>,
+        vectors: &[Vector {
+            message: Message::Bytes(ABC),
+            digest: &hex!("508c5e8c327c14e2e1a72ba34eeb452f37458b209ed63a294d999b4c86675982"),
+        }],
+    },
+    // Test vectors from the BLAKE3 repository, see test_blake3()
+    Algorithm {
+        name: "BLAKE3",
+        new: boxed::<Blake3>,
+        vectors: &[
+            Vector {
+                message: Message::Bytes(b""),
+                digest: &hex!("af1349b9f5f9a1a6a0404dea36dcc9499bcb25c9adc112b7cc9a93cae41f3262"),
+            },
+            Vector {
+                message: Message::Bytes(ABC),
+                digest: &hex!("6437b3ac38465133ffb63b75273a8db548c558465d79db03fd359c6cd5bd9d85"),
+            },
+        ],
+    },
+    // RFC 1321, appendix A.5
+    Algorithm {
+        name: "MD5",
+        new: boxed::<Md5>,
+        vectors: &[
+            Vector {
+                message: Message::Bytes(b""),
+                digest: &hex!("d41d8cd98f00b204e9800998ecf8427e"),
+            },
+            Vector {
+                message: Message::Bytes(ABC),
+                digest: &hex!("900150983cd24fb0d6963f7d28e17f72"),
+            },
+        ],
+    },
+    Algorithm {
+        name: "SHA-1",
+        new: boxed::<Sha1>,
+        vectors: &[
+            // SHA1ShortMsg.rsp, Len = 8
+            Vector {
+                message: Message::Bytes(&hex!("36")),
+                digest: &hex!("c1dfd96eea8cc2b62785275bca38ac261256e278"),
+            },
+            Vector {
+                message: Message::Bytes(ABC),
+                digest: &hex!("a9993e364706816aba3e25717850c26c9cd0d89d"),
+            },
+            Vector {
+                message: Message::Bytes(TWO_BLOCK_448),
+                digest: &hex!("84983e441c3bd26ebaae4aa1f95129e5e54670f1"),
+            },
+        ],
+    },
+];
 
-    // Note that calling `finalize()` consumes hasher
-    let hash = hasher.finalize();
+fn hash(algorithm: &Algorithm, message: &Message) -> Box<[u8]> {
+    let mut hasher = (algorithm.new)();
+    match message {
+        Message::Bytes(bytes) => hasher.update(bytes),
+        Message::MillionA => {
+            let block = [b'a'; 1000];
+            for _ in 0..1000 {
+                hasher.update(&block);
+            }
+        }
+    }
+    hasher.finalize()
+}
 
-    // Print the binary hash
-    println!("SHA-256 hash: {:x}", hash);
+fn test_vectors() {
+    println!("Testing known answer vectors");
 
-    // Print each byte of the hash individually
-    print!("Hash bytes: ");
-    for byte in hash {
-        print!("{:02x}", byte);
+    for algorithm in ALGORITHMS {
+        println!(
+            "  {:<12} {} vectors",
+            algorithm.name,
+            algorithm.vectors.len()
+        );
+        for vector in algorithm.vectors {
+            let digest = hash(algorithm, &vector.message);
+            assert_eq!(digest.len(), (algorithm.new)().output_size());
+            assert_eq!(*digest, *vector.digest);
+        }
     }
-    println!();
+
+    println!("Known answer vectors passed");
+}
+
+fn test_cavp() {
+    println!("Testing CAVP SHAVS messages");
+
+    let suites: [(&str, &[Vector]); 5] = [
+        ("SHA-224", cavp::SHA224),
+        ("SHA-256", cavp::SHA256),
+        ("SHA-384", cavp::SHA384),
+        ("SHA-512", cavp::SHA512),
+        ("SHA-512/256", cavp::SHA512_256),
+    ];
+    for (name, vectors) in suites {
+        let algorithm = ALGORITHMS.iter().find(|a| a.name == name).unwrap();
+        println!("  {:<12} {} messages", name, vectors.len());
+        for vector in vectors {
+            assert_eq!(
+                *hash(algorithm, &vector.message),
+                *vector.digest,
+                "{}",
+                name
+            );
+        }
+    }
+
+    println!("CAVP SHAVS messages passed");
+}
+
+fn test_blake3() {
+    println!("Testing BLAKE3 chunk boundaries, keyed hashing and key derivation");
+
+    // test_vectors.json of the BLAKE3 repository: the input is the byte
+    // sequence 0, 1, ..., 250, 0, 1, ... and chunks are 1024 bytes long
+    let key = b"whats the Elvish word for friend";
+    let context = "BLAKE3 2019-12-27 16:29:52 test vectors context";
+    let input: Vec<u8> = (0..2049).map(|i| (i % 251) as u8).collect();
+    let cases: [(usize, [u8; 32], [u8; 32]); 4] = [
+        (
+            1,
+            hex!("2d3adedff11b61f14c886e35afa036736dcd87a74d27b5c1510225d0f592e213"),
+            hex!("6d7878dfff2f485635d39013278ae14f1454b8c0a3a2d34bc1ab38228a80c95b"),
+        ),
+        (
+            1024,
+            hex!("42214739f095a406f3fc83deb889744ac00df831c10daa55189b5d121c855af7"),
+            hex!("75c46f6f3d9eb4f55ecaaee480db732e6c2105546f1e675003687c31719c7ba4"),
+        ),
+        (
+            1025,
+            hex!("d00278ae47eb27b34faecf67b4fe263f82d5412916c1ffd97c8cb7fb814b8444"),
+            hex!("357dc55de0c7e382c900fd6e320acc04146be01db6a8ce7210b7189bd664ea69"),
+        ),
+        (
+            2049,
+            hex!("5f4d72f40d7a5f82b15ca2b2e44b1de3c2ef86c426c95c1af0b6879522563030"),
+            hex!("9f29700902f7c86e514ddc4df1e3049f258b2472b6dd5267f61bf13983b78dd5"),
+        ),
+    ];
+
+    for (len, hash, keyed_hash) in cases {
+        println!("  input_len {}", len);
+        assert_eq!(*blake3::hash(&input[..len]).as_bytes(), hash);
+        assert_eq!(
+            *blake3::keyed_hash(key, &input[..len]).as_bytes(),
+            keyed_hash
+        );
+    }
+
+    let derived = blake3::derive_key(context, &input[..1025]);
+    assert_eq!(
+        derived,
+        hex!("effaa245f065fbf82ac186839a249707c3bddf6d3fdda22d1b95a3c970379bcb")
+    );
+
+    // The default output is a prefix of the extendable output
+    let mut long = [0u8; 131];
+    let mut hasher = blake3::Hasher::new();
+    hasher.update(&input[..1025]);
+    hasher.finalize_xof().fill(&mut long);
+    assert_eq!(long[..32], cases[2].1);
+
+    println!("BLAKE3 tests passed");
+}
+
+/// xorshift64, enough to pick chunk sizes
+fn next_random(state: &mut u64) -> u64 {
+    *state ^= *state << 13;
+    *state ^= *state >> 7;
+    *state ^= *state << 17;
+    *state
+}
+
+fn test_incremental() {
+    println!("Testing incremental hashing on random chunkings");
+
+    // A fixed seed, so a failure reproduces; another one can be tried
+    // with `export SHA2_CHUNK_SEED=...` in NSH
+    let seed = std::env::var("SHA2_CHUNK_SEED")
+        .ok()
+        .and_then(|seed| u64::from_str_radix(seed.trim_start_matches("0x"), 16).ok())
+        .unwrap_or(CHUNK_SEED)
+        | 1;
+    println!("  seed {:#018x}", seed);
+    let mut state = seed;
+
+    let data: Vec<u8> = (0..4099u32).map(|i| (i * 31 % 251) as u8).collect();
+    for algorithm in ALGORITHMS {
+        let mut hasher = (algorithm.new)();
+        hasher.update(&data);
+        let one_shot = hasher.finalize_reset();
+
+        // finalize_reset() leaves the hasher as if new
+        hasher.update(&data);
+        assert_eq!(hasher.finalize_reset(), one_shot);
+
+        for _ in 0..8 {
+            // Chunk lengths of 0 up to several blocks, so updates both split
+            // and span block boundaries
+            let mut rest = &data[..];
+            while !rest.is_empty() {
+                let len = (next_random(&mut state) % 300) as usize;
+                let (chunk, tail) = rest.split_at(len.min(rest.len()));
+                hasher.update(chunk);
+                rest = tail;
+            }
+            assert_eq!(hasher.finalize_reset(), one_shot, "{}", algorithm.name);
+        }
+
+        // A clone taken mid-stream continues independently
+        hasher.update(&data[..1000]);
+        let mut clone = hasher.box_clone();
+        hasher.update(&data[1000..]);
+        clone.update(&data[1000..]);
+        assert_eq!(hasher.finalize(), one_shot);
+        assert_eq!(clone.finalize(), one_shot);
+    }
+
+    println!("Incremental hashing tests passed");
+}
+
+fn report_throughput() {
+    println!("Throughput, {} byte messages:", THROUGHPUT_BUFFER);
+
+    let buffer = vec![0x5a; THROUGHPUT_BUFFER];
+    let bytes = (THROUGHPUT_BUFFER as u32 * THROUGHPUT_ITERATIONS) as f64;
+
+    for algorithm in ALGORITHMS {
+        let start = Instant::now();
+        for _ in 0..THROUGHPUT_ITERATIONS {
+            let mut hasher = (algorithm.new)();
+            hasher.update(&buffer);
+            std::hint::black_box(hasher.finalize());
+        }
+        let elapsed = start.elapsed().as_secs_f64();
+        println!(
+            "  {:<12} {:>8.2} MB/s",
+            algorithm.name,
+            bytes / 1_000_000.0 / elapsed
+        );
+    }
+}
+
+#[unsafe(no_mangle)]
+pub fn rust_crate_test_sha2_main() {
+    println!("Running hash tests");
+
+    test_vectors();
+    test_cavp();
+    test_blake3();
+    test_incremental();
+    report_throughput();
+
+    println!("All hash tests completed");
 }
@@ -2,21 +2,46 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use std::future::Future;
-use std::pin::Pin;
-use std::task::{Context, Poll};
-use std::thread;
+use std::pin::{Pin, pin};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Wake, Waker};
+use std::thread::{self, Thread};
 use std::time::{Duration, Instant};
 
-// A simple future that resolves after a specified duration
+// State shared between the timer future and its timer thread
+struct TimerState {
+    completed: bool,
+    waker: Option<Waker>,
+}
+
+// A future that resolves after a specified duration
 struct TimerFuture {
-    expiration_time: Instant,
+    state: Arc<Mutex<TimerState>>,
 }
 
 impl TimerFuture {
     fn new(duration: Duration) -> Self {
-        Self {
-            expiration_time: Instant::now() + duration,
-        }
+        let state = Arc::new(Mutex::new(TimerState {
+            completed: false,
+            waker: None,
+        }));
+
+        // Sleep on a helper thread and wake the task once the deadline passes
+        let thread_state = state.clone();
+        thread::Builder::new()
+            .stack_size(4 * 1024)
+            .spawn(move || {
+                thread::sleep(duration);
+                let mut state = thread_state.lock().unwrap();
+                state.completed = true;
+                if let Some(waker) = state.waker.take() {
+                    waker.wake();
+                }
+            })
+            .unwrap();
+
+        Self { state }
     }
 }
 
@@ -24,16 +49,15 @@ impl Future for TimerFuture {
     type Output = ();
 
     fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
-        if Instant::now() >= self.expiration_time {
+        let mut state = self.state.lock().unwrap();
+        if state.completed {
             println!("Timer future completed!");
             Poll::Ready(())
         } else {
-            // In a real executor, we would register a waker here
-            println!("Timer future not ready yet, polling again...");
-
-            // For simplicity, we'll just sleep briefly and then manually re-poll
-            thread::sleep(Duration::from_millis(100));
-            cx.waker().wake_by_ref();
+            // Register the current waker, the timer thread will use it to
+            // notify the executor instead of being polled in a loop
+            println!("Timer future not ready yet, waiting for wakeup...");
+            state.waker = Some(cx.waker().clone());
             Poll::Pending
         }
     }
@@ -49,30 +73,47 @@ async fn async_task() {
     println!("Async task completed after waiting");
 }
 
-// Simple executor to run our future
-fn block_on<F: Future>(mut future: F) -> F::Output {
-    use std::sync::Arc;
-    use std::task::Wake;
+// Waker that unparks the thread running the executor, counting the wakes
+struct ThreadWaker {
+    thread: Thread,
+    wakes: AtomicUsize,
+}
 
-    // Create a waker that just calls wake_by_ref on itself
-    struct DummyWaker;
-    impl Wake for DummyWaker {
-        fn wake(self: Arc<Self>) {
-            // Do nothing
-        }
+impl Wake for ThreadWaker {
+    fn wake(self: Arc<Self>) {
+        self.wake_by_ref();
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        self.wakes.fetch_add(1, Ordering::Release);
+        self.thread.unpark();
     }
+}
 
-    let waker = Arc::new(DummyWaker).into();
+// Simple executor to run our future, parking the thread between polls.
+// Returns the output with the number of polls and of wakes it took
+fn block_on<F: Future>(future: F) -> (F::Output, usize, usize) {
+    let thread_waker = Arc::new(ThreadWaker {
+        thread: thread::current(),
+        wakes: AtomicUsize::new(0),
+    });
+    let waker = thread_waker.clone().into();
     let mut context = Context::from_waker(&waker);
 
-    // Pin the future and poll it repeatedly until it's ready
-    let mut pinned = unsafe { Pin::new_unchecked(&mut future) };
+    let mut future = pin!(future);
+    let mut polls = 0;
+    let mut wakes = 0;
     loop {
-        match pinned.as_mut().poll(&mut context) {
-            Poll::Ready(output) => return output,
+        polls += 1;
+        match future.as_mut().poll(&mut context) {
+            Poll::Ready(output) => return (output, polls, wakes),
+            // Sleep until a waker unparks us, parking again after a
+            // spurious wakeup instead of polling the future for nothing
             Poll::Pending => {
-                // In a real executor, we would yield the thread here
-                thread::sleep(Duration::from_millis(10));
+                while thread_waker.wakes.load(Ordering::Acquire) == wakes {
+                    thread::park();
+                }
+                wakes = thread_waker.wakes.load(Ordering::Acquire);
             }
         }
     }
@@ -83,7 +124,16 @@ pub fn rust_crate_test_std_future_main() {
     println!("=== Rust std::Future example ===");
 
     println!("Running async task using our simple executor...");
-    block_on(async_task());
+    let start = Instant::now();
+    let ((), polls, wakes) = block_on(async_task());
+    let elapsed = start.elapsed();
+
+    println!(
+        "Future polled {} times, woken {} times, in {:?}",
+        polls, wakes, elapsed
+    );
+    assert!(elapsed >= Duration::from_secs(2), "timer fired too early");
+    assert!(polls <= wakes + 1, "executor should poll once per wake");
 
     println!("Execution complete!");
 }
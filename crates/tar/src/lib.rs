@@ -0,0 +1,311 @@
+// Copyright (c) 2025 Xiaomi Corporation
+// SPDX-License-Identifier: Apache-2.0
+
+//! tar example for NuttX
+//!
+//! Extracts a firmware bundle shipped as a .tar.gz with the tar crate and as
+//! a zip with stored and deflated entries with the zip crate into /tmp,
+//! checks the contents, permissions and directory structure left behind, and
+//! makes sure entries reaching outside the destination are not written.
+
+use flate2::read::GzDecoder;
+use hex_literal::hex;
+use std::fs;
+use std::io::{Cursor, Read};
+use std::os::unix::fs::PermissionsExt;
+use std::path::Path;
+use tar::{Archive, EntryType};
+use zip::{CompressionMethod, ZipArchive};
+
+const TAR_DIR: &str = "/tmp/tar_bundle";
+const ZIP_DIR: &str = "/tmp/zip_bundle";
+
+/// Hostile archives are extracted one level down, so a `../` entry would
+/// land in a directory the test can look at
+const EVIL_ROOT: &str = "/tmp/archive_evil";
+const EVIL_DIR: &str = "/tmp/archive_evil/out";
+
+const MANIFEST: &[u8] = b"name=sensor-fw\nversion=1.4.2\n";
+const UPDATE_SCRIPT: &[u8] = b"#!/bin/sh\necho updating\n";
+const DEVICE_CONF: &[u8] = b"channel=4\nrate=250\n";
+const LOG_LINES: usize = 64;
+
+/// Python tarfile, ustar format, then gzip -9: the bundle as the build
+/// server packs it, with directories, an executable and a private file
+const BUNDLE_TAR_GZ: [u8; 662] = hex!(
+    "1f8b0800000000000203ed57cb8ee2400ccc99af60b5e7cdb4fbdd877c4c0861"
+    "409a09231266f7f3b7ed20612f9a91f6905ce8ba00565429c755eeb0bb0efbb7"
+    "fea55a122a2338479f19ff7ed277b0c13850da533d6867aaadab56c0759cda4b"
+    "be65f59cd8cdf37f6f87d3a11fa77afa332d327f6fedd7f3374ece1fc06b556d"
+    "5599ffe218daf7be19fb613c5f7e1d7e6f3efbcb783a0f0dd4b6d69baae049f2"
+    "bf3b0dcb9d01ffbdff41295df6ffdaf3bf7eecdba9afc7e3eaf337ea61ffe723"
+    "a1ecff15f0f307cd7e3c6efaee78de92074ec36b59fdcf95ff7eea16deff8fb9"
+    "ff76ff832dfb7fe5f9effbcf53d7d7dd79382cf0feffddfcf3612fe7af0142d9"
+    "ff6ba03bb6c3d0bf357673c9877fa39d2aabff09f39f4ffdf665fe1b58bf9d5f"
+    "57fdffaf0dc043fe4379ff5b0553a3b6dd516ddb7dd7a8cd840b20ff06fa6d42"
+    "2e3885054d85607321d015860a00902bf9b8be93808d58e23c101d963893d61a"
+    "4b9c4bbb84f7e75c3a792c094dc6cc2aef5c269070ce65154a379ccb5a146f38"
+    "978da8de702e07a8de702ee750bde55c2ea17acbb9bc46f59673798fea2de70a"
+    "cacc8ff4ce152caa779c2b047af09c2b02aa779c2b3a54ef39578ca8de73aea4"
+    "51bde75cc9a37acfb95242f541cc112f0ae271a579fa4c153618842134361805"
+    "91c706a33044c206a33084c106a33044c00613e7320a1b4cc210161b4cc21011"
+    "b52761084837a33247384f35d9a3a19af084515413a6f0d80108e77b65a9266c"
+    "612920c2fb3e504284f903504484fb83a38c08fb87986ee96253d0d4870840f4"
+    "d48748407e925413f6a08c83c840a298830801a839e922069aae933198af1274"
+    "d4ad8841bedf6d21309b04ea5604412bea5624415bea5644414753de5a0a0a0a"
+    "0a0a0a0a0a0a0a0a0a0a0abec65f79f836d200280000"
+);
+
+/// `../escape.txt`, `bundle/../../escape.txt` and `/abs.txt`
+const EVIL_TAR_GZ: [u8; 168] = hex!(
+    "1f8b0800000000000203edd5410ac320144551c75d4557a0dfd6e87a92c64121"
+    "34a52a74f93599b5a37660a0e41e84278e04795fad4d4c97fe1e757e66d58654"
+    "deb935abcf14b1a2ac0be7cecac92fe735a55347511b2829f78f7a15b54f73c9"
+    "e93ac683c22e0de5364ed168bdac4693e0f7fe5b1f2cfda7ff68cdf4436af8f5"
+    "7fd97ffbde7fa9fb40ffb7509f7f9e4a660000000000000000000000c09f7b01"
+    "2ae8ea3f00280000"
+);
+
+/// Python zipfile with Unix modes: directories and small files stored, the
+/// log deflated at level 9
+const BUNDLE_ZIP: [u8; 959] = hex!(
+    "504b03041400000000000000215a000000000000000000000000070000006275"
+    "6e646c652f504b03041400000000000000215a40191d251d0000001d00000013"
+    "00000062756e646c652f6d616e69666573742e7478746e616d653d73656e736f"
+    "722d66770a76657273696f6e3d312e342e320a504b0304140000000000000021"
+    "5a0000000000000000000000000b00000062756e646c652f62696e2f504b0304"
+    "1400000000000000215a066c75a718000000180000001400000062756e646c65"
+    "2f62696e2f7570646174652e736823212f62696e2f73680a6563686f20757064"
+    "6174696e670a504b03041400000008000000215a0ffcfeb85a010000c9040000"
+    "1600000062756e646c652f646174612f73656e736f722e6c6f675594cb6dc440"
+    "0c43ef5b454a184933fa1cb69860734801e91f31e505563c9a183c9363d27fcf"
+    "f5f5fa5d5fdf3fafe77afc3df5e059fad9e212ce82a02dc4be84e813d682885c"
+    "8aacf581c84e48932379204d92aa429a2c3d85f74f969643224f66b7cb0fcba2"
+    "8d4fd65eb06e93b537ccdb64ed847b9bac23706f93750edcefc93a05f77bb25c"
+    "e17e4f963bdcefc98a65f7957e58b1e1fe4c56445ffc64a5c0fd99ac3c70ef93"
+    "9509f73e59a570ef93550ef73e5955701ff41d7128e8baeafefac31502061542"
+    "113009e40898548842c0a442180226152210b026cb16021615622360512112de"
+    "8b0a21f52eea68c4f1d638a3b5469db0d51a95c29140a8f9be766b548bdd03a1"
+    "ee7bf442a8fc213d116a7f9cde08d53fb2deeb1a5f413b070d20bd73d002ae9b"
+    "6c8dead11b17da40f5cc854620eb5e3acd40fb1ccfe03e45b84e4b33b8def7fe"
+    "218c9a44a7a521e8eab4b404dd9d96a6a0698f7f504b01021403140000000000"
+    "0000215a000000000000000000000000070000000000000000001000ed410000"
+    "000062756e646c652f504b010214031400000000000000215a40191d251d0000"
+    "001d000000130000000000000000000000a4812500000062756e646c652f6d61"
+    "6e69666573742e747874504b010214031400000000000000215a000000000000"
+    "0000000000000b0000000000000000001000ed417300000062756e646c652f62"
+    "696e2f504b010214031400000000000000215a066c75a7180000001800000014"
+    "0000000000000000000000ed819c00000062756e646c652f62696e2f75706461"
+    "74652e7368504b010214031400000008000000215a0ffcfeb85a010000c90400"
+    "00160000000000000000000000a481e600000062756e646c652f646174612f73"
+    "656e736f722e6c6f67504b0506000000000500050035010000740200000000"
+);
+
+/// `bundle/ok.txt` followed by `../escape.txt`
+const EVIL_ZIP: [u8; 241] = hex!(
+    "504b03041400000000000000215af48f61fe07000000070000000d0000006275"
+    "6e646c652f6f6b2e747874696e736964650a504b03041400000000000000215a"
+    "b3ead70d08000000080000000d0000002e2e2f6573636170652e7478746f7574"
+    "736964650a504b010214031400000000000000215af48f61fe07000000070000"
+    "000d0000000000000000000000a4810000000062756e646c652f6f6b2e747874"
+    "504b010214031400000000000000215ab3ead70d08000000080000000d000000"
+    "0000000000000000a481320000002e2e2f6573636170652e747874504b050600"
+    "0000000200020076000000650000000000"
+);
+
+/// Lines like `t=500 ch2 adc=74`, the contents of bundle/data/sensor.log
+fn sensor_log(lines: usize) -> Vec<u8> {
+    (0..lines)
+        .flat_map(|i| format!("t={} ch{} adc={}\n", i * 250, i % 4, (i * 37) % 1024).into_bytes())
+        .collect()
+}
+
+/// Removes what an earlier run left behind and creates `dir` empty
+fn fresh_dir(dir: &str) {
+    let _ = fs::remove_dir_all(dir);
+    fs::create_dir_all(dir).unwrap();
+}
+
+fn check_dir(root: &str, path: &str, mode: u32) {
+    let metadata = fs::metadata(Path::new(root).join(path)).unwrap();
+    assert!(metadata.is_dir(), "{} is not a directory", path);
+    assert_eq!(metadata.permissions().mode() & 0o777, mode, "{}", path);
+}
+
+fn check_file(root: &str, path: &str, mode: u32, contents: &[u8]) {
+    let path = Path::new(root).join(path);
+    let metadata = fs::metadata(&path).unwrap();
+    assert!(metadata.is_file(), "{} is not a file", path.display());
+    assert_eq!(
+        metadata.permissions().mode() & 0o777,
+        mode,
+        "{}",
+        path.display()
+    );
+    assert_eq!(fs::read(&path).unwrap(), contents, "{}", path.display());
+}
+
+fn test_tar_listing() {
+    println!("Testing tar listing");
+
+    let mut archive = Archive::new(GzDecoder::new(&BUNDLE_TAR_GZ[..]));
+    let mut names = Vec::new();
+    for entry in archive.entries().unwrap() {
+        let entry = entry.unwrap();
+        let header = entry.header();
+        let path = entry.path().unwrap().display().to_string();
+        println!(
+            "  {:>6o} {:>6} {}",
+            header.mode().unwrap(),
+            header.size().unwrap(),
+            path
+        );
+        assert_eq!(
+            header.entry_type() == EntryType::Directory,
+            path.ends_with('/')
+        );
+        names.push(path);
+    }
+    assert_eq!(
+        names,
+        [
+            "bundle/",
+            "bundle/manifest.txt",
+            "bundle/bin/",
+            "bundle/bin/update.sh",
+            "bundle/etc/",
+            "bundle/etc/device.conf",
+            "bundle/data/sensor.log",
+        ]
+    );
+
+    println!("Tar listing tests passed");
+}
+
+fn test_tar_extract() {
+    println!("Testing tar extraction to {}", TAR_DIR);
+
+    fresh_dir(TAR_DIR);
+    Archive::new(GzDecoder::new(&BUNDLE_TAR_GZ[..]))
+        .unpack(TAR_DIR)
+        .unwrap();
+
+    check_dir(TAR_DIR, "bundle", 0o755);
+    check_dir(TAR_DIR, "bundle/bin", 0o755);
+    check_dir(TAR_DIR, "bundle/etc", 0o700);
+    check_file(TAR_DIR, "bundle/manifest.txt", 0o644, MANIFEST);
+    check_file(TAR_DIR, "bundle/bin/update.sh", 0o755, UPDATE_SCRIPT);
+    check_file(TAR_DIR, "bundle/etc/device.conf", 0o600, DEVICE_CONF);
+
+    // Not in the archive as a directory of its own, so made on the way
+    assert!(Path::new(TAR_DIR).join("bundle/data").is_dir());
+    check_file(
+        TAR_DIR,
+        "bundle/data/sensor.log",
+        0o644,
+        &sensor_log(LOG_LINES),
+    );
+
+    fs::remove_dir_all(TAR_DIR).unwrap();
+    println!("Tar extraction tests passed");
+}
+
+fn test_zip_extract() {
+    println!("Testing zip extraction to {}", ZIP_DIR);
+
+    let mut archive = ZipArchive::new(Cursor::new(&BUNDLE_ZIP[..])).unwrap();
+    for i in 0..archive.len() {
+        let file = archive.by_index(i).unwrap();
+        println!(
+            "  {:>6o} {:>6} {:>6} {:<10} {}",
+            file.unix_mode().unwrap(),
+            file.compressed_size(),
+            file.size(),
+            file.compression().to_string(),
+            file.name().unwrap()
+        );
+    }
+
+    // Single entries can be read without touching the file system
+    let mut manifest = Vec::new();
+    let mut file = archive.by_name("bundle/manifest.txt").unwrap();
+    assert_eq!(file.compression(), CompressionMethod::Stored);
+    file.read_to_end(&mut manifest).unwrap();
+    assert_eq!(manifest, MANIFEST);
+    drop(file);
+
+    let file = archive.by_name("bundle/data/sensor.log").unwrap();
+    assert_eq!(file.compression(), CompressionMethod::Deflated);
+    assert!(file.compressed_size() < file.size() / 2);
+    drop(file);
+
+    fresh_dir(ZIP_DIR);
+    archive.extract(ZIP_DIR).unwrap();
+
+    check_dir(ZIP_DIR, "bundle", 0o755);
+    check_dir(ZIP_DIR, "bundle/bin", 0o755);
+    check_file(ZIP_DIR, "bundle/manifest.txt", 0o644, MANIFEST);
+    check_file(ZIP_DIR, "bundle/bin/update.sh", 0o755, UPDATE_SCRIPT);
+    check_file(
+        ZIP_DIR,
+        "bundle/data/sensor.log",
+        0o644,
+        &sensor_log(LOG_LINES),
+    );
+
+    fs::remove_dir_all(ZIP_DIR).unwrap();
+    println!("Zip extraction tests passed");
+}
+
+fn test_path_traversal() {
+    println!("Testing path traversal entries");
+
+    let escaped = Path::new(EVIL_ROOT).join("escape.txt");
+
+    // tar skips entries with a `..` component and strips a leading `/`,
+    // as GNU tar and bsdtar do
+    fresh_dir(EVIL_DIR);
+    let mut archive = Archive::new(GzDecoder::new(&EVIL_TAR_GZ[..]));
+    for entry in archive.entries().unwrap() {
+        let mut entry = entry.unwrap();
+        let path = entry.path().unwrap().display().to_string();
+        let unpacked = entry.unpack_in(EVIL_DIR).unwrap();
+        println!(
+            "  tar {:<24} {}",
+            path,
+            if unpacked { "unpacked" } else { "skipped" }
+        );
+        assert_eq!(unpacked, !path.contains(".."));
+    }
+    assert!(!escaped.exists());
+    assert!(!Path::new("/abs.txt").exists());
+    assert_eq!(
+        fs::read(Path::new(EVIL_DIR).join("abs.txt")).unwrap(),
+        b"absolute\n"
+    );
+
+    // zip has no safe name for the entry and stops there, after writing the
+    // entries before it, so bundles belong in a staging directory
+    fresh_dir(EVIL_DIR);
+    let mut archive = ZipArchive::new(Cursor::new(&EVIL_ZIP[..])).unwrap();
+    assert!(archive.by_index(1).unwrap().enclosed_name().is_none());
+    let err = archive.extract(EVIL_DIR).unwrap_err();
+    println!("  zip {:<24} {}", "../escape.txt", err);
+    assert!(Path::new(EVIL_DIR).join("bundle/ok.txt").exists());
+    assert!(!escaped.exists());
+
+    fs::remove_dir_all(EVIL_ROOT).unwrap();
+    println!("Path traversal tests passed");
+}
+
+#[unsafe(no_mangle)]
+pub fn rust_crate_test_tar_main() {
+    println!("Running tar tests");
+
+    test_tar_listing();
+    test_tar_extract();
+    test_zip_extract();
+    test_path_traversal();
+
+    println!("All tar tests completed");
+}
@@ -0,0 +1,173 @@
+// Copyright (c) 2025 Xiaomi Corporation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Tokio `process` and `signal` example for NuttX
+//!
+//! Signals are raised with `kill()` from a separate pthread, which on NuttX
+//! delivers them to the task group of this application. Tokio installs its
+//! handlers through `sigaction()` and wakes the runtime through a self-pipe,
+//! so the listeners below exercise NuttX's signal delivery end to end.
+
+use std::io;
+use std::process::Stdio;
+use std::thread;
+
+use tokio::io::{AsyncBufReadExt, BufReader};
+use tokio::process::Command;
+use tokio::signal::unix::{SignalKind, signal};
+use tokio::time::{Duration, timeout};
+
+/// Upper bound for waiting on a signal before the test is considered failed
+const SIGNAL_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Send `signo` to this task group from another thread after `delay`
+fn kill_later(signo: libc::c_int, delay: Duration) -> thread::JoinHandle<()> {
+    thread::Builder::new()
+        .stack_size(4 * 1024)
+        .spawn(move || {
+            thread::sleep(delay);
+            let ret = unsafe { libc::kill(libc::getpid(), signo) };
+            assert_eq!(ret, 0, "kill({signo}) failed");
+        })
+        .unwrap()
+}
+
+/// Receive SIGUSR1 several times through a single listener
+async fn test_user_defined1() -> io::Result<()> {
+    println!("Testing SignalKind::user_defined1");
+
+    // Register before raising the signal, the default action would
+    // terminate the task otherwise
+    let mut usr1 = signal(SignalKind::user_defined1())?;
+
+    for round in 0..3 {
+        let killer = kill_later(libc::SIGUSR1, Duration::from_millis(50));
+        let received = timeout(SIGNAL_TIMEOUT, usr1.recv()).await;
+        killer.join().unwrap();
+
+        assert_eq!(received, Ok(Some(())), "SIGUSR1 not delivered");
+        println!("SIGUSR1 received (round {round})");
+    }
+
+    Ok(())
+}
+
+/// Receive SIGTERM, the usual request for a graceful shutdown
+async fn test_terminate() -> io::Result<()> {
+    println!("Testing SignalKind::terminate");
+
+    let mut term = signal(SignalKind::terminate())?;
+
+    let killer = kill_later(libc::SIGTERM, Duration::from_millis(50));
+    let received = timeout(SIGNAL_TIMEOUT, term.recv()).await;
+    killer.join().unwrap();
+
+    assert_eq!(received, Ok(Some(())), "SIGTERM not delivered");
+    println!("SIGTERM received");
+
+    Ok(())
+}
+
+/// Wait for SIGINT through `tokio::signal::ctrl_c`
+async fn test_ctrl_c() -> io::Result<()> {
+    println!("Testing tokio::signal::ctrl_c");
+
+    // The handler is installed on the first poll of the future, so let the
+    // spawned task run once before raising SIGINT
+    let waiter = tokio::spawn(tokio::signal::ctrl_c());
+    tokio::task::yield_now().await;
+
+    let killer = kill_later(libc::SIGINT, Duration::from_millis(50));
+    let received = timeout(SIGNAL_TIMEOUT, waiter).await;
+    killer.join().unwrap();
+
+    match received {
+        Ok(Ok(result)) => result?,
+        Ok(Err(e)) => panic!("ctrl_c task failed: {e}"),
+        Err(_) => panic!("SIGINT not delivered"),
+    }
+    println!("SIGINT received");
+
+    Ok(())
+}
+
+/// Spawn the NSH builtin and read its stdout asynchronously
+///
+/// Returns `Ok(false)` when the platform cannot spawn processes at all.
+async fn test_process_spawn() -> io::Result<bool> {
+    println!("Testing tokio::process::Command");
+
+    let child = Command::new("sh")
+        .arg("-c")
+        .arg("echo hello from nsh")
+        .stdout(Stdio::piped())
+        .spawn();
+
+    // libstd routes process creation to its unsupported backend on NuttX,
+    // report it instead of failing the signal results gathered above
+    let mut child = match child {
+        Ok(child) => child,
+        Err(e) if e.kind() == io::ErrorKind::Unsupported => {
+            println!("Process spawning is not supported: {e}");
+            return Ok(false);
+        }
+        Err(e) => return Err(e),
+    };
+
+    let stdout = child.stdout.take().expect("stdout should be piped");
+    let mut lines = BufReader::new(stdout).lines();
+
+    let mut output = Vec::new();
+    while let Some(line) = lines.next_line().await? {
+        println!("Child output: {line}");
+        output.push(line);
+    }
+
+    let status = child.wait().await?;
+    println!("Child exited with {status}");
+
+    assert!(status.success(), "child should exit successfully");
+    assert!(
+        output.iter().any(|line| line.contains("hello from nsh")),
+        "child output should be captured"
+    );
+
+    Ok(true)
+}
+
+/// Run all signal and process tests in sequence
+async fn run_tests() -> io::Result<()> {
+    test_user_defined1().await?;
+    test_terminate().await?;
+    test_ctrl_c().await?;
+    let process = test_process_spawn().await?;
+
+    println!("Summary:");
+    println!("  signal(user_defined1): supported");
+    println!("  signal(terminate):     supported");
+    println!("  ctrl_c:                supported");
+    println!(
+        "  process::Command:      {}",
+        if process { "supported" } else { "unsupported" }
+    );
+
+    Ok(())
+}
+
+#[unsafe(no_mangle)]
+pub fn rust_crate_test_tokio_process_signal_main() {
+    println!("Starting Tokio process and signal tests on NuttX");
+
+    // The signal driver needs the IO driver, enable everything
+    let rt = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .unwrap();
+
+    if let Err(e) = rt.block_on(run_tests()) {
+        eprintln!("Tokio process and signal tests failed: {e}");
+        panic!("Tokio process and signal tests failed");
+    }
+
+    println!("Tokio process and signal tests completed");
+}
@@ -3,6 +3,12 @@
 
 use serde::Deserialize;
 use serde::Serialize;
+use std::fs;
+use std::path::Path;
+use toml::value::{Datetime, Offset};
+use toml_edit::{DocumentMut, InlineTable, Item, Table, Value, value};
+
+const CONFIG_PATH: &str = "/tmp/device_config.toml";
 
 /// This is what we're going to decode into. Each field is optional, meaning
 /// that it doesn't have to be present in TOML.
@@ -126,8 +132,258 @@ fn test_toml_encode_decode() {
     }
 }
 
+/// Device configuration as a config store would keep it
+#[derive(Debug, Deserialize, Serialize, PartialEq)]
+struct DeviceConfig {
+    name: String,
+    #[serde(default)]
+    log_level: LogLevel,
+    #[serde(default = "default_interval_ms")]
+    interval_ms: u32,
+    installed: Datetime,
+    network: Network,
+    #[serde(default, rename = "sensor")]
+    sensors: Vec<Sensor>,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+enum LogLevel {
+    Error,
+    #[default]
+    Info,
+    Debug,
+}
+
+fn default_interval_ms() -> u32 {
+    1000
+}
+
+#[derive(Debug, Deserialize, Serialize, PartialEq)]
+struct Network {
+    address: String,
+    port: u16,
+}
+
+#[derive(Debug, Deserialize, Serialize, PartialEq)]
+struct Sensor {
+    name: String,
+    channels: Vec<u8>,
+}
+
+const DEVICE_TOML: &str = r#"# Device configuration
+name = "gw-01"
+installed = 2025-03-14T09:26:53.589+08:00
+network = { address = "192.168.1.10", port = 502 }
+
+# One entry per attached sensor
+[[sensor]]
+name = "temp0"
+channels = [0, 1]
+
+[[sensor]]
+name = "volt1"
+channels = [2]
+"#;
+
+fn test_serde_defaults_and_tables() {
+    println!("Testing defaults, inline tables and arrays of tables");
+
+    let config: DeviceConfig = toml::from_str(DEVICE_TOML).unwrap();
+    println!("{:?}", config);
+
+    // Missing keys take their serde defaults
+    assert_eq!(config.log_level, LogLevel::Info);
+    assert_eq!(config.interval_ms, 1000);
+
+    assert_eq!(config.network.port, 502);
+    assert_eq!(config.sensors.len(), 2);
+    assert_eq!(config.sensors[1].channels, [2]);
+
+    // Serializing writes arrays of tables back as [[sensor]] sections
+    let encoded = toml::to_string(&config).unwrap();
+    println!("Encoded:\n{}", encoded);
+    assert!(encoded.contains("[[sensor]]\nname = \"temp0\""));
+    assert_eq!(toml::from_str::<DeviceConfig>(&encoded).unwrap(), config);
+
+    println!("Defaults, inline tables and arrays of tables tests passed");
+}
+
+fn test_datetimes() {
+    println!("Testing datetimes");
+
+    let config: DeviceConfig = toml::from_str(DEVICE_TOML).unwrap();
+    let installed = config.installed;
+    let date = installed.date.unwrap();
+    let time = installed.time.unwrap();
+    assert_eq!((date.year, date.month, date.day), (2025, 3, 14));
+    assert_eq!((time.hour, time.minute, time.second), (9, 26, 53));
+    assert_eq!(time.nanosecond, 589_000_000);
+    assert_eq!(installed.offset, Some(Offset::Custom { minutes: 480 }));
+    assert_eq!(installed.to_string(), "2025-03-14T09:26:53.589+08:00");
+
+    // Local dates and times have no offset, or no time or date at all
+    let table: toml::Table = toml::from_str(
+        "utc = 1979-05-27T07:32:00Z\nday = 1979-05-27\nalarm = 07:32:00\nlocal = 1979-05-27 07:32:00",
+    )
+    .unwrap();
+    let get = |key: &str| *table[key].as_datetime().unwrap();
+    assert_eq!(get("utc").offset, Some(Offset::Z));
+    assert!(get("day").time.is_none());
+    assert!(get("alarm").date.is_none());
+    assert!(get("local").offset.is_none());
+    assert_eq!(get("local").to_string(), "1979-05-27T07:32:00");
+
+    assert!("2025-02-30".parse::<Datetime>().is_err());
+
+    println!("Datetimes tests passed");
+}
+
+fn test_error_spans() {
+    println!("Testing error spans");
+
+    // A type mismatch points at the offending value
+    let input =
+        "name = \"gw-01\"\ninstalled = 2025-03-14\nnetwork = { address = \"x\", port = 70000 }\n";
+    let err = toml::from_str::<DeviceConfig>(input).unwrap_err();
+    println!("Error (expected): {}", err);
+    let span = err.span().unwrap();
+    assert_eq!(&input[span], "70000");
+
+    // So does a syntax error
+    let input = "name = \"gw-01\"\nport = 80 80\n";
+    let err = toml::from_str::<toml::Table>(input).unwrap_err();
+    println!("Error (expected): {}", err);
+    assert_eq!(err.span().unwrap().start, 25);
+
+    // And a duplicate key, at the second definition
+    let input = "[network]\nport = 1\nport = 2\n";
+    let err = input.parse::<DocumentMut>().unwrap_err();
+    println!("Error (expected): {}", err);
+    assert!(input[err.span().unwrap().start..].starts_with("port = 2"));
+
+    println!("Error spans tests passed");
+}
+
+/// Replaces a value while keeping the whitespace and comments around it
+fn set_value(item: &mut Item, new: impl Into<Value>) {
+    let decor = item.as_value().unwrap().decor().clone();
+    let mut new = new.into();
+    *new.decor_mut() = decor;
+    *item = Item::Value(new);
+}
+
+fn test_edit_preserving_format() {
+    println!("Testing toml_edit round-trip editing");
+
+    let input = r#"# Device configuration
+name = "gw-01"   # shown in the UI
+
+[network]
+address = "192.168.1.10"
+port = 502       # Modbus/TCP
+
+# Sensors are probed in this order
+[[sensor]]
+name = "temp0"
+"#;
+    let mut doc = input.parse::<DocumentMut>().unwrap();
+
+    // Parsing and printing an untouched document gives back the same text
+    assert_eq!(doc.to_string(), input);
+
+    set_value(&mut doc["network"]["port"], 1502);
+    doc["network"]["dhcp"] = value(false);
+    doc["log_level"] = value("debug");
+    doc["network"].as_table_mut().unwrap().remove("address");
+
+    let mut sensor = Table::new();
+    sensor["name"] = value("volt1");
+    let mut limits = InlineTable::new();
+    limits.insert("min", 3000.into());
+    limits.insert("max", 3600.into());
+    sensor["limits"] = value(limits);
+    doc["sensor"].as_array_of_tables_mut().unwrap().push(sensor);
+
+    let output = doc.to_string();
+    println!("Edited:\n{}", output);
+    assert_eq!(
+        output,
+        r#"# Device configuration
+name = "gw-01"   # shown in the UI
+log_level = "debug"
+
+[network]
+port = 1502       # Modbus/TCP
+dhcp = false
+
+# Sensors are probed in this order
+[[sensor]]
+name = "temp0"
+
+[[sensor]]
+name = "volt1"
+limits = { min = 3000, max = 3600 }
+"#
+    );
+
+    // The edited text is still valid for serde
+    let table: toml::Table = toml::from_str(&output).unwrap();
+    assert_eq!(table["network"]["port"].as_integer(), Some(1502));
+
+    println!("toml_edit round-trip editing tests passed");
+}
+
+/// Loads the config, creating it with defaults on first boot
+fn load_config(path: &Path) -> DocumentMut {
+    let text = fs::read_to_string(path).unwrap_or_else(|_| {
+        "# Written by the device, comments are kept across updates\nboot_count = 0\n".to_string()
+    });
+    text.parse().unwrap()
+}
+
+/// Saves the config through a temporary file, so a reset while writing
+/// never leaves a truncated config behind
+fn save_config(path: &Path, doc: &DocumentMut) {
+    let tmp = path.with_extension("toml.tmp");
+    fs::write(&tmp, doc.to_string()).unwrap();
+    fs::rename(&tmp, path).unwrap();
+}
+
+fn test_config_store() {
+    println!("Testing config store at {}", CONFIG_PATH);
+
+    let path = Path::new(CONFIG_PATH);
+    let _ = fs::remove_file(path);
+
+    for boot in 1..=3 {
+        let mut doc = load_config(path);
+        let count = doc["boot_count"].as_integer().unwrap();
+        set_value(&mut doc["boot_count"], count + 1);
+        doc["last_boot"] = value(format!("boot {}", boot));
+        save_config(path, &doc);
+    }
+
+    let saved = fs::read_to_string(path).unwrap();
+    println!("Saved:\n{}", saved);
+    assert_eq!(
+        saved,
+        "# Written by the device, comments are kept across updates\nboot_count = 3\nlast_boot = \"boot 3\"\n"
+    );
+    assert!(!path.with_extension("toml.tmp").exists());
+
+    fs::remove_file(path).unwrap();
+
+    println!("Config store tests passed");
+}
+
 #[unsafe(no_mangle)]
 pub fn rust_crate_test_toml_main() {
     test_toml_decode();
     test_toml_encode_decode();
+    test_serde_defaults_and_tables();
+    test_datetimes();
+    test_error_spans();
+    test_edit_preserving_format();
+    test_config_store();
 }
@@ -0,0 +1,197 @@
+// Copyright (c) 2025 Xiaomi Corporation
+// SPDX-License-Identifier: Apache-2.0
+
+//! tracing example for NuttX
+//!
+//! Drives the `tracing` macros through a `tracing-subscriber` fmt layer that
+//! writes to stdout with `SystemTime` timestamps and an `EnvFilter` read from
+//! the environment. The size delta of this crate can be compared with the
+//! `std_println_minimal` entry to see what structured logging costs.
+
+use std::env;
+use std::io::{self, Write};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+use tracing::{Dispatch, Level, debug, error, info, info_span, instrument, span, trace, warn};
+use tracing_subscriber::EnvFilter;
+use tracing_subscriber::fmt::time::SystemTime;
+
+/// Filter directives exported through the environment before building the filter
+const FILTER: &str = "debug,app::noisy=warn";
+
+/// Writer that prints to stdout and keeps a copy for the assertions
+#[derive(Clone, Default)]
+struct CaptureWriter {
+    captured: Arc<Mutex<Vec<u8>>>,
+}
+
+impl CaptureWriter {
+    fn take(&self) -> String {
+        let mut captured = self.captured.lock().unwrap();
+        String::from_utf8(std::mem::take(&mut *captured)).unwrap()
+    }
+}
+
+impl Write for CaptureWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.captured.lock().unwrap().extend_from_slice(buf);
+        io::stdout().write_all(buf)?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        io::stdout().flush()
+    }
+}
+
+/// Build the fmt subscriber with the filter read from `RUST_LOG`
+fn build_dispatch(writer: CaptureWriter) -> Dispatch {
+    // Safety: no other thread is reading the environment at this point
+    unsafe { env::set_var(EnvFilter::DEFAULT_ENV, FILTER) };
+
+    let filter = EnvFilter::try_from_default_env().expect("valid filter directives");
+    println!("EnvFilter from {}: {}", EnvFilter::DEFAULT_ENV, filter);
+
+    let subscriber = tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_timer(SystemTime)
+        .with_ansi(false)
+        .with_target(true)
+        .with_thread_names(true)
+        .with_writer(move || writer.clone())
+        .finish();
+
+    Dispatch::new(subscriber)
+}
+
+#[instrument]
+fn checksum(data: &[u8], seed: u32) -> u32 {
+    let sum = data.iter().fold(seed, |acc, b| {
+        acc.wrapping_mul(31).wrapping_add(u32::from(*b))
+    });
+    debug!(sum, "checksum computed");
+    sum
+}
+
+#[instrument(skip(payload), fields(len = payload.len()))]
+fn send_packet(id: u32, payload: &[u8]) {
+    info!("packet sent");
+}
+
+fn test_events_and_fields(writer: &CaptureWriter) {
+    println!("Testing events with structured fields");
+
+    error!(code = 42, "error event");
+    warn!(retries = 3, "warning event");
+    info!(user = "nuttx", active = true, ratio = 0.5, "info event");
+    debug!(level = ?Level::DEBUG, "debug event");
+    trace!("trace event dropped by the filter");
+
+    let output = writer.take();
+    assert!(output.contains("ERROR") && output.contains("code=42"));
+    assert!(output.contains("retries=3"));
+    assert!(output.contains("user=\"nuttx\" active=true ratio=0.5"));
+    assert!(output.contains("level=Level(Debug)") && output.contains("debug event"));
+    assert!(!output.contains("trace event"));
+
+    // SystemTime prints RFC 3339 timestamps such as 1970-01-01T00:00:05.123456Z
+    for line in output.lines() {
+        let timestamp = line.split_whitespace().next().unwrap();
+        assert!(
+            timestamp.len() > 20 && timestamp.as_bytes()[4] == b'-' && timestamp.ends_with('Z'),
+            "unexpected timestamp in {line:?}"
+        );
+    }
+
+    println!("Events and fields tests passed");
+}
+
+fn test_env_filter(writer: &CaptureWriter) {
+    println!("Testing EnvFilter directives");
+
+    info!(target: "app::noisy", "info dropped for app::noisy");
+    warn!(target: "app::noisy", "warn kept for app::noisy");
+    debug!(target: "app::sensor", "debug kept for app::sensor");
+
+    let output = writer.take();
+    assert!(!output.contains("info dropped"));
+    assert!(output.contains("app::noisy: warn kept"));
+    assert!(output.contains("app::sensor: debug kept"));
+
+    println!("EnvFilter tests passed");
+}
+
+fn test_instrument(writer: &CaptureWriter) {
+    println!("Testing #[instrument]");
+
+    let sum = checksum(b"nuttx", 7);
+    send_packet(1, &[0u8; 16]);
+
+    let output = writer.take();
+    assert!(output.contains("checksum{data=[110, 117, 116, 116, 120] seed=7}"));
+    assert!(output.contains(&format!("sum={}", sum)));
+    assert!(output.contains("send_packet{id=1 len=16}"));
+    assert!(!output.contains("payload"));
+
+    println!("#[instrument] tests passed");
+}
+
+fn test_nested_spans_across_threads(dispatch: &Dispatch, writer: &CaptureWriter) {
+    println!("Testing nested spans across threads");
+
+    let request = span!(Level::INFO, "request", id = 7);
+    let _request = request.enter();
+    let stage = info_span!("stage", name = "parse");
+
+    let handles = (0..2)
+        .map(|worker| {
+            let dispatch = dispatch.clone();
+            let parent = stage.clone();
+            thread::Builder::new()
+                .name(format!("worker{worker}"))
+                .stack_size(8 * 1024)
+                .spawn(move || {
+                    // The default subscriber is per thread, hand it over
+                    tracing::dispatcher::with_default(&dispatch, || {
+                        let span = info_span!(parent: &parent, "job", worker);
+                        span.in_scope(|| info!("job done"));
+                    });
+                })
+                .unwrap()
+        })
+        .collect::<Vec<_>>();
+
+    for handle in handles {
+        handle.join().unwrap();
+    }
+
+    stage.in_scope(|| info!("stage done"));
+
+    let output = writer.take();
+    for worker in 0..2 {
+        assert!(output.contains(&format!(
+            "request{{id=7}}:stage{{name=\"parse\"}}:job{{worker={worker}}}: tracing: job done"
+        )));
+    }
+    assert!(output.contains("request{id=7}:stage{name=\"parse\"}: tracing: stage done"));
+
+    println!("Nested span tests passed");
+}
+
+#[unsafe(no_mangle)]
+pub fn rust_crate_test_tracing_main() {
+    println!("Running tracing tests");
+
+    let writer = CaptureWriter::default();
+    let dispatch = build_dispatch(writer.clone());
+
+    tracing::dispatcher::with_default(&dispatch, || {
+        test_events_and_fields(&writer);
+        test_env_filter(&writer);
+        test_instrument(&writer);
+        test_nested_spans_across_threads(&dispatch, &writer);
+    });
+
+    println!("All tracing tests completed");
+}
@@ -0,0 +1,180 @@
+// Copyright (c) 2025 Xiaomi Corporation
+// SPDX-License-Identifier: Apache-2.0
+
+//! x25519-dalek example for NuttX
+//!
+//! Checks X25519 against the test vectors of RFC 7748, rejects low order
+//! peer keys, runs an ephemeral key exchange seeded from /dev/urandom and
+//! measures key generation and Diffie-Hellman time.
+
+use hex_literal::hex;
+use rand_core::OsRng;
+use std::fs::File;
+use std::io::Read;
+use std::num::NonZeroU32;
+use std::time::Instant;
+use x25519_dalek::{EphemeralSecret, PublicKey, StaticSecret, X25519_BASEPOINT_BYTES, x25519};
+
+const TIMING_ITERATIONS: u32 = 4;
+
+/// getrandom 0.2 has no NuttX backend, so entropy comes from /dev/urandom.
+/// The same backend is copied unchanged into ed25519_dalek, x25519_dalek,
+/// p256 and rustls
+fn dev_urandom(buf: &mut [u8]) -> Result<(), getrandom::Error> {
+    File::open("/dev/urandom")
+        .and_then(|mut file| file.read_exact(buf))
+        .map_err(|err| {
+            err.raw_os_error()
+                .and_then(|code| NonZeroU32::new(code as u32))
+                .map_or(getrandom::Error::UNEXPECTED, getrandom::Error::from)
+        })
+}
+
+getrandom::register_custom_getrandom!(dev_urandom);
+
+struct TestCase {
+    tc_id: u32,
+    comment: &'static str,
+    scalar: [u8; 32],
+    u: [u8; 32],
+    shared: [u8; 32],
+}
+
+/// Laid out like the Wycheproof x25519_test.json groups
+const TEST_CASES: &[TestCase] = &[
+    TestCase {
+        tc_id: 1,
+        comment: "RFC 7748 section 5.2",
+        scalar: hex!("a546e36bf0527c9d3b16154b82465edd62144c0ac1fc5a18506a2244ba449ac4"),
+        u: hex!("e6db6867583030db3594c1a424b15f7c726624ec26b3353b10a903a6d0ab1c4c"),
+        shared: hex!("c3da55379de9c6908e94ea4df28d084f32eccf03491c71f754b4075577a28552"),
+    },
+    TestCase {
+        tc_id: 2,
+        comment: "RFC 7748 one iteration",
+        scalar: X25519_BASEPOINT_BYTES,
+        u: X25519_BASEPOINT_BYTES,
+        shared: hex!("422c8e7a6227d7bca1350b3e2bb7279f7897b87bb6854b783c60e80311ae3079"),
+    },
+    TestCase {
+        tc_id: 3,
+        comment: "public key u = 0",
+        scalar: hex!("a546e36bf0527c9d3b16154b82465edd62144c0ac1fc5a18506a2244ba449ac4"),
+        u: [0; 32],
+        shared: [0; 32],
+    },
+    TestCase {
+        tc_id: 4,
+        comment: "public key u = 1",
+        scalar: hex!("a546e36bf0527c9d3b16154b82465edd62144c0ac1fc5a18506a2244ba449ac4"),
+        u: hex!("0100000000000000000000000000000000000000000000000000000000000000"),
+        shared: [0; 32],
+    },
+];
+
+/// RFC 7748, section 6.1
+const ALICE_SECRET: [u8; 32] =
+    hex!("77076d0a7318a57d3c16c17251b26645df4c2f87ebc0992ab177fba51db92c2a");
+const ALICE_PUBLIC: [u8; 32] =
+    hex!("8520f0098930a754748b7ddcb43ef75a0dbf3a0d26381af4eba4a98eaa9b4e6a");
+const BOB_SECRET: [u8; 32] =
+    hex!("5dab087e624a8a4b79e17f8b83800ee66f3bb1292618b6fd1c2f8b27ff88e0eb");
+const BOB_PUBLIC: [u8; 32] =
+    hex!("de9edb7d7b7dc1b4d35b61c2ece435373f8343c85b78674dadfc7e146f882b4f");
+const SHARED: [u8; 32] = hex!("4a5d9d5ba4ce2de1728e3bf480350f25e07e21c947d19e3376f09b3c1e161742");
+
+fn test_vectors() {
+    println!("Testing scalar multiplication vectors");
+
+    for case in TEST_CASES {
+        println!("  tcId {} {}", case.tc_id, case.comment);
+        assert_eq!(x25519(case.scalar, case.u), case.shared);
+    }
+
+    println!("Scalar multiplication vectors passed");
+}
+
+fn test_rfc7748_exchange() {
+    println!("Testing RFC 7748 Diffie-Hellman");
+
+    let alice = StaticSecret::from(ALICE_SECRET);
+    let bob = StaticSecret::from(BOB_SECRET);
+    assert_eq!(PublicKey::from(&alice).to_bytes(), ALICE_PUBLIC);
+    assert_eq!(PublicKey::from(&bob).to_bytes(), BOB_PUBLIC);
+
+    let alice_shared = alice.diffie_hellman(&PublicKey::from(BOB_PUBLIC));
+    let bob_shared = bob.diffie_hellman(&PublicKey::from(ALICE_PUBLIC));
+    assert_eq!(alice_shared.to_bytes(), SHARED);
+    assert_eq!(bob_shared.to_bytes(), SHARED);
+    assert!(alice_shared.was_contributory());
+
+    println!("RFC 7748 Diffie-Hellman passed");
+}
+
+fn test_low_order_peer() {
+    println!("Testing low order peer keys");
+
+    // A peer sending a low order point forces the all zero shared secret,
+    // which was_contributory() detects before the secret is used as a key
+    let secret = StaticSecret::from(ALICE_SECRET);
+    for case in &TEST_CASES[2..] {
+        let shared = secret.diffie_hellman(&PublicKey::from(case.u));
+        assert_eq!(shared.to_bytes(), [0; 32]);
+        assert!(!shared.was_contributory());
+    }
+
+    println!("Low order peer key tests passed");
+}
+
+fn test_ephemeral_exchange() {
+    println!("Testing ephemeral key exchange from /dev/urandom");
+
+    let device = EphemeralSecret::random_from_rng(OsRng);
+    let device_public = PublicKey::from(&device);
+    let server = EphemeralSecret::random_from_rng(OsRng);
+    let server_public = PublicKey::from(&server);
+    assert_ne!(device_public, server_public);
+
+    // Ephemeral secrets are consumed by the exchange
+    let device_shared = device.diffie_hellman(&server_public);
+    let server_shared = server.diffie_hellman(&device_public);
+    assert_eq!(device_shared.as_bytes(), server_shared.as_bytes());
+    assert!(device_shared.was_contributory());
+
+    println!("Ephemeral key exchange tests passed");
+}
+
+fn report_timing() {
+    println!("Timing, {} iterations:", TIMING_ITERATIONS);
+
+    let start = Instant::now();
+    for _ in 0..TIMING_ITERATIONS {
+        let secret = StaticSecret::random_from_rng(OsRng);
+        std::hint::black_box(PublicKey::from(&secret));
+    }
+    let keygen = start.elapsed() / TIMING_ITERATIONS;
+
+    let secret = StaticSecret::from(ALICE_SECRET);
+    let peer = PublicKey::from(BOB_PUBLIC);
+    let start = Instant::now();
+    for _ in 0..TIMING_ITERATIONS {
+        std::hint::black_box(secret.diffie_hellman(&peer));
+    }
+    let exchange = start.elapsed() / TIMING_ITERATIONS;
+
+    println!("  {:<8} {:>8} us", "keygen", keygen.as_micros());
+    println!("  {:<8} {:>8} us", "exchange", exchange.as_micros());
+}
+
+#[unsafe(no_mangle)]
+pub fn rust_crate_test_x25519_dalek_main() {
+    println!("Running x25519-dalek tests");
+
+    test_vectors();
+    test_rfc7748_exchange();
+    test_low_order_peer();
+    test_ephemeral_exchange();
+    report_timing();
+
+    println!("All x25519-dalek tests completed");
+}